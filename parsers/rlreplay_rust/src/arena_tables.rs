@@ -9,9 +9,21 @@
 ///   "orange" — pad is in the orange team's half (y > 2000 approximately)
 ///   "mid"    — pad is in the midfield zone (abs(y) <= 2000)
 ///
-/// Snap tolerances (default):
+/// Snap tolerances (default), horizontal from the pad centre:
 ///   big pads:   200 uu
 ///   small pads: 160 uu
+/// plus a separate 100 uu vertical tolerance, so a pad sitting higher than its table entry
+/// (raised floor geometry, or the 70 / 73 uu z variants between tables) still snaps.
+///
+/// Pad respawn durations are per arena (`pad_respawn_for_slug`); Soccar uses 10 s for big
+/// pads and 4 s for small pads.
+///
+/// Kickoff spawns (`kickoff_spawns_for_slug`) are listed for the blue team; orange spawns
+/// are the same points turned half a turn (x and y negated). Left / right are from the
+/// spawning player's point of view, facing the opponent goal: +x is blue's left.
+///
+/// Goal centres (`goal_centers_for_slug`) are the (x, y) midpoints of each goal line, blue
+/// goal (defended by team 0, at -y) first.
 
 #[derive(Clone, Copy, Debug)]
 pub struct ArenaPadDef {
@@ -30,9 +42,11 @@ pub struct SnapResult {
     pub snap_error_uu: f32,
 }
 
-/// Default snap tolerances in uu.
+/// Default horizontal snap tolerances in uu.
 pub const SNAP_TOLERANCE_BIG_UU: f32 = 200.0;
 pub const SNAP_TOLERANCE_SMALL_UU: f32 = 160.0;
+/// Default vertical snap tolerance in uu, checked separately from the horizontal one.
+pub const SNAP_TOLERANCE_Z_UU: f32 = 100.0;
 
 fn distance_3d(ax: f32, ay: f32, az: f32, b: &ArenaPadDef) -> f32 {
     let dx = ax - b.x;
@@ -41,12 +55,17 @@ fn distance_3d(ax: f32, ay: f32, az: f32, b: &ArenaPadDef) -> f32 {
     (dx * dx + dy * dy + dz * dz).sqrt()
 }
 
-/// Try to snap an observed pad position to the nearest canonical pad definition.
+/// Try to snap an observed pad position to the nearest canonical pad definition, matching
+/// in xy: a candidate must be within the pad's horizontal tolerance and within
+/// `SNAP_TOLERANCE_Z_UU` vertically, and the horizontally nearest one wins.
 /// Returns `None` if no candidate is within the snap tolerance.
 pub fn snap_to_pad(pads: &[ArenaPadDef], x: f32, y: f32, z: f32) -> Option<SnapResult> {
     let mut best: Option<(f32, usize)> = None;
     for (idx, pad) in pads.iter().enumerate() {
-        let dist = distance_3d(x, y, z, pad);
+        if (z - pad.z).abs() > SNAP_TOLERANCE_Z_UU {
+            continue;
+        }
+        let dist = (x - pad.x).hypot(y - pad.y);
         let tolerance = if pad.is_big {
             SNAP_TOLERANCE_BIG_UU
         } else {
@@ -60,12 +79,91 @@ pub fn snap_to_pad(pads: &[ArenaPadDef], x: f32, y: f32, z: f32) -> Option<SnapR
             }
         }
     }
-    best.map(|(dist, idx)| SnapResult {
+    best.map(|(_, idx)| SnapResult {
         pad_def: pads[idx],
-        snap_error_uu: dist,
+        snap_error_uu: distance_3d(x, y, z, &pads[idx]),
     })
 }
 
+/// Pickup cylinder radii in uu (horizontal distance from the pad centre to the car).
+pub const PAD_OVERLAP_BIG_UU: f32 = 208.0;
+pub const PAD_OVERLAP_SMALL_UU: f32 = 144.0;
+/// Pickup cylinder height in uu; cars above this are airborne over the pad, not on it.
+pub const PAD_OVERLAP_HEIGHT_UU: f32 = 168.0;
+
+/// The pad whose pickup cylinder contains the car position, nearest first.
+/// Returns `None` when the car is not overlapping any pad.
+pub fn overlapping_pad(pads: &[ArenaPadDef], x: f32, y: f32, z: f32) -> Option<&ArenaPadDef> {
+    if z > PAD_OVERLAP_HEIGHT_UU {
+        return None;
+    }
+    let mut best: Option<(f32, &ArenaPadDef)> = None;
+    for pad in pads {
+        let dist = ((x - pad.x).powi(2) + (y - pad.y).powi(2)).sqrt();
+        let radius = if pad.is_big {
+            PAD_OVERLAP_BIG_UU
+        } else {
+            PAD_OVERLAP_SMALL_UU
+        };
+        if dist <= radius && best.is_none_or(|(best_dist, _)| dist < best_dist) {
+            best = Some((dist, pad));
+        }
+    }
+    best.map(|(_, pad)| pad)
+}
+
+/// Seconds a pad stays unavailable after being collected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PadRespawnTimes {
+    pub big_s: f32,
+    pub small_s: f32,
+}
+
+impl PadRespawnTimes {
+    pub fn for_pad(&self, is_big: bool) -> f32 {
+        if is_big {
+            self.big_s
+        } else {
+            self.small_s
+        }
+    }
+}
+
+pub const SOCCAR_PAD_RESPAWN: PadRespawnTimes = PadRespawnTimes {
+    big_s: 10.0,
+    small_s: 4.0,
+};
+
+/// The five standard kickoff spawn positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KickoffPosition {
+    DiagonalLeft,
+    DiagonalRight,
+    OffsetLeft,
+    OffsetRight,
+    Back,
+}
+
+impl KickoffPosition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KickoffPosition::DiagonalLeft => "diagonal_left",
+            KickoffPosition::DiagonalRight => "diagonal_right",
+            KickoffPosition::OffsetLeft => "offset_left",
+            KickoffPosition::OffsetRight => "offset_right",
+            KickoffPosition::Back => "back",
+        }
+    }
+}
+
+/// A canonical kickoff spawn point on the blue half.
+#[derive(Clone, Copy, Debug)]
+pub struct KickoffSpawn {
+    pub x: f32,
+    pub y: f32,
+    pub position: KickoffPosition,
+}
+
 /// Map a raw map name (as reported in the replay header) to a canonical arena slug
 /// used internally for table lookup. Returns `None` for unsupported arena types.
 pub fn lookup_arena_slug(map_name: &str) -> Option<&'static str> {
@@ -87,6 +185,64 @@ pub fn pad_table_for_slug(slug: &str) -> Option<&'static [ArenaPadDef]> {
     }
 }
 
+/// Return the pad respawn durations for a given arena slug.
+/// Currently only "soccar" is supported.
+pub fn pad_respawn_for_slug(slug: &str) -> Option<PadRespawnTimes> {
+    match slug {
+        "soccar" => Some(SOCCAR_PAD_RESPAWN),
+        _ => None,
+    }
+}
+
+/// Return the blue-side kickoff spawns for a given arena slug.
+/// Currently only "soccar" is supported.
+pub fn kickoff_spawns_for_slug(slug: &str) -> Option<&'static [KickoffSpawn]> {
+    match slug {
+        "soccar" => Some(SOCCAR_KICKOFF_SPAWNS),
+        _ => None,
+    }
+}
+
+/// Return the (x, y) goal line centres for a given arena slug, blue goal first.
+/// Currently only "soccar" is supported.
+pub fn goal_centers_for_slug(slug: &str) -> Option<[(f32, f32); 2]> {
+    match slug {
+        "soccar" => Some(SOCCAR_GOAL_CENTERS),
+        _ => None,
+    }
+}
+
+pub const SOCCAR_GOAL_CENTERS: [(f32, f32); 2] = [(0.0, -5120.0), (0.0, 5120.0)];
+
+/// Blue-side kickoff spawns shared by all standard Soccar arenas.
+pub static SOCCAR_KICKOFF_SPAWNS: &[KickoffSpawn] = &[
+    KickoffSpawn {
+        x: 2048.0,
+        y: -2560.0,
+        position: KickoffPosition::DiagonalLeft,
+    },
+    KickoffSpawn {
+        x: -2048.0,
+        y: -2560.0,
+        position: KickoffPosition::DiagonalRight,
+    },
+    KickoffSpawn {
+        x: 256.0,
+        y: -3840.0,
+        position: KickoffPosition::OffsetLeft,
+    },
+    KickoffSpawn {
+        x: -256.0,
+        y: -3840.0,
+        position: KickoffPosition::OffsetRight,
+    },
+    KickoffSpawn {
+        x: 0.0,
+        y: -4608.0,
+        position: KickoffPosition::Back,
+    },
+];
+
 /// Canonical pad table for all standard Soccar arenas.
 /// Covers: DFH Stadium, Champions Field, Mannfield, Beckwith Park, Urban Central,
 /// Utopia Coliseum, Wasteland, Neo Tokyo, Aqua Dome, Farmstead, Sunset Stadium,
@@ -185,6 +341,17 @@ mod tests {
         assert!(r.snap_error_uu < SNAP_TOLERANCE_BIG_UU);
     }
 
+    #[test]
+    fn test_snap_raised_pad() {
+        // Small pad 33 at (0, 4240, 70), seen 40 uu higher and 155 uu off in x: over the
+        // 160 uu tolerance in 3D, but within it horizontally and within the z tolerance.
+        let r = snap_to_pad(SOCCAR_PADS, 155.0, 4240.0, 110.0).unwrap();
+        assert_eq!(r.pad_def.id, 33);
+        assert!(r.snap_error_uu > SNAP_TOLERANCE_SMALL_UU);
+        // Directly above it, but too high to be the pad.
+        assert!(snap_to_pad(SOCCAR_PADS, 0.0, 4240.0, 70.0 + 150.0).is_none());
+    }
+
     #[test]
     fn test_snap_outside_tolerance() {
         // Far from any pad
@@ -192,6 +359,21 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_overlapping_pad() {
+        // Big pad 0 at (-3584, -4096): inside the big radius, outside the small one.
+        let pad = overlapping_pad(SOCCAR_PADS, -3584.0 + 180.0, -4096.0, 17.0);
+        assert_eq!(pad.map(|p| p.id), Some(0));
+        // Airborne over the pad.
+        assert!(overlapping_pad(SOCCAR_PADS, -3584.0, -4096.0, 400.0).is_none());
+        // Small pad 33 at (0, 4240): 180 uu away is outside its radius.
+        assert!(overlapping_pad(SOCCAR_PADS, 180.0, 4240.0, 17.0).is_none());
+        assert_eq!(
+            overlapping_pad(SOCCAR_PADS, 100.0, 4240.0, 17.0).map(|p| p.id),
+            Some(33)
+        );
+    }
+
     #[test]
     fn test_lookup_arena_slug_standard() {
         assert_eq!(lookup_arena_slug("DFHStadium"), Some("soccar"));
@@ -205,4 +387,12 @@ mod tests {
         assert_eq!(lookup_arena_slug("HoopsStadium_P"), None);
         assert_eq!(lookup_arena_slug("Dropshot_P"), None);
     }
+
+    #[test]
+    fn test_pad_respawn_for_slug() {
+        let soccar = pad_respawn_for_slug("soccar").unwrap();
+        assert_eq!(soccar.for_pad(true), 10.0);
+        assert_eq!(soccar.for_pad(false), 4.0);
+        assert_eq!(pad_respawn_for_slug("unknown"), None);
+    }
 }
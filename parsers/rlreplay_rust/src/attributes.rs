@@ -0,0 +1,98 @@
+/// Attribute variant names for `iter_attribute`, which taps one boxcars `Attribute`
+/// variant out of the network stream without building frames.
+///
+/// Names are the boxcars 0.10 variant names ("ReplicatedBoost", "RigidBody", ...), matched
+/// case-sensitively.
+use boxcars::Attribute;
+
+/// Defines `ATTRIBUTE_NAMES` and `attribute_name` from one variant list, so the two cannot
+/// drift apart; the match is exhaustive, so a new boxcars variant fails to compile here.
+macro_rules! attribute_variants {
+    ($($variant:ident),* $(,)?) => {
+        pub const ATTRIBUTE_NAMES: &[&str] = &[$(stringify!($variant)),*];
+
+        /// The boxcars variant name of `attribute`.
+        pub fn attribute_name(attribute: &Attribute) -> &'static str {
+            match attribute {
+                $(Attribute::$variant { .. } => stringify!($variant),)*
+            }
+        }
+    };
+}
+
+attribute_variants!(
+    Boolean,
+    Byte,
+    AppliedDamage,
+    DamageState,
+    CamSettings,
+    ClubColors,
+    Demolish,
+    DemolishExtended,
+    DemolishFx,
+    Enum,
+    Explosion,
+    ExtendedExplosion,
+    FlaggedByte,
+    ActiveActor,
+    Float,
+    GameMode,
+    Int,
+    Int64,
+    Loadout,
+    TeamLoadout,
+    Location,
+    MusicStinger,
+    PlayerHistoryKey,
+    Pickup,
+    PickupNew,
+    QWord,
+    Welded,
+    Title,
+    TeamPaint,
+    RigidBody,
+    String,
+    UniqueId,
+    Reservation,
+    PartyLeader,
+    PrivateMatch,
+    LoadoutOnline,
+    LoadoutsOnline,
+    StatEvent,
+    Rotation,
+    RepStatTitle,
+    PickupInfo,
+    Impulse,
+    ReplicatedBoost,
+    LogoData,
+);
+
+pub fn is_attribute_name(name: &str) -> bool {
+    ATTRIBUTE_NAMES.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boxcars::{ReplicatedBoost, Vector3f};
+
+    #[test]
+    fn test_attribute_names() {
+        let boost = Attribute::ReplicatedBoost(ReplicatedBoost {
+            grant_count: 0,
+            boost_amount: 85,
+            unused1: 0,
+            unused2: 0,
+        });
+        let location = Attribute::Location(Vector3f {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        });
+        for attribute in [boost, location, Attribute::Int(3), Attribute::Boolean(true)] {
+            assert!(is_attribute_name(attribute_name(&attribute)));
+        }
+        assert_eq!(attribute_name(&Attribute::Int(3)), "Int");
+        assert!(!is_attribute_name("replicatedboost"));
+    }
+}
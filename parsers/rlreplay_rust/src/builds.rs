@@ -0,0 +1,92 @@
+/// Attribute support hints derived from the header `BuildVersion`, so callers can pick an
+/// analysis path before decoding the network stream.
+///
+/// `BuildVersion` looks like "250811.43331.492665"; the leading YYMMDD date is compared
+/// against the first build known to replicate each attribute:
+///
+///   capability                first build   notes
+///   has_double_jump           160223        CarComponent_DoubleJump_TA active state (v1.13)
+///   has_rigid_body_rotation   180905        quaternion RigidBody rotation, net version 7 (v1.50)
+///   uses_pickup_new           201208        ReplicatedPickupData replaced by PickupNew (v1.88)
+///
+/// Dates are approximate patch dates, so replays recorded within days of a threshold may be
+/// misclassified. Missing or unparseable build versions report every capability as present,
+/// as do builds newer than the whole table.
+use crate::header::find_prop;
+use boxcars::HeaderProp;
+
+pub const DOUBLE_JUMP_BUILD: u32 = 160223;
+pub const RIGID_BODY_ROTATION_BUILD: u32 = 180905;
+pub const PICKUP_NEW_BUILD: u32 = 201208;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BuildCapabilities {
+    pub has_double_jump: bool,
+    pub has_rigid_body_rotation: bool,
+    pub uses_pickup_new: bool,
+}
+
+impl BuildCapabilities {
+    pub const ALL: BuildCapabilities = BuildCapabilities {
+        has_double_jump: true,
+        has_rigid_body_rotation: true,
+        uses_pickup_new: true,
+    };
+
+    /// Capabilities for a `BuildVersion` string; `ALL` when it has no YYMMDD prefix.
+    pub fn from_build_version(build_version: Option<&str>) -> Self {
+        let Some(date) = build_version.and_then(build_date) else {
+            return Self::ALL;
+        };
+        BuildCapabilities {
+            has_double_jump: date >= DOUBLE_JUMP_BUILD,
+            has_rigid_body_rotation: date >= RIGID_BODY_ROTATION_BUILD,
+            uses_pickup_new: date >= PICKUP_NEW_BUILD,
+        }
+    }
+}
+
+pub fn header_build_version(properties: &[(String, HeaderProp)]) -> Option<&str> {
+    find_prop(properties, "BuildVersion").and_then(|p| p.as_string())
+}
+
+/// The YYMMDD date prefix of a build version, e.g. 250811 for "250811.43331.492665".
+pub fn build_date(build_version: &str) -> Option<u32> {
+    let prefix = build_version.split('.').next()?;
+    if prefix.len() != 6 || !prefix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    prefix.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_date() {
+        assert_eq!(build_date("250811.43331.492665"), Some(250811));
+        assert_eq!(build_date("250811"), Some(250811));
+        assert_eq!(build_date("v1.88"), None);
+        assert_eq!(build_date(""), None);
+    }
+
+    #[test]
+    fn test_capability_thresholds() {
+        let old = BuildCapabilities::from_build_version(Some("170101.1.1"));
+        assert!(old.has_double_jump);
+        assert!(!old.has_rigid_body_rotation);
+        assert!(!old.uses_pickup_new);
+
+        let current = BuildCapabilities::from_build_version(Some("250811.43331.492665"));
+        assert_eq!(current, BuildCapabilities::ALL);
+        assert_eq!(
+            BuildCapabilities::from_build_version(None),
+            BuildCapabilities::ALL
+        );
+        assert_eq!(
+            BuildCapabilities::from_build_version(Some("unknown")),
+            BuildCapabilities::ALL
+        );
+    }
+}
@@ -0,0 +1,161 @@
+/// Car-on-car bump detection (contacts that are not demolitions).
+///
+/// A bump is registered on a live frame when a car's velocity changes by at least
+/// `BUMP_MIN_VELOCITY_DELTA_UU_S` since the previous frame while another non-demolished car
+/// is within `BUMP_DISTANCE_UU` (centre to centre); the nearest such car is the bumper.
+/// Cars jumping or dodging on the frame are skipped (a dodge impulse is ~500 uu/s), as are
+/// cars involved in a demolition on the frame. Each car is judged on its own, so a mutual
+/// bump where both cars are knocked yields one bump in each direction. A repeat of the same
+/// (bumper, bumped) pair within `BUMP_COOLDOWN_S` is the same contact and is dropped.
+use crate::geometry::{distance, magnitude};
+use crate::network::FrameSnapshot;
+use std::collections::HashMap;
+
+/// Max car-centre to car-centre distance (uu) for a velocity change to count as a bump.
+pub const BUMP_DISTANCE_UU: f32 = 250.0;
+/// Min frame-to-frame car velocity change (uu/s) treated as a bump impulse.
+pub const BUMP_MIN_VELOCITY_DELTA_UU_S: f32 = 600.0;
+/// Seconds during which the same bumper / bumped pair is not reported again.
+pub const BUMP_COOLDOWN_S: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bump {
+    pub timestamp: f32,
+    pub bumper_slot: usize,
+    pub bumped_slot: usize,
+    /// Change in the bumped car's velocity (uu/s) since the previous frame.
+    pub velocity_delta: (f32, f32, f32),
+}
+
+#[derive(Default)]
+pub struct BumpDetector {
+    prev_velocity: HashMap<usize, (f32, f32, f32)>,
+    /// (bumper, bumped) → timestamp of the last reported bump.
+    last_bump: HashMap<(usize, usize), f32>,
+}
+
+impl BumpDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame; returns the bumps registered on it, in bumped-slot order.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> Vec<Bump> {
+        let prev_velocity = std::mem::take(&mut self.prev_velocity);
+        for player in frame.players.iter().filter(|p| !p.is_demolished) {
+            self.prev_velocity.insert(player.slot, player.velocity);
+        }
+        if !frame.is_live {
+            return Vec::new();
+        }
+        let in_demolition = |slot: usize| {
+            frame
+                .demolitions
+                .iter()
+                .any(|demo| demo.attacker_slot == Some(slot) || demo.victim_slot == Some(slot))
+        };
+
+        let mut bumps = Vec::new();
+        for bumped in frame.players.iter().filter(|p| !p.is_demolished) {
+            if bumped.is_jumping || bumped.is_dodging || in_demolition(bumped.slot) {
+                continue;
+            }
+            let Some(prev) = prev_velocity.get(&bumped.slot) else {
+                continue;
+            };
+            let v = bumped.velocity;
+            let delta = (v.0 - prev.0, v.1 - prev.1, v.2 - prev.2);
+            if magnitude(delta) < BUMP_MIN_VELOCITY_DELTA_UU_S {
+                continue;
+            }
+            let bumper = frame
+                .players
+                .iter()
+                .filter(|p| p.slot != bumped.slot && !p.is_demolished && !in_demolition(p.slot))
+                .map(|p| (p.slot, distance(p.position, bumped.position)))
+                .filter(|(_, d)| *d <= BUMP_DISTANCE_UU)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            let Some((bumper_slot, _)) = bumper else {
+                continue;
+            };
+            let pair = (bumper_slot, bumped.slot);
+            if let Some(last) = self.last_bump.get(&pair) {
+                if frame.timestamp - last < BUMP_COOLDOWN_S {
+                    continue;
+                }
+            }
+            self.last_bump.insert(pair, frame.timestamp);
+            bumps.push(Bump {
+                timestamp: frame.timestamp,
+                bumper_slot,
+                bumped_slot: bumped.slot,
+                velocity_delta: delta,
+            });
+        }
+        bumps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{frame, player};
+    use crate::network::{FrameDemolition, PlayerSnapshot};
+
+    fn car(slot: usize, x: f32, vx: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            team: slot as i64 % 2,
+            position: (x, 0.0, 17.0),
+            velocity: (vx, 0.0, 0.0),
+            ..player(slot)
+        }
+    }
+
+    #[test]
+    fn test_one_sided_bump() {
+        let mut bumps = BumpDetector::new();
+        assert!(bumps
+            .observe(&frame(1.0, vec![car(0, 0.0, 1500.0), car(1, 200.0, 0.0)]))
+            .is_empty());
+        let found = bumps.observe(&frame(
+            1.03,
+            vec![car(0, 40.0, 1400.0), car(1, 210.0, 900.0)],
+        ));
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].bumper_slot, found[0].bumped_slot), (0, 1));
+        assert_eq!(found[0].velocity_delta, (900.0, 0.0, 0.0));
+        // Still in contact on the next frame: same bump, not reported again.
+        let again = bumps.observe(&frame(
+            1.06,
+            vec![car(0, 80.0, 1400.0), car(1, 250.0, 1900.0)],
+        ));
+        assert!(again.is_empty());
+    }
+
+    #[test]
+    fn test_mutual_bump_and_demolition() {
+        let mut bumps = BumpDetector::new();
+        bumps.observe(&frame(
+            1.0,
+            vec![car(0, 0.0, 1000.0), car(1, 200.0, -1000.0)],
+        ));
+        let found = bumps.observe(&frame(
+            1.03,
+            vec![car(0, 20.0, -200.0), car(1, 180.0, 200.0)],
+        ));
+        let pairs: Vec<(usize, usize)> = found
+            .iter()
+            .map(|b| (b.bumper_slot, b.bumped_slot))
+            .collect();
+        assert_eq!(pairs, vec![(1, 0), (0, 1)]);
+
+        let mut bumps = BumpDetector::new();
+        bumps.observe(&frame(1.0, vec![car(0, 0.0, 2300.0), car(1, 200.0, 0.0)]));
+        let mut demo = frame(1.03, vec![car(0, 40.0, 2200.0), car(1, 210.0, 1500.0)]);
+        demo.demolitions.push(FrameDemolition {
+            attacker_slot: Some(0),
+            victim_slot: Some(1),
+        });
+        assert!(bumps.observe(&demo).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+/// Ceiling shot detection.
+///
+/// A ceiling shot is a car that drives up a wall onto the ceiling, then leaves the ceiling
+/// and falls towards the ball. One event is emitted per drop; `resulted_in_touch` tells
+/// whether the same player touched the ball before landing on the ground or a wall, within
+/// `CEILING_SHOT_WINDOW_S` of the drop and without play stopping.
+use crate::geometry::{classify_surface, Surface};
+use crate::network::FrameSnapshot;
+use crate::touches::Touch;
+use std::collections::HashMap;
+
+/// Max seconds between the last wall contact and ceiling arrival; covers the curved
+/// wall-to-ceiling transition where the car is briefly classified as airborne.
+pub const WALL_TO_CEILING_MAX_S: f32 = 1.0;
+/// Max seconds after the drop for a touch to count as the ceiling shot.
+pub const CEILING_SHOT_WINDOW_S: f32 = 3.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CeilingShot {
+    pub slot: usize,
+    pub ceiling_arrival_time: f32,
+    pub drop_time: f32,
+    pub resulted_in_touch: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+enum Phase {
+    #[default]
+    Idle,
+    OnWall {
+        last_contact: f32,
+    },
+    OnCeiling {
+        arrival: f32,
+    },
+    Falling {
+        arrival: f32,
+        drop: f32,
+    },
+}
+
+#[derive(Default)]
+pub struct CeilingShotDetector {
+    phases: HashMap<usize, Phase>,
+}
+
+impl CeilingShotDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame and the touch detected on it; returns completed ceiling shots.
+    pub fn observe(&mut self, frame: &FrameSnapshot, touch: Option<&Touch>) -> Vec<CeilingShot> {
+        let mut out = Vec::new();
+        if !frame.is_live {
+            // Goals and kickoff resets end every sequence.
+            return self.finish();
+        }
+        let t = frame.timestamp;
+        for player in &frame.players {
+            let phase = self.phases.entry(player.slot).or_default();
+            if player.is_demolished {
+                if let Phase::Falling { arrival, drop } = *phase {
+                    out.push(missed(player.slot, arrival, drop));
+                }
+                *phase = Phase::Idle;
+                continue;
+            }
+            let surface = classify_surface(player.position);
+            let touched = touch.is_some_and(|touch| touch.slot == player.slot);
+            *phase = match (*phase, surface) {
+                (Phase::Falling { arrival, drop }, _) if touched => {
+                    out.push(CeilingShot {
+                        slot: player.slot,
+                        ceiling_arrival_time: arrival,
+                        drop_time: drop,
+                        resulted_in_touch: true,
+                    });
+                    Phase::Idle
+                }
+                (Phase::Falling { arrival, .. }, Surface::Ceiling) => Phase::OnCeiling { arrival },
+                (Phase::Falling { arrival, drop }, Surface::Air)
+                    if t - drop <= CEILING_SHOT_WINDOW_S =>
+                {
+                    Phase::Falling { arrival, drop }
+                }
+                (Phase::Falling { arrival, drop }, surface) => {
+                    out.push(missed(player.slot, arrival, drop));
+                    match surface {
+                        Surface::Wall => Phase::OnWall { last_contact: t },
+                        _ => Phase::Idle,
+                    }
+                }
+                (Phase::OnCeiling { arrival }, Surface::Ceiling) => Phase::OnCeiling { arrival },
+                (Phase::OnCeiling { arrival }, Surface::Air) => Phase::Falling { arrival, drop: t },
+                (_, Surface::Wall) => Phase::OnWall { last_contact: t },
+                (Phase::OnWall { last_contact }, Surface::Ceiling)
+                    if t - last_contact <= WALL_TO_CEILING_MAX_S =>
+                {
+                    Phase::OnCeiling { arrival: t }
+                }
+                (Phase::OnWall { last_contact }, Surface::Air) => Phase::OnWall { last_contact },
+                _ => Phase::Idle,
+            };
+        }
+        out
+    }
+
+    /// Close out pending drops as misses.
+    pub fn finish(&mut self) -> Vec<CeilingShot> {
+        let mut out: Vec<CeilingShot> = self
+            .phases
+            .drain()
+            .filter_map(|(slot, phase)| match phase {
+                Phase::Falling { arrival, drop } => Some(missed(slot, arrival, drop)),
+                _ => None,
+            })
+            .collect();
+        out.sort_by_key(|shot| shot.slot);
+        out
+    }
+}
+
+fn missed(slot: usize, arrival: f32, drop: f32) -> CeilingShot {
+    CeilingShot {
+        slot,
+        ceiling_arrival_time: arrival,
+        drop_time: drop,
+        resulted_in_touch: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, player};
+    use crate::network::PlayerSnapshot;
+
+    fn frame(timestamp: f32, car: (f32, f32, f32)) -> FrameSnapshot {
+        let car = PlayerSnapshot {
+            position: car,
+            ..player(1)
+        };
+        test_support::frame(timestamp, vec![car])
+    }
+
+    #[test]
+    fn test_wall_ceiling_drop_touch() {
+        let mut detector = CeilingShotDetector::new();
+        let touch = Touch { slot: 1, team: 0 };
+        assert!(detector
+            .observe(&frame(0.0, (0.0, 0.0, 17.0)), None)
+            .is_empty());
+        assert!(detector
+            .observe(&frame(1.0, (4070.0, 0.0, 1200.0)), None)
+            .is_empty());
+        assert!(detector
+            .observe(&frame(1.3, (3900.0, 0.0, 1950.0)), None)
+            .is_empty());
+        assert!(detector
+            .observe(&frame(1.5, (3500.0, 0.0, 2027.0)), None)
+            .is_empty());
+        assert!(detector
+            .observe(&frame(2.0, (3000.0, 0.0, 1900.0)), None)
+            .is_empty());
+        let shots = detector.observe(&frame(2.8, (2000.0, 0.0, 900.0)), Some(&touch));
+        assert_eq!(
+            shots,
+            vec![CeilingShot {
+                slot: 1,
+                ceiling_arrival_time: 1.5,
+                drop_time: 2.0,
+                resulted_in_touch: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_drop_without_touch_and_no_wall() {
+        let mut detector = CeilingShotDetector::new();
+        // Reaching the ceiling straight from the ground (e.g. a flip reset attempt) is ignored.
+        detector.observe(&frame(0.0, (0.0, 0.0, 17.0)), None);
+        detector.observe(&frame(1.0, (0.0, 0.0, 2027.0)), None);
+        assert!(detector
+            .observe(&frame(1.5, (0.0, 0.0, 1500.0)), None)
+            .is_empty());
+
+        detector.observe(&frame(3.0, (-4070.0, 0.0, 1000.0)), None);
+        detector.observe(&frame(3.5, (-3800.0, 0.0, 2027.0)), None);
+        detector.observe(&frame(4.0, (-3500.0, 0.0, 1800.0)), None);
+        let shots = detector.observe(&frame(5.0, (-3000.0, 0.0, 17.0)), None);
+        assert_eq!(shots.len(), 1);
+        assert!(!shots[0].resulted_in_touch);
+        assert_eq!(shots[0].drop_time, 4.0);
+    }
+}
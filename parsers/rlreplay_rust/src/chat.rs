@@ -0,0 +1,28 @@
+/// Chat entries in the replay's debug log (boxcars `Replay::debug_info`).
+///
+/// The game itself rarely writes to the debug log; in practice it is filled by BakkesMod,
+/// which stores its own metadata there (a banner, the match start time, per-player MMR).
+/// Those entries are not chat; they are recognised by their `user` field (the values below)
+/// and filtered out here.
+const BAKKESMOD_LOG_USERS: [&str; 2] = ["Message from the Wizard", "GameStartTime"];
+/// `user` prefix of BakkesMod MMR entries ("MMR:Steam|<id>|<n>:PRE").
+const BAKKESMOD_MMR_PREFIX: &str = "MMR:";
+
+/// Whether a debug log entry from `user` is a chat message rather than tool metadata.
+pub fn is_chat_entry(user: &str) -> bool {
+    !BAKKESMOD_LOG_USERS.contains(&user) && !user.starts_with(BAKKESMOD_MMR_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bakkesmod_metadata_is_not_chat() {
+        assert!(!is_chat_entry("Message from the Wizard"));
+        assert!(!is_chat_entry("GameStartTime"));
+        assert!(!is_chat_entry("MMR:Steam|76561198313792336|0:PRE"));
+        assert!(!is_chat_entry("MMR:Epic|abc|0:POST"));
+        assert!(is_chat_entry("SomePlayer"));
+    }
+}
@@ -0,0 +1,214 @@
+/// Column-oriented frame storage for `frames_columnar` and `player_trajectory`.
+///
+/// Every column has one entry per frame. Player columns are fixed per slot and hold `None`
+/// for frames where that slot has no car, so all columns stay the same length.
+use crate::network::FrameSnapshot;
+
+/// Leading fields of a flat frame row (`iter_frames(flat=True)`), in order.
+pub const FLAT_FRAME_FIELDS: [&str; 9] = [
+    "timestamp",
+    "is_live",
+    "lag_spike",
+    "ball_x",
+    "ball_y",
+    "ball_z",
+    "ball_vx",
+    "ball_vy",
+    "ball_vz",
+];
+/// Per-slot fields of a flat frame row, repeated for slot 0, 1, ... after the frame fields.
+pub const FLAT_PLAYER_FIELDS: [&str; 8] =
+    ["x", "y", "z", "vx", "vy", "vz", "boost", "is_demolished"];
+
+#[derive(Default)]
+pub struct PlayerColumns {
+    pub x: Vec<Option<f32>>,
+    pub y: Vec<Option<f32>>,
+    pub z: Vec<Option<f32>>,
+    pub vx: Vec<Option<f32>>,
+    pub vy: Vec<Option<f32>>,
+    pub vz: Vec<Option<f32>>,
+    pub boost: Vec<Option<i64>>,
+    pub is_demolished: Vec<Option<bool>>,
+}
+
+impl PlayerColumns {
+    fn with_nulls(len: usize) -> Self {
+        PlayerColumns {
+            x: vec![None; len],
+            y: vec![None; len],
+            z: vec![None; len],
+            vx: vec![None; len],
+            vy: vec![None; len],
+            vz: vec![None; len],
+            boost: vec![None; len],
+            is_demolished: vec![None; len],
+        }
+    }
+
+    fn push_null(&mut self) {
+        self.x.push(None);
+        self.y.push(None);
+        self.z.push(None);
+        self.vx.push(None);
+        self.vy.push(None);
+        self.vz.push(None);
+        self.boost.push(None);
+        self.is_demolished.push(None);
+    }
+}
+
+#[derive(Default)]
+pub struct FrameColumns {
+    pub timestamp: Vec<f32>,
+    pub is_live: Vec<bool>,
+    pub lag_spike: Vec<bool>,
+    pub ball_x: Vec<f32>,
+    pub ball_y: Vec<f32>,
+    pub ball_z: Vec<f32>,
+    pub ball_vx: Vec<f32>,
+    pub ball_vy: Vec<f32>,
+    pub ball_vz: Vec<f32>,
+    /// Indexed by slot.
+    pub players: Vec<PlayerColumns>,
+}
+
+impl FrameColumns {
+    /// Start with `slots` player column sets; more are added (null-backfilled) as new slots
+    /// appear.
+    pub fn new(slots: usize) -> Self {
+        FrameColumns {
+            players: (0..slots).map(|_| PlayerColumns::default()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.timestamp.len()
+    }
+
+    /// Append `frame` as a row; `lag_spike` as in iter_frames.
+    pub fn push(&mut self, frame: &FrameSnapshot, lag_spike: bool) {
+        let row = self.len();
+        for player in &frame.players {
+            while self.players.len() <= player.slot {
+                self.players.push(PlayerColumns::with_nulls(row));
+            }
+        }
+        self.timestamp.push(frame.timestamp);
+        self.is_live.push(frame.is_live);
+        self.lag_spike.push(lag_spike);
+        self.ball_x.push(frame.ball.position.0);
+        self.ball_y.push(frame.ball.position.1);
+        self.ball_z.push(frame.ball.position.2);
+        self.ball_vx.push(frame.ball.velocity.0);
+        self.ball_vy.push(frame.ball.velocity.1);
+        self.ball_vz.push(frame.ball.velocity.2);
+
+        for columns in self.players.iter_mut() {
+            columns.push_null();
+        }
+        for player in &frame.players {
+            let columns = &mut self.players[player.slot];
+            columns.x[row] = Some(player.position.0);
+            columns.y[row] = Some(player.position.1);
+            columns.z[row] = Some(player.position.2);
+            columns.vx[row] = Some(player.velocity.0);
+            columns.vy[row] = Some(player.velocity.1);
+            columns.vz[row] = Some(player.velocity.2);
+            columns.boost[row] = Some(player.boost_amount);
+            columns.is_demolished[row] = Some(player.is_demolished);
+        }
+    }
+}
+
+/// One player's trajectory over the frames where they have a live (spawned, not demolished)
+/// car.
+#[derive(Default)]
+pub struct PlayerTrajectory {
+    pub slot: usize,
+    pub timestamp: Vec<f32>,
+    pub x: Vec<f32>,
+    pub y: Vec<f32>,
+    pub z: Vec<f32>,
+    pub speed: Vec<f32>,
+    pub boost: Vec<i64>,
+}
+
+impl PlayerTrajectory {
+    pub fn new(slot: usize) -> Self {
+        PlayerTrajectory {
+            slot,
+            ..Default::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.timestamp.len()
+    }
+
+    pub fn push(&mut self, frame: &FrameSnapshot) {
+        let Some(player) = frame
+            .players
+            .iter()
+            .find(|p| p.slot == self.slot && !p.is_demolished)
+        else {
+            return;
+        };
+        self.timestamp.push(frame.timestamp);
+        self.x.push(player.position.0);
+        self.y.push(player.position.1);
+        self.z.push(player.position.2);
+        self.speed.push(player.speed());
+        self.boost.push(player.boost_amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, player};
+    use crate::network::PlayerSnapshot;
+
+    fn frame(timestamp: f32, slots: &[usize]) -> FrameSnapshot {
+        let players = slots
+            .iter()
+            .map(|&slot| PlayerSnapshot {
+                position: (slot as f32, 0.0, 17.0),
+                ..player(slot)
+            })
+            .collect();
+        test_support::frame(timestamp, players)
+    }
+
+    #[test]
+    fn test_columns_stay_rectangular() {
+        let mut columns = FrameColumns::new(1);
+        columns.push(&frame(0.0, &[0]), false);
+        columns.push(&frame(0.1, &[2]), false);
+        columns.push(&frame(0.2, &[0, 2]), true);
+
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns.players.len(), 3);
+        for player in &columns.players {
+            assert_eq!(player.x.len(), 3);
+        }
+        assert_eq!(columns.players[0].x, vec![Some(0.0), None, Some(0.0)]);
+        assert_eq!(columns.players[1].x, vec![None, None, None]);
+        assert_eq!(columns.players[2].x, vec![None, Some(2.0), Some(2.0)]);
+        assert_eq!(columns.lag_spike, vec![false, false, true]);
+    }
+
+    #[test]
+    fn test_trajectory_skips_absent_frames() {
+        let mut trajectory = PlayerTrajectory::new(2);
+        trajectory.push(&frame(0.0, &[0]));
+        trajectory.push(&frame(0.1, &[0, 2]));
+        trajectory.push(&frame(0.2, &[2]));
+
+        assert_eq!(trajectory.len(), 2);
+        assert_eq!(trajectory.timestamp, vec![0.1, 0.2]);
+        assert_eq!(trajectory.x, vec![2.0, 2.0]);
+        assert_eq!(trajectory.boost, vec![33, 33]);
+    }
+}
@@ -0,0 +1,157 @@
+/// Double-commit detection: two teammates challenging the same ball.
+///
+/// A pair of same-team cars is double-committing on a live frame when both are
+/// non-demolished, within `DOUBLE_COMMIT_RANGE_UU` of the ball (centre to centre) and
+/// closing on it at `DOUBLE_COMMIT_MIN_CLOSING_SPEED_UU_S` or faster. Requiring both to
+/// close keeps a pass-and-follow out: the passer's teammate trails the ball rather than
+/// driving into it. A double commit is a run of such frames lasting at least
+/// `DOUBLE_COMMIT_MIN_DURATION_S`, ending on the first frame the pair no longer qualifies
+/// (or play stops).
+use crate::geometry::distance;
+use crate::network::{FrameSnapshot, PlayerSnapshot};
+use crate::whiffs::closing_speed;
+use std::collections::BTreeMap;
+
+/// Car-centre to ball-centre distance (uu) within which a car is challenging the ball.
+pub const DOUBLE_COMMIT_RANGE_UU: f32 = 800.0;
+/// Min closing speed (uu/s) on the ball for a car to be committing to it.
+pub const DOUBLE_COMMIT_MIN_CLOSING_SPEED_UU_S: f32 = 300.0;
+pub const DOUBLE_COMMIT_MIN_DURATION_S: f32 = 0.2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoubleCommit {
+    pub team: i64,
+    /// The pair's slots, lower first.
+    pub slots: (usize, usize),
+    pub start: f32,
+    /// Timestamp of the frame the double commit ended on.
+    pub end: f32,
+}
+
+#[derive(Default)]
+pub struct DoubleCommitDetector {
+    /// Slot pair → double commit in progress.
+    current: BTreeMap<(usize, usize), DoubleCommit>,
+}
+
+impl DoubleCommitDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame; returns the double commits that ended on it, in slot order.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> Vec<DoubleCommit> {
+        let committed: Vec<&PlayerSnapshot> = if frame.is_live {
+            frame
+                .players
+                .iter()
+                .filter(|player| is_committing(frame, player))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut active: BTreeMap<(usize, usize), i64> = BTreeMap::new();
+        for (i, a) in committed.iter().enumerate() {
+            for b in &committed[i + 1..] {
+                if a.team == b.team {
+                    active.insert((a.slot.min(b.slot), a.slot.max(b.slot)), a.team);
+                }
+            }
+        }
+
+        let ended: Vec<(usize, usize)> = self
+            .current
+            .keys()
+            .filter(|pair| !active.contains_key(pair))
+            .copied()
+            .collect();
+        for (pair, team) in active {
+            self.current.entry(pair).or_insert(DoubleCommit {
+                team,
+                slots: pair,
+                start: frame.timestamp,
+                end: frame.timestamp,
+            });
+        }
+        ended
+            .into_iter()
+            .filter_map(|pair| self.current.remove(&pair))
+            .map(|commit| DoubleCommit {
+                end: frame.timestamp,
+                ..commit
+            })
+            .filter(|commit| commit.end - commit.start >= DOUBLE_COMMIT_MIN_DURATION_S)
+            .collect()
+    }
+}
+
+fn is_committing(frame: &FrameSnapshot, car: &PlayerSnapshot) -> bool {
+    let ball = &frame.ball;
+    !car.is_demolished
+        && distance(car.position, ball.position) <= DOUBLE_COMMIT_RANGE_UU
+        && closing_speed(car, ball.position, ball.velocity) >= DOUBLE_COMMIT_MIN_CLOSING_SPEED_UU_S
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{frame, player};
+
+    fn car(slot: usize, team: i64, x: f32, vx: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            team,
+            position: (x, 0.0, 17.0),
+            velocity: (vx, 0.0, 0.0),
+            ..player(slot)
+        }
+    }
+
+    #[test]
+    fn test_double_commit() {
+        let mut detector = DoubleCommitDetector::new();
+        let mut found = Vec::new();
+        // Slots 0 and 2 converge on the ball from either side; opponent slot 1 joins in.
+        for (t, spread) in [(1.0, 700.0), (1.2, 500.0), (1.4, 300.0)] {
+            found.extend(detector.observe(&frame(
+                t,
+                vec![
+                    car(0, 0, -spread, 1000.0),
+                    car(1, 1, spread, -1000.0),
+                    car(2, 0, spread, -1000.0),
+                ],
+            )));
+        }
+        assert!(found.is_empty());
+        // Slot 2 peels away from the ball.
+        found.extend(detector.observe(&frame(
+            1.5,
+            vec![car(0, 0, -200.0, 1000.0), car(2, 0, 300.0, 1000.0)],
+        )));
+        assert_eq!(
+            found,
+            vec![DoubleCommit {
+                team: 0,
+                slots: (0, 2),
+                start: 1.0,
+                end: 1.5,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trailing_teammate_is_not_committing() {
+        // Pass and follow: the teammate behind the ball drives the same way it travels.
+        let mut detector = DoubleCommitDetector::new();
+        let mut found = Vec::new();
+        for t in [1.0, 1.2, 1.4, 1.6] {
+            let mut f = frame(
+                t,
+                vec![car(0, 0, -300.0, 1000.0), car(1, 0, -600.0, 1000.0)],
+            );
+            f.ball.velocity = (1200.0, 0.0, 0.0);
+            found.extend(detector.observe(&f));
+        }
+        found.extend(detector.observe(&frame(1.8, Vec::new())));
+        assert!(found.is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+/// Ground dribble detection, with the height the ball is carried at.
+///
+/// A car carries the ball on a live frame when it is grounded (centre below
+/// `DRIBBLE_CAR_MAX_Z`), the ball centre is within `DRIBBLE_XY_RADIUS_UU` of it
+/// horizontally and `DRIBBLE_MIN_HEIGHT_UU`..`DRIBBLE_MAX_HEIGHT_UU` above it, and the two
+/// move within `DRIBBLE_MAX_RELATIVE_SPEED_UU_S` of each other; the same envelope as the
+/// Python mechanics analysis. A dribble is a run of carrying frames lasting at least
+/// `DRIBBLE_MIN_DURATION_S`, ending on the first frame the car no longer carries the ball
+/// (or is demolished, or play stops).
+///
+/// Each carrying frame adds one to the bin of `CARRY_HEIGHT_BIN_EDGES_UU` holding the ball's
+/// height above the car centre; higher sustained carries indicate more control.
+use crate::geometry::magnitude;
+use crate::network::{FrameSnapshot, PlayerSnapshot};
+use std::collections::HashMap;
+
+pub const DRIBBLE_XY_RADIUS_UU: f32 = 100.0;
+/// Ball centre height above the car centre (uu) for a carry: roof plus ball radius, give
+/// or take, up to a loose bounce.
+pub const DRIBBLE_MIN_HEIGHT_UU: f32 = 90.0;
+pub const DRIBBLE_MAX_HEIGHT_UU: f32 = 180.0;
+/// Max car centre height (uu) for the car to count as grounded.
+pub const DRIBBLE_CAR_MAX_Z: f32 = 50.0;
+pub const DRIBBLE_MAX_RELATIVE_SPEED_UU_S: f32 = 300.0;
+pub const DRIBBLE_MIN_DURATION_S: f32 = 0.5;
+/// Carry height histogram bin edges (uu above the car centre), spanning the carry envelope.
+pub const CARRY_HEIGHT_BIN_EDGES_UU: [f32; 4] = [90.0, 120.0, 150.0, 180.0];
+pub const CARRY_HEIGHT_BINS: usize = CARRY_HEIGHT_BIN_EDGES_UU.len() - 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dribble {
+    pub slot: usize,
+    pub start: f32,
+    /// Timestamp of the frame the carry ended on.
+    pub end: f32,
+    /// Carrying frames per `CARRY_HEIGHT_BIN_EDGES_UU` bin.
+    pub carry_heights: [u32; CARRY_HEIGHT_BINS],
+}
+
+#[derive(Default)]
+pub struct DribbleDetector {
+    /// Slot → carry in progress.
+    carries: HashMap<usize, Dribble>,
+}
+
+impl DribbleDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame; returns the dribbles that ended on it, in slot order.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> Vec<Dribble> {
+        let mut ended: Vec<usize> = Vec::new();
+        if frame.is_live {
+            for player in &frame.players {
+                let Some(height) = carry_height(frame, player) else {
+                    ended.push(player.slot);
+                    continue;
+                };
+                let carry = self.carries.entry(player.slot).or_insert(Dribble {
+                    slot: player.slot,
+                    start: frame.timestamp,
+                    end: frame.timestamp,
+                    carry_heights: [0; CARRY_HEIGHT_BINS],
+                });
+                if let Some(bin) = CARRY_HEIGHT_BIN_EDGES_UU
+                    .windows(2)
+                    .position(|edges| height < edges[1])
+                {
+                    carry.carry_heights[bin] += 1;
+                }
+            }
+        }
+        // Play stopped, or cars that left the field mid-carry.
+        ended.extend(
+            self.carries
+                .keys()
+                .filter(|slot| !frame.is_live || !frame.players.iter().any(|p| p.slot == **slot))
+                .copied(),
+        );
+        ended.sort_unstable();
+        ended
+            .into_iter()
+            .filter_map(|slot| self.carries.remove(&slot))
+            .map(|carry| Dribble {
+                end: frame.timestamp,
+                ..carry
+            })
+            .filter(|dribble| dribble.end - dribble.start >= DRIBBLE_MIN_DURATION_S)
+            .collect()
+    }
+}
+
+/// Height (uu) of the ball centre above `car` when the car is carrying it, else None.
+fn carry_height(frame: &FrameSnapshot, car: &PlayerSnapshot) -> Option<f32> {
+    if car.is_demolished || car.position.2 >= DRIBBLE_CAR_MAX_Z {
+        return None;
+    }
+    let (ball, p) = (frame.ball.position, car.position);
+    let height = ball.2 - p.2;
+    let (bv, cv) = (frame.ball.velocity, car.velocity);
+    let relative_speed = magnitude((bv.0 - cv.0, bv.1 - cv.1, bv.2 - cv.2));
+    let carrying = (ball.0 - p.0).hypot(ball.1 - p.1) < DRIBBLE_XY_RADIUS_UU
+        && height > DRIBBLE_MIN_HEIGHT_UU
+        && height < DRIBBLE_MAX_HEIGHT_UU
+        && relative_speed < DRIBBLE_MAX_RELATIVE_SPEED_UU_S;
+    carrying.then_some(height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, ball, player};
+    use crate::network::BallSnapshot;
+
+    fn car(x: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            position: (x, 0.0, 17.0),
+            velocity: (1000.0, 0.0, 0.0),
+            ..player(0)
+        }
+    }
+
+    fn frame(timestamp: f32, ball_height: f32) -> FrameSnapshot {
+        let x = timestamp * 1000.0;
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position: (x + 20.0, 0.0, 17.0 + ball_height),
+                velocity: (1100.0, 0.0, 0.0),
+                ..ball()
+            },
+            ..test_support::frame(timestamp, vec![car(x)])
+        }
+    }
+
+    #[test]
+    fn test_dribble_carry_heights() {
+        let mut dribbles = DribbleDetector::new();
+        for (i, height) in [100.0, 110.0, 130.0, 160.0, 170.0, 140.0]
+            .iter()
+            .enumerate()
+        {
+            assert!(dribbles.observe(&frame(i as f32 * 0.1, *height)).is_empty());
+        }
+        // Ball knocked off the roof.
+        let found = dribbles.observe(&frame(0.6, 400.0));
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].slot, found[0].start, found[0].end), (0, 0.0, 0.6));
+        assert_eq!(found[0].carry_heights, [2, 2, 2]);
+    }
+
+    #[test]
+    fn test_short_carry_is_not_a_dribble() {
+        let mut dribbles = DribbleDetector::new();
+        dribbles.observe(&frame(0.0, 120.0));
+        dribbles.observe(&frame(0.1, 120.0));
+        assert!(dribbles.observe(&frame(0.2, 400.0)).is_empty());
+        // A carry cut by a stoppage still ends.
+        dribbles.observe(&frame(1.0, 120.0));
+        let mut dead = frame(1.6, 120.0);
+        dead.is_live = false;
+        assert_eq!(dribbles.observe(&dead).len(), 1);
+    }
+}
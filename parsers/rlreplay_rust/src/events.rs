@@ -0,0 +1,459 @@
+/// Discrete match events merged into one time-ordered timeline for `iter_events`.
+///
+/// The other detectors (touches, goals) are composed in the frame loop; this module adds
+/// kickoff detection, the event type itself and the CSV export. A kickoff is the first
+/// live frame after dead time (countdown, post-goal replay) with the ball resting on the
+/// centre spot.
+///
+/// A kickoff's outcome is settled after the first contact on it. Touches within
+/// `KICKOFF_CONTACT_S` of that contact are the challenge itself; the next touch after them
+/// but within `KICKOFF_OUTCOME_WINDOW_S` of the contact wins the kickoff for the toucher's
+/// team. Without one, the ball's y velocity at the end of the window decides: at least
+/// `KICKOFF_NEUTRAL_SPEED_UU_S` towards a goal is a win for the attacking team, anything
+/// slower is neutral. Play stopping inside the window (a goal straight from the kickoff)
+/// is judged on the last live ball velocity.
+use crate::arena_tables::{KickoffPosition, KickoffSpawn};
+use crate::geometry::half_turn;
+use crate::goals::GoalEvent;
+use crate::network::FrameSnapshot;
+use crate::shots::ShotQualityCoefficients;
+use crate::touches::Touch;
+use std::io::{self, Write};
+
+/// Max ball distance (uu, per horizontal axis) from the centre spot for a restart to count
+/// as a kickoff.
+pub const KICKOFF_SPOT_TOLERANCE_UU: f32 = 50.0;
+
+/// Max (x, y) distance (uu) from a canonical spawn for a car to count as spawned there; the
+/// nearest spawns are 512 uu apart.
+pub const KICKOFF_SPAWN_TOLERANCE_UU: f32 = 250.0;
+
+/// Seconds after a kickoff's first contact during which further touches are part of the
+/// challenge.
+pub const KICKOFF_CONTACT_S: f32 = 0.25;
+/// Seconds after a kickoff's first contact within which its outcome is settled.
+pub const KICKOFF_OUTCOME_WINDOW_S: f32 = 1.5;
+/// Min ball y speed (uu/s) towards a goal for a kickoff without a follow-up touch to count
+/// as won.
+pub const KICKOFF_NEUTRAL_SPEED_UU_S: f32 = 500.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatchEvent {
+    Kickoff {
+        timestamp: f32,
+    },
+    Touch {
+        timestamp: f32,
+        slot: usize,
+        team: i64,
+    },
+    Demolition {
+        timestamp: f32,
+        attacker_slot: Option<usize>,
+        victim_slot: Option<usize>,
+    },
+    BoostPickup {
+        timestamp: f32,
+        slot: Option<usize>,
+        team: Option<i64>,
+        pad_id: usize,
+        is_big: bool,
+    },
+    Goal(GoalEvent),
+}
+
+impl MatchEvent {
+    pub fn timestamp(&self) -> f32 {
+        match *self {
+            MatchEvent::Kickoff { timestamp }
+            | MatchEvent::Touch { timestamp, .. }
+            | MatchEvent::Demolition { timestamp, .. }
+            | MatchEvent::BoostPickup { timestamp, .. } => timestamp,
+            MatchEvent::Goal(goal) => goal.timestamp,
+        }
+    }
+}
+
+/// Sort by timestamp; events at the same time keep their detection order.
+pub fn sort_timeline(events: &mut [MatchEvent]) {
+    events.sort_by(|a, b| a.timestamp().total_cmp(&b.timestamp()));
+}
+
+/// CSV header for `write_timeline_csv`. Fields that do not apply to an event type are
+/// left empty: demolitions put the attacker in `player_id`, goals put the scorer in
+/// `player_id` and the scoring team in `team`.
+pub const TIMELINE_CSV_COLUMNS: [&str; 10] = [
+    "type",
+    "timestamp",
+    "player_id",
+    "team",
+    "victim_player_id",
+    "pad_id",
+    "is_big",
+    "goal_type",
+    "shot_speed",
+    "shot_quality",
+];
+
+/// Write `events` as CSV rows (with a header) in `TIMELINE_CSV_COLUMNS` order.
+pub fn write_timeline_csv<W: Write>(
+    out: &mut W,
+    events: &[MatchEvent],
+    coefficients: &ShotQualityCoefficients,
+) -> io::Result<()> {
+    write_csv_row(out, &TIMELINE_CSV_COLUMNS.map(String::from))?;
+    let player_id = |slot: Option<usize>| {
+        slot.map(|slot| format!("player_{}", slot))
+            .unwrap_or_default()
+    };
+    let opt = |value: Option<String>| value.unwrap_or_default();
+    for event in events {
+        let mut row: [String; 10] = Default::default();
+        row[1] = event.timestamp().to_string();
+        match *event {
+            MatchEvent::Kickoff { .. } => row[0] = "kickoff".to_string(),
+            MatchEvent::Touch { slot, team, .. } => {
+                row[0] = "touch".to_string();
+                row[2] = player_id(Some(slot));
+                row[3] = team.to_string();
+            }
+            MatchEvent::Demolition {
+                attacker_slot,
+                victim_slot,
+                ..
+            } => {
+                row[0] = "demolition".to_string();
+                row[2] = player_id(attacker_slot);
+                row[4] = player_id(victim_slot);
+            }
+            MatchEvent::BoostPickup {
+                slot,
+                team,
+                pad_id,
+                is_big,
+                ..
+            } => {
+                row[0] = "boost_pickup".to_string();
+                row[2] = player_id(slot);
+                row[3] = opt(team.map(|team| team.to_string()));
+                row[5] = pad_id.to_string();
+                row[6] = is_big.to_string();
+            }
+            MatchEvent::Goal(goal) => {
+                row[0] = "goal".to_string();
+                row[2] = player_id(goal.scorer_slot);
+                row[3] = goal.scoring_team.to_string();
+                row[7] = goal.goal_type.as_str().to_string();
+                row[8] = goal.shot_speed.to_string();
+                row[9] = opt(goal.shot.map(|shot| coefficients.score(&shot).to_string()));
+            }
+        }
+        write_csv_row(out, &row)?;
+    }
+    Ok(())
+}
+
+/// One RFC 4180 record: fields containing a comma, quote or line break are quoted, with
+/// embedded quotes doubled.
+fn write_csv_row<W: Write>(out: &mut W, fields: &[String]) -> io::Result<()> {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.write_all(b",")?;
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            write!(out, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            out.write_all(field.as_bytes())?;
+        }
+    }
+    out.write_all(b"\r\n")
+}
+
+#[derive(Default)]
+pub struct KickoffDetector {
+    prev_live: bool,
+}
+
+impl KickoffDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame; true when it starts a kickoff.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> bool {
+        let restarted = frame.is_live && !self.prev_live;
+        self.prev_live = frame.is_live;
+        let (x, y, _) = frame.ball.position;
+        restarted && x.abs() <= KICKOFF_SPOT_TOLERANCE_UU && y.abs() <= KICKOFF_SPOT_TOLERANCE_UU
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KickoffOutcome {
+    Won,
+    Neutral,
+    Lost,
+}
+
+impl KickoffOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KickoffOutcome::Won => "won",
+            KickoffOutcome::Neutral => "neutral",
+            KickoffOutcome::Lost => "lost",
+        }
+    }
+
+    /// Outcomes indexed by team when `winner` (or nobody) won the kickoff.
+    fn for_winner(winner: Option<i64>) -> [KickoffOutcome; 2] {
+        match winner {
+            Some(0) => [KickoffOutcome::Won, KickoffOutcome::Lost],
+            Some(_) => [KickoffOutcome::Lost, KickoffOutcome::Won],
+            None => [KickoffOutcome::Neutral; 2],
+        }
+    }
+}
+
+/// Settles the outcome of the kickoff in progress; see the module docs.
+#[derive(Default)]
+pub struct KickoffOutcomeDetector {
+    /// A kickoff is in progress and unsettled.
+    active: bool,
+    first_contact: Option<f32>,
+    last_ball_velocity_y: f32,
+}
+
+impl KickoffOutcomeDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame, whether it starts a kickoff and the touch detected on it;
+    /// returns the outcome (indexed by team) of the kickoff it settles, if any. A kickoff
+    /// that ends without any contact has no outcome.
+    pub fn observe(
+        &mut self,
+        frame: &FrameSnapshot,
+        kickoff: bool,
+        touch: Option<&Touch>,
+    ) -> Option<[KickoffOutcome; 2]> {
+        if kickoff {
+            self.active = true;
+            self.first_contact = None;
+        }
+        if !self.active {
+            return None;
+        }
+        if !frame.is_live {
+            self.active = false;
+            return self
+                .first_contact
+                .map(|_| KickoffOutcome::for_winner(direction_winner(self.last_ball_velocity_y)));
+        }
+        self.last_ball_velocity_y = frame.ball.velocity.1;
+        let Some(contact) = self.first_contact else {
+            if touch.is_some() {
+                self.first_contact = Some(frame.timestamp);
+            }
+            return None;
+        };
+        let elapsed = frame.timestamp - contact;
+        let winner = if elapsed >= KICKOFF_OUTCOME_WINDOW_S {
+            direction_winner(self.last_ball_velocity_y)
+        } else {
+            match touch {
+                Some(touch) if elapsed >= KICKOFF_CONTACT_S => Some(touch.team),
+                _ => return None,
+            }
+        };
+        self.active = false;
+        Some(KickoffOutcome::for_winner(winner))
+    }
+}
+
+/// Team the ball is heading away from at y velocity `vy`, if fast enough.
+fn direction_winner(vy: f32) -> Option<i64> {
+    if vy >= KICKOFF_NEUTRAL_SPEED_UU_S {
+        Some(0)
+    } else if vy <= -KICKOFF_NEUTRAL_SPEED_UU_S {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// The kickoff spawn `team`'s car at `position` starts from, if it is near one of `spawns`
+/// (blue-side points; orange cars are half-turned first).
+pub fn classify_kickoff_spawn(
+    spawns: &[KickoffSpawn],
+    position: (f32, f32, f32),
+    team: i64,
+) -> Option<KickoffPosition> {
+    let (x, y, _) = if team == 1 {
+        half_turn(position)
+    } else {
+        position
+    };
+    spawns
+        .iter()
+        .map(|spawn| ((spawn.x - x).hypot(spawn.y - y), spawn.position))
+        .filter(|(dist, _)| *dist <= KICKOFF_SPAWN_TOLERANCE_UU)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, position)| position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena_tables::SOCCAR_KICKOFF_SPAWNS;
+    use crate::network::test_support;
+    use crate::network::BallSnapshot;
+
+    fn frame(timestamp: f32, is_live: bool, ball: (f32, f32, f32)) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position: ball,
+                ..test_support::ball()
+            },
+            is_live,
+            ..test_support::frame(timestamp, Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_kickoff_on_restart_at_centre() {
+        let mut kickoffs = KickoffDetector::new();
+        assert!(!kickoffs.observe(&frame(0.0, false, (0.0, 0.0, 93.0))));
+        assert!(kickoffs.observe(&frame(3.0, true, (0.0, 0.0, 93.0))));
+        assert!(!kickoffs.observe(&frame(3.1, true, (0.0, 0.0, 93.0))));
+        // Play resuming away from the centre spot is not a kickoff.
+        assert!(!kickoffs.observe(&frame(4.0, false, (0.0, 5200.0, 93.0))));
+        assert!(!kickoffs.observe(&frame(5.0, true, (0.0, 5200.0, 93.0))));
+    }
+
+    #[test]
+    fn test_kickoff_outcome() {
+        let moving = |timestamp, vy| {
+            let mut f = frame(timestamp, true, (0.0, 0.0, 93.0));
+            f.ball.velocity = (0.0, vy, 0.0);
+            f
+        };
+        let blue = Touch { slot: 0, team: 0 };
+        let orange = Touch { slot: 1, team: 1 };
+
+        // Orange picks up the ball after the challenge.
+        let mut outcomes = KickoffOutcomeDetector::new();
+        assert!(outcomes.observe(&moving(3.0, 0.0), true, None).is_none());
+        assert!(outcomes
+            .observe(&moving(5.0, 800.0), false, Some(&blue))
+            .is_none());
+        assert!(outcomes
+            .observe(&moving(5.1, -300.0), false, Some(&orange))
+            .is_none());
+        assert_eq!(
+            outcomes.observe(&moving(5.8, -900.0), false, Some(&orange)),
+            Some([KickoffOutcome::Lost, KickoffOutcome::Won])
+        );
+        assert!(outcomes
+            .observe(&moving(5.9, -900.0), false, Some(&blue))
+            .is_none());
+
+        // No follow-up touch: ball direction at the end of the window.
+        let mut outcomes = KickoffOutcomeDetector::new();
+        outcomes.observe(&moving(3.0, 0.0), true, None);
+        outcomes.observe(&moving(5.0, 1200.0), false, Some(&orange));
+        assert!(outcomes
+            .observe(&moving(6.0, 1100.0), false, None)
+            .is_none());
+        assert_eq!(
+            outcomes.observe(&moving(6.5, 1000.0), false, None),
+            Some([KickoffOutcome::Won, KickoffOutcome::Lost])
+        );
+
+        let mut outcomes = KickoffOutcomeDetector::new();
+        outcomes.observe(&moving(3.0, 0.0), true, None);
+        outcomes.observe(&moving(5.0, 200.0), false, Some(&blue));
+        assert_eq!(
+            outcomes.observe(&moving(6.5, 100.0), false, None),
+            Some([KickoffOutcome::Neutral; 2])
+        );
+    }
+
+    #[test]
+    fn test_classify_kickoff_spawn() {
+        let classify =
+            |position, team| classify_kickoff_spawn(SOCCAR_KICKOFF_SPAWNS, position, team);
+        assert_eq!(
+            classify((2040.0, -2550.0, 17.0), 0),
+            Some(KickoffPosition::DiagonalLeft)
+        );
+        // Orange's left is -x.
+        assert_eq!(
+            classify((-2048.0, 2560.0, 17.0), 1),
+            Some(KickoffPosition::DiagonalLeft)
+        );
+        assert_eq!(
+            classify((256.0, 3840.0, 17.0), 1),
+            Some(KickoffPosition::OffsetRight)
+        );
+        assert_eq!(
+            classify((0.0, -4608.0, 17.0), 0),
+            Some(KickoffPosition::Back)
+        );
+        assert_eq!(classify((0.0, -3000.0, 17.0), 0), None);
+    }
+
+    #[test]
+    fn test_sort_timeline_is_stable() {
+        let mut events = vec![
+            MatchEvent::Touch {
+                timestamp: 2.0,
+                slot: 0,
+                team: 0,
+            },
+            MatchEvent::Kickoff { timestamp: 1.0 },
+            MatchEvent::Demolition {
+                timestamp: 2.0,
+                attacker_slot: Some(1),
+                victim_slot: Some(0),
+            },
+        ];
+        sort_timeline(&mut events);
+        assert_eq!(events[0], MatchEvent::Kickoff { timestamp: 1.0 });
+        assert!(matches!(events[1], MatchEvent::Touch { .. }));
+        assert!(matches!(events[2], MatchEvent::Demolition { .. }));
+    }
+
+    #[test]
+    fn test_timeline_csv() {
+        let events = [
+            MatchEvent::Kickoff { timestamp: 1.5 },
+            MatchEvent::BoostPickup {
+                timestamp: 2.0,
+                slot: Some(1),
+                team: None,
+                pad_id: 4,
+                is_big: true,
+            },
+        ];
+        let mut out = Vec::new();
+        write_timeline_csv(&mut out, &events, &ShotQualityCoefficients::DEFAULT).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "type,timestamp,player_id,team,victim_player_id,pad_id,is_big,goal_type,shot_speed,shot_quality"
+        );
+        assert_eq!(lines[1], "kickoff,1.5,,,,,,,,");
+        assert_eq!(lines[2], "boost_pickup,2,player_1,,,4,true,,,");
+        assert_eq!(lines[3], "");
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let mut out = Vec::new();
+        let fields = ["plain", "a,b", "say \"hi\""].map(String::from);
+        write_csv_row(&mut out, &fields).unwrap();
+        assert_eq!(out, b"plain,\"a,b\",\"say \"\"hi\"\"\"\r\n");
+    }
+}
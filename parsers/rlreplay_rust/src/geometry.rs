@@ -0,0 +1,444 @@
+//! Small vector helpers for (x, y, z) tuples in uu / uu/s, quaternion ↔ rotator
+//! conversion, plus standard Soccar arena surface classification.
+
+use std::f32::consts::PI;
+
+/// Side walls sit at x = ±SIDE_WALL_X.
+pub const SIDE_WALL_X: f32 = 4096.0;
+/// Back walls sit at y = ±BACK_WALL_Y (goal lines).
+pub const BACK_WALL_Y: f32 = 5120.0;
+pub const CEILING_Z: f32 = 2044.0;
+/// Corner walls are the 45° planes |x| + |y| = CORNER_SUM.
+pub const CORNER_SUM: f32 = 8064.0;
+pub const GOAL_HALF_WIDTH: f32 = 893.0;
+pub const GOAL_HEIGHT: f32 = 642.0;
+/// Standard Soccar ball radius (uu).
+pub const BALL_RADIUS: f32 = 92.75;
+/// Field thirds split the goal-line-to-goal-line length evenly; a team's attacking third
+/// is past y = +ATTACKING_THIRD_Y (blue) or -ATTACKING_THIRD_Y (orange).
+pub const ATTACKING_THIRD_Y: f32 = BACK_WALL_Y / 3.0;
+/// A car centre within this distance (uu) of a surface is treated as in contact with it.
+pub const SURFACE_CONTACT_UU: f32 = 60.0;
+/// Default arena gravity (uu/s², downward).
+pub const GRAVITY_UU_S2: f32 = 650.0;
+/// A car centre at or below this height (uu) is low enough to be on the floor; a car at
+/// rest sits at about 17.
+pub const CAR_GROUND_Z: f32 = 18.0;
+/// Minimum world-z component of the car's roof normal for its wheels to be down (60° tilt).
+pub const CAR_UPRIGHT_MIN_UP_Z: f32 = 0.5;
+/// A ball whose bottom is within this height (uu) of the floor and not rising is grounded.
+pub const BALL_GROUNDED_UU: f32 = 5.0;
+
+/// Ball physics for landing prediction and grounded detection. Mutators (low gravity,
+/// big ball) change both, so callers can override the Soccar defaults.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Physics {
+    /// Downward acceleration (uu/s², positive).
+    pub gravity: f32,
+    /// Ball radius (uu); the ball centre rests this high above the floor.
+    pub ball_radius: f32,
+}
+
+impl Physics {
+    /// `GRAVITY_UU_S2` (650 uu/s²) and `BALL_RADIUS` (92.75 uu).
+    pub const SOCCAR: Physics = Physics {
+        gravity: GRAVITY_UU_S2,
+        ball_radius: BALL_RADIUS,
+    };
+
+    /// The whole ball is over the goal line once its centre passes this |y|.
+    pub fn goal_line_y(&self) -> f32 {
+        BACK_WALL_Y + self.ball_radius
+    }
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Self::SOCCAR
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Surface {
+    Ground,
+    Wall,
+    Ceiling,
+    Air,
+}
+
+pub fn distance(a: (f32, f32, f32), b: (f32, f32, f32)) -> f32 {
+    magnitude((a.0 - b.0, a.1 - b.1, a.2 - b.2))
+}
+
+pub fn magnitude(v: (f32, f32, f32)) -> f32 {
+    (v.0 * v.0 + v.1 * v.1 + v.2 * v.2).sqrt()
+}
+
+/// Surface a car centred at `position` is driving on, by proximity to the arena planes.
+pub fn classify_surface(position: (f32, f32, f32)) -> Surface {
+    let (x, y, z) = (position.0.abs(), position.1.abs(), position.2);
+    if z <= SURFACE_CONTACT_UU {
+        return Surface::Ground;
+    }
+    if z >= CEILING_Z - SURFACE_CONTACT_UU {
+        return Surface::Ceiling;
+    }
+    let in_goal_mouth = x < GOAL_HALF_WIDTH && z < GOAL_HEIGHT;
+    let near_side = x >= SIDE_WALL_X - SURFACE_CONTACT_UU;
+    let near_back = y >= BACK_WALL_Y - SURFACE_CONTACT_UU && !in_goal_mouth;
+    // Distance to a 45° plane is (|x| + |y| - CORNER_SUM) / sqrt(2).
+    let near_corner = x + y >= CORNER_SUM - SURFACE_CONTACT_UU * std::f32::consts::SQRT_2;
+    if near_side || near_back || near_corner {
+        Surface::Wall
+    } else {
+        Surface::Air
+    }
+}
+
+/// Whether a car centred at `position` has its wheels on the floor: low enough, and, when
+/// its `rotation` quaternion is known, upright rather than on its side or roof.
+pub fn car_on_ground(position: (f32, f32, f32), rotation: Option<(f32, f32, f32, f32)>) -> bool {
+    if position.2 > CAR_GROUND_Z {
+        return false;
+    }
+    rotation.is_none_or(|q| quat_up(q).2 >= CAR_UPRIGHT_MIN_UP_Z)
+}
+
+/// World direction of the car's up (+z, roof) axis for a quaternion (x, y, z, w).
+pub fn quat_up(q: (f32, f32, f32, f32)) -> (f32, f32, f32) {
+    let (x, y, z, w) = q;
+    (
+        2.0 * (x * z + w * y),
+        2.0 * (y * z - w * x),
+        1.0 - 2.0 * (x * x + y * y),
+    )
+}
+
+/// Convert a replicated quaternion (x, y, z, w) to Euler angles (roll, pitch, yaw) in
+/// radians, using the game's (Unreal / RLBot) rotator convention rather than the
+/// right-handed aerospace one: yaw is 0 facing +x and π/2 facing +y, pitch is positive
+/// nose-up, and roll is positive when the car rolls right (its +y side drops). In the
+/// game's left-handed frame this negates the aerospace pitch and roll.
+pub fn quat_to_euler(q: (f32, f32, f32, f32)) -> (f64, f64, f64) {
+    let (x, y, z, w) = (q.0 as f64, q.1 as f64, q.2 as f64, q.3 as f64);
+
+    let roll = (-2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+
+    // Forward (+x) axis of the rotated car; pitch from its elevation stays accurate near
+    // vertical, where asin of the z component loses precision.
+    let forward = (
+        1.0 - 2.0 * (y * y + z * z),
+        2.0 * (w * z + x * y),
+        2.0 * (z * x - w * y),
+    );
+    let pitch = forward.2.atan2(forward.0.hypot(forward.1));
+    let yaw = forward.1.atan2(forward.0);
+
+    (roll, pitch, yaw)
+}
+
+/// Output coordinate convention of iter_frames(coordinates=...). Every convention keeps the
+/// game's axes (uu, blue goal at -y, +z up), so positions and velocities are unchanged;
+/// they differ only in the sign of the Euler angles derived from a rotation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoordinateConvention {
+    /// `quat_to_euler`'s game rotator convention.
+    #[default]
+    Rlcoach,
+    /// RLBot reports the game's own rotator, so this matches `Rlcoach`.
+    Rlbot,
+    /// Right-handed aerospace angles of the same quaternion: pitch and roll negated.
+    Carball,
+}
+
+impl CoordinateConvention {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rlcoach" => Some(CoordinateConvention::Rlcoach),
+            "rlbot" => Some(CoordinateConvention::Rlbot),
+            "carball" => Some(CoordinateConvention::Carball),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CoordinateConvention::Rlcoach => "rlcoach",
+            CoordinateConvention::Rlbot => "rlbot",
+            CoordinateConvention::Carball => "carball",
+        }
+    }
+
+    /// Convert (roll, pitch, yaw) from the `Rlcoach` convention to this one.
+    pub fn euler(&self, (roll, pitch, yaw): (f64, f64, f64)) -> (f64, f64, f64) {
+        match self {
+            CoordinateConvention::Rlcoach | CoordinateConvention::Rlbot => (roll, pitch, yaw),
+            CoordinateConvention::Carball => (-roll, -pitch, yaw),
+        }
+    }
+}
+
+/// Inverse of `quat_to_euler`: the unit quaternion (x, y, z, w) for a (roll, pitch, yaw)
+/// rotator in radians, i.e. yaw about z, then pitch and roll about the car's own axes.
+pub fn euler_to_quat(roll: f32, pitch: f32, yaw: f32) -> (f32, f32, f32, f32) {
+    // Aerospace Z-Y-X composition with the game's pitch and roll negated.
+    let (sr, cr) = (-roll / 2.0).sin_cos();
+    let (sp, cp) = (-pitch / 2.0).sin_cos();
+    let (sy, cy) = (yaw / 2.0).sin_cos();
+    (
+        sr * cp * cy - cr * sp * sy,
+        cr * sp * cy + sr * cp * sy,
+        cr * cp * sy - sr * sp * cy,
+        cr * cp * cy + sr * sp * sy,
+    )
+}
+
+/// Quaternion for a compressed byte rotator (`boxcars::Rotation`, 256 steps per turn);
+/// missing components are 0.
+pub fn byte_rotator_to_quat(
+    pitch: Option<i8>,
+    yaw: Option<i8>,
+    roll: Option<i8>,
+) -> (f32, f32, f32, f32) {
+    let angle = |steps: Option<i8>| steps.map_or(0.0, |s| s as f32 * PI / 128.0);
+    euler_to_quat(angle(roll), angle(pitch), angle(yaw))
+}
+
+/// Rotate a vector half a turn about the vertical axis: the field as seen from the orange
+/// end, where orange attacks +y.
+pub fn half_turn(v: (f32, f32, f32)) -> (f32, f32, f32) {
+    (-v.0, -v.1, v.2)
+}
+
+/// Compose a half turn about the vertical axis onto a quaternion (x, y, z, w), i.e.
+/// (0, 0, 1, 0) * q; adds π to the yaw and keeps pitch and roll.
+pub fn half_turn_quat(q: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+    (-q.1, q.0, q.3, -q.2)
+}
+
+/// A car's (depth, width) in its own team's frame from a blue-frame `position`: the y
+/// offset from midfield toward its own goal, and the x offset to its left when facing the
+/// opponent goal (the frame is left-handed, so +x is blue's left).
+pub fn goal_relative_offsets(team: i64, position: (f32, f32, f32)) -> (f32, f32) {
+    let (x, y, _) = if team == 1 {
+        half_turn(position)
+    } else {
+        position
+    };
+    (-y, x)
+}
+
+/// Where and when a free-flying ball next touches the floor: (x, y, seconds from now).
+/// Ignores bounces, walls, spin and drag. `None` when the ball is already grounded.
+pub fn predict_landing(
+    position: (f32, f32, f32),
+    velocity: (f32, f32, f32),
+    physics: &Physics,
+) -> Option<(f32, f32, f32)> {
+    let height = position.2 - physics.ball_radius;
+    if height <= BALL_GROUNDED_UU && velocity.2 <= 0.0 {
+        return None;
+    }
+    // Positive root of height + vz t - g t² / 2 = 0.
+    let height = height.max(0.0);
+    let vz = velocity.2;
+    let g = physics.gravity;
+    let t = (vz + (vz * vz + 2.0 * g * height).sqrt()) / g;
+    Some((position.0 + velocity.0 * t, position.1 + velocity.1 * t, t))
+}
+
+/// (min, mean, max) distance over every pair of `positions`; `None` with fewer than two.
+pub fn pairwise_spacing(positions: &[(f32, f32, f32)]) -> Option<(f32, f32, f32)> {
+    let (mut min, mut sum, mut max) = (f32::INFINITY, 0.0f32, 0.0f32);
+    let mut pairs = 0usize;
+    for (i, a) in positions.iter().enumerate() {
+        for b in &positions[i + 1..] {
+            let d = distance(*a, *b);
+            min = min.min(d);
+            max = max.max(d);
+            sum += d;
+            pairs += 1;
+        }
+    }
+    (pairs > 0).then(|| (min, sum / pairs as f32, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_car_on_ground() {
+        assert!(car_on_ground((0.0, 0.0, 17.0), None));
+        assert!(!car_on_ground((0.0, 0.0, 40.0), None));
+        assert!(car_on_ground((0.0, 0.0, 17.0), Some((0.0, 0.0, 0.0, 1.0))));
+        // Rolled onto its roof: half a turn about the forward (x) axis.
+        assert!(!car_on_ground((0.0, 0.0, 17.0), Some((1.0, 0.0, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn test_classify_surface() {
+        assert_eq!(classify_surface((0.0, 0.0, 17.0)), Surface::Ground);
+        assert_eq!(classify_surface((4070.0, 0.0, 800.0)), Surface::Wall);
+        assert_eq!(classify_surface((2000.0, -5100.0, 900.0)), Surface::Wall);
+        assert_eq!(classify_surface((3500.0, 4550.0, 400.0)), Surface::Wall);
+        assert_eq!(classify_surface((0.0, 1000.0, 2027.0)), Surface::Ceiling);
+        assert_eq!(classify_surface((0.0, 0.0, 800.0)), Surface::Air);
+        // Inside the goal mouth is not the back wall.
+        assert_eq!(classify_surface((0.0, 5110.0, 300.0)), Surface::Air);
+    }
+
+    /// Unit quaternion for a rotation of `angle` radians about `axis`.
+    fn axis_angle(axis: (f32, f32, f32), angle: f32) -> (f32, f32, f32, f32) {
+        let (s, c) = (angle / 2.0).sin_cos();
+        (axis.0 * s, axis.1 * s, axis.2 * s, c)
+    }
+
+    fn assert_euler(q: (f32, f32, f32, f32), expected: (f64, f64, f64)) {
+        let (roll, pitch, yaw) = quat_to_euler(q);
+        // Compare on the circle: yaw ±π are the same heading.
+        let close = |a: f64, b: f64| {
+            let (pi, tau) = (std::f64::consts::PI, std::f64::consts::TAU);
+            ((a - b + pi).rem_euclid(tau) - pi).abs() < 1e-5
+        };
+        assert!(
+            close(roll, expected.0) && close(pitch, expected.1) && close(yaw, expected.2),
+            "{:?} -> {:?}, expected {:?}",
+            q,
+            (roll, pitch, yaw),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_quat_to_euler_facing_each_axis() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+        let up = (0.0, 0.0, 1.0);
+        // Facing +x, +y, -y and -x on the ground.
+        assert_euler((0.0, 0.0, 0.0, 1.0), (0.0, 0.0, 0.0));
+        assert_euler(axis_angle(up, FRAC_PI_2 as f32), (0.0, 0.0, FRAC_PI_2));
+        assert_euler(axis_angle(up, -FRAC_PI_2 as f32), (0.0, 0.0, -FRAC_PI_2));
+        assert_euler(axis_angle(up, PI as f32), (0.0, 0.0, PI));
+        // Rotating about +y by -θ lifts the +x nose: positive pitch. Nose straight up is +z.
+        let side = (0.0, 1.0, 0.0);
+        assert_euler(axis_angle(side, -FRAC_PI_4 as f32), (0.0, FRAC_PI_4, 0.0));
+        assert_euler(axis_angle(side, -FRAC_PI_2 as f32), (0.0, FRAC_PI_2, 0.0));
+        assert_euler(axis_angle(side, FRAC_PI_2 as f32), (0.0, -FRAC_PI_2, 0.0));
+        // Rotating about +x by -θ drops the +y side: the car rolls right, positive roll.
+        let forward = (1.0, 0.0, 0.0);
+        assert_euler(
+            axis_angle(forward, -FRAC_PI_4 as f32),
+            (FRAC_PI_4, 0.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn test_euler_to_quat_round_trip() {
+        for (roll, pitch, yaw) in [(0.0, 0.0, 0.0), (0.2, 0.3, 0.5), (-1.0, -0.7, 2.5)] {
+            let q = euler_to_quat(roll, pitch, yaw);
+            assert_euler(q, (roll as f64, pitch as f64, yaw as f64));
+        }
+        // 64 steps is a quarter turn: facing +y.
+        assert_euler(
+            byte_rotator_to_quat(None, Some(64), None),
+            (0.0, 0.0, std::f64::consts::FRAC_PI_2),
+        );
+    }
+
+    #[test]
+    fn test_carball_euler_is_aerospace() {
+        // A right-handed +0.3 rad turn about +y tips the nose down: aerospace pitch +0.3.
+        let q = axis_angle((0.0, 1.0, 0.0), 0.3);
+        assert_euler(q, (0.0, -0.3, 0.0));
+        let (roll, pitch, yaw) = CoordinateConvention::Carball.euler(quat_to_euler(q));
+        assert!(roll.abs() < 1e-5 && (pitch - 0.3).abs() < 1e-5 && yaw.abs() < 1e-5);
+        assert_eq!(
+            CoordinateConvention::Rlbot.euler((0.1, 0.2, 0.3)),
+            (0.1, 0.2, 0.3)
+        );
+        assert_eq!(CoordinateConvention::from_name("unity"), None);
+    }
+
+    #[test]
+    fn test_predict_landing() {
+        // Dropped from 650 uu above the floor: lands after sqrt(2) s.
+        let soccar = Physics::SOCCAR;
+        let drop = predict_landing(
+            (100.0, 200.0, 650.0 + BALL_RADIUS),
+            (0.0, 0.0, 0.0),
+            &soccar,
+        );
+        let (x, y, t) = drop.unwrap();
+        assert_eq!((x, y), (100.0, 200.0));
+        assert!((t - std::f32::consts::SQRT_2).abs() < 1e-4);
+
+        // Popped up off the floor at 650 uu/s with forward speed: airborne for 2 s.
+        let (x, y, t) =
+            predict_landing((0.0, 0.0, BALL_RADIUS), (500.0, -250.0, 650.0), &soccar).unwrap();
+        assert!((t - 2.0).abs() < 1e-4);
+        assert!((x - 1000.0).abs() < 1e-2 && (y + 500.0).abs() < 1e-2);
+
+        // Rolling on the floor.
+        assert!(predict_landing((0.0, 0.0, BALL_RADIUS), (800.0, 0.0, 0.0), &soccar).is_none());
+
+        // Half gravity doubles the flight time of the same pop-up; a big ball resting
+        // at its own radius is grounded.
+        let mutated = Physics {
+            gravity: 325.0,
+            ball_radius: 200.0,
+        };
+        let (_, _, t) = predict_landing((0.0, 0.0, 200.0), (0.0, 0.0, 650.0), &mutated).unwrap();
+        assert!((t - 4.0).abs() < 1e-4);
+        assert!(predict_landing((0.0, 0.0, 200.0), (0.0, 0.0, 0.0), &mutated).is_none());
+    }
+
+    #[test]
+    fn test_pairwise_spacing() {
+        assert!(pairwise_spacing(&[]).is_none());
+        assert!(pairwise_spacing(&[(0.0, 0.0, 17.0)]).is_none());
+        let trio = [(0.0, 0.0, 17.0), (300.0, 0.0, 17.0), (0.0, 400.0, 17.0)];
+        let (min, mean, max) = pairwise_spacing(&trio).unwrap();
+        assert_eq!((min, max), (300.0, 500.0));
+        assert!((mean - 400.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_half_turn_quat_adds_pi_to_yaw() {
+        // An arbitrary orientation: yawed 0.5 rad, pitched 0.3 rad, rolled 0.2 rad.
+        let (sp, cp) = (0.15f32.sin(), 0.15f32.cos());
+        let (sr, cr) = (0.1f32.sin(), 0.1f32.cos());
+        let (sy, cy) = (0.25f32.sin(), 0.25f32.cos());
+        let mul = |a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)| {
+            (
+                a.3 * b.0 + a.0 * b.3 + a.1 * b.2 - a.2 * b.1,
+                a.3 * b.1 - a.0 * b.2 + a.1 * b.3 + a.2 * b.0,
+                a.3 * b.2 + a.0 * b.1 - a.1 * b.0 + a.2 * b.3,
+                a.3 * b.3 - a.0 * b.0 - a.1 * b.1 - a.2 * b.2,
+            )
+        };
+        let q = mul(
+            mul((0.0, 0.0, sy, cy), (0.0, -sp, 0.0, cp)),
+            (-sr, 0.0, 0.0, cr),
+        );
+        let (roll, pitch, yaw) = quat_to_euler(q);
+        let (roll2, pitch2, yaw2) = quat_to_euler(half_turn_quat(q));
+        assert!((roll2 - roll).abs() < 1e-5);
+        assert!((pitch2 - pitch).abs() < 1e-5);
+        let turned = (yaw2 - yaw).rem_euclid(2.0 * std::f64::consts::PI);
+        assert!((turned - std::f64::consts::PI).abs() < 1e-5);
+        assert_eq!(half_turn((1.0, -2.0, 3.0)), (-1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_goal_relative_width_is_to_the_left() {
+        // Both cars on the +x side in their own half: blue's left, orange's right.
+        assert_eq!(
+            goal_relative_offsets(0, (1000.0, -2000.0, 17.0)),
+            (2000.0, 1000.0)
+        );
+        assert_eq!(
+            goal_relative_offsets(1, (1000.0, 2000.0, 17.0)),
+            (2000.0, -1000.0)
+        );
+    }
+}
@@ -0,0 +1,406 @@
+/// Goal events from ball goal-line crossings, attributed via touch history.
+///
+/// A goal is the first live frame in which the ball centre passes the goal line
+/// (`Physics::goal_line_y`), or the live → dead transition (the server freezes the ball as
+/// it scores) with the ball centre already past the goal line at `BACK_WALL_Y`. Blue
+/// (team 0) scores in the +y net. The scorer is the last scoring-team touch. When a
+/// defender touched the ball last, within `DEFLECTION_WINDOW_S` of a scoring-team touch,
+/// the goal is `deflected` and both players are kept; with no such attacker touch it is an
+/// own goal credited to the defender.
+///
+/// Goal type, from the scoring touch:
+///   "redirect" — a different teammate touched the ball within `REDIRECT_WINDOW_S` before
+///   "aerial"   — ball above `AERIAL_MIN_BALL_Z` and the scorer's car off every surface
+///   "ground"   — anything else
+use crate::geometry::{classify_surface, magnitude, Physics, Surface, BACK_WALL_Y};
+use crate::header::HeaderGoal;
+use crate::network::FrameSnapshot;
+use crate::shots::ShotFeatures;
+use crate::touches::Touch;
+
+pub const DEFLECTION_WINDOW_S: f32 = 1.0;
+pub const REDIRECT_WINDOW_S: f32 = 1.0;
+/// Ball centre height (uu) above which an airborne touch counts as aerial.
+pub const AERIAL_MIN_BALL_Z: f32 = 300.0;
+/// Max distance (network frames) from a header `Goals` frame to a detected goal for
+/// `nearest_goal_frame` to match them; about a second at 30 fps.
+pub const GOAL_VERIFY_WINDOW_FRAMES: usize = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GoalType {
+    Aerial,
+    Ground,
+    Redirect,
+}
+
+impl GoalType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalType::Aerial => "aerial",
+            GoalType::Ground => "ground",
+            GoalType::Redirect => "redirect",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TouchRecord {
+    timestamp: f32,
+    slot: usize,
+    team: i64,
+    ball_position: (f32, f32, f32),
+    ball_velocity: (f32, f32, f32),
+    car_surface: Surface,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoalEvent {
+    pub timestamp: f32,
+    pub scoring_team: i64,
+    pub scorer_slot: Option<usize>,
+    /// Time of the scoring touch.
+    pub touch_time: Option<f32>,
+    pub goal_type: GoalType,
+    /// Ball speed at the goal-line crossing (uu/s).
+    pub shot_speed: f32,
+    /// Defender whose last touch deflected the shot in.
+    pub deflected_by_slot: Option<usize>,
+    /// Ball state just after the scoring touch, for shot quality.
+    pub shot: Option<ShotFeatures>,
+}
+
+pub struct GoalDetector {
+    /// Touches since the last kickoff.
+    touches: Vec<TouchRecord>,
+    scored: bool,
+    /// Ball velocity on the previous live frame; the frozen goal frame replicates zero.
+    last_live_ball_velocity: Option<(f32, f32, f32)>,
+    goal_line_y: f32,
+}
+
+impl Default for GoalDetector {
+    fn default() -> Self {
+        Self::with_physics(&Physics::SOCCAR)
+    }
+}
+
+impl GoalDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A detector for a ball of `physics.ball_radius`, which moves the goal line.
+    pub fn with_physics(physics: &Physics) -> Self {
+        GoalDetector {
+            touches: Vec::new(),
+            scored: false,
+            last_live_ball_velocity: None,
+            goal_line_y: physics.goal_line_y(),
+        }
+    }
+
+    pub fn observe(&mut self, frame: &FrameSnapshot, touch: Option<&Touch>) -> Option<GoalEvent> {
+        if !frame.is_live {
+            let frozen_in_goal = frame.ball.position.1.abs() > BACK_WALL_Y;
+            let goal = match self.last_live_ball_velocity.take() {
+                Some(velocity) if frozen_in_goal && !self.scored => {
+                    Some(self.goal(frame, magnitude(velocity)))
+                }
+                _ => None,
+            };
+            self.touches.clear();
+            self.scored = false;
+            return goal;
+        }
+        self.last_live_ball_velocity = Some(frame.ball.velocity);
+        if let Some(touch) = touch {
+            let car = frame.players.iter().find(|p| p.slot == touch.slot);
+            self.touches.push(TouchRecord {
+                timestamp: frame.timestamp,
+                slot: touch.slot,
+                team: touch.team,
+                ball_position: frame.ball.position,
+                ball_velocity: frame.ball.velocity,
+                car_surface: car.map_or(Surface::Air, |car| classify_surface(car.position)),
+            });
+        }
+        if self.scored || frame.ball.position.1.abs() <= self.goal_line_y {
+            return None;
+        }
+        Some(self.goal(frame, magnitude(frame.ball.velocity)))
+    }
+
+    fn goal(&mut self, frame: &FrameSnapshot, shot_speed: f32) -> GoalEvent {
+        self.scored = true;
+        let scoring_team = if frame.ball.position.1 > 0.0 { 0 } else { 1 };
+
+        let mut scorer_idx = self.touches.len().checked_sub(1);
+        let mut deflected_by_slot = None;
+        if let Some(last_idx) = scorer_idx {
+            let last = self.touches[last_idx];
+            if last.team != scoring_team {
+                let attacker = self.touches[..last_idx].iter().rposition(|t| {
+                    t.team == scoring_team && last.timestamp - t.timestamp <= DEFLECTION_WINDOW_S
+                });
+                if attacker.is_some() {
+                    scorer_idx = attacker;
+                    deflected_by_slot = Some(last.slot);
+                }
+            }
+        }
+
+        let scorer = scorer_idx.map(|idx| self.touches[idx]);
+        let goal_type = match scorer_idx {
+            Some(idx) => self.goal_type(idx),
+            None => GoalType::Ground,
+        };
+        GoalEvent {
+            timestamp: frame.timestamp,
+            scoring_team,
+            scorer_slot: scorer.map(|t| t.slot),
+            touch_time: scorer.map(|t| t.timestamp),
+            goal_type,
+            shot_speed,
+            deflected_by_slot,
+            shot: scorer.map(|t| ShotFeatures::new(t.ball_position, t.ball_velocity, scoring_team)),
+        }
+    }
+
+    fn goal_type(&self, scorer_idx: usize) -> GoalType {
+        let scorer = self.touches[scorer_idx];
+        let assisted = scorer_idx
+            .checked_sub(1)
+            .map(|idx| self.touches[idx])
+            .is_some_and(|prev| {
+                prev.team == scorer.team
+                    && prev.slot != scorer.slot
+                    && scorer.timestamp - prev.timestamp <= REDIRECT_WINDOW_S
+            });
+        if assisted {
+            GoalType::Redirect
+        } else if scorer.ball_position.2 > AERIAL_MIN_BALL_Z && scorer.car_surface == Surface::Air {
+            GoalType::Aerial
+        } else {
+            GoalType::Ground
+        }
+    }
+}
+
+/// The frame in `goal_frames` (network frame indices of detected goals) nearest to
+/// `header_frame`, if within `GOAL_VERIFY_WINDOW_FRAMES`; the earlier one on a tie.
+pub fn nearest_goal_frame(goal_frames: &[usize], header_frame: usize) -> Option<usize> {
+    goal_frames
+        .iter()
+        .copied()
+        .filter(|frame| frame.abs_diff(header_frame) <= GOAL_VERIFY_WINDOW_FRAMES)
+        .min_by_key(|frame| frame.abs_diff(header_frame))
+}
+
+/// A header goal whose scorer differs from the player the frames credit with it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScorerMismatch {
+    /// Network frame of the goal crossing.
+    pub frame: usize,
+    pub header_scorer: String,
+    /// Header roster name of the slot credited by the goal detector, None for a slot
+    /// beyond the roster.
+    pub frame_scorer: Option<String>,
+}
+
+/// Cross-check of the car → player slot mapping against the header goal scorers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScorerCheck {
+    /// Header goals that could be compared.
+    pub checked: usize,
+    pub mismatches: Vec<ScorerMismatch>,
+}
+
+impl ScorerCheck {
+    /// Share of compared goals whose scorer agrees, None when none could be compared.
+    pub fn confidence(&self) -> Option<f32> {
+        (self.checked > 0)
+            .then(|| (self.checked - self.mismatches.len()) as f32 / self.checked as f32)
+    }
+}
+
+/// Compare each header goal's `PlayerName` with the roster name (`slot_names`, by slot) of
+/// the scorer the goal detector credited at the matching crossing. `detected` holds
+/// (network frame, credited slot) per detected goal. Goals without a scorer name, a
+/// crossing within `GOAL_VERIFY_WINDOW_FRAMES` or a credited touch are not compared.
+pub fn check_scorer_mapping(
+    header_goals: &[HeaderGoal],
+    detected: &[(usize, Option<usize>)],
+    slot_names: &[String],
+) -> ScorerCheck {
+    let goal_frames: Vec<usize> = detected.iter().map(|(frame, _)| *frame).collect();
+    let mut check = ScorerCheck::default();
+    for goal in header_goals {
+        let (Some(header_scorer), Some(header_frame)) = (&goal.player_name, goal.frame) else {
+            continue;
+        };
+        let Some(frame) = nearest_goal_frame(&goal_frames, header_frame) else {
+            continue;
+        };
+        let Some(slot) = detected
+            .iter()
+            .find(|(f, _)| *f == frame)
+            .and_then(|(_, slot)| *slot)
+        else {
+            continue;
+        };
+        check.checked += 1;
+        let frame_scorer = slot_names.get(slot);
+        if frame_scorer != Some(header_scorer) {
+            check.mismatches.push(ScorerMismatch {
+                frame,
+                header_scorer: header_scorer.clone(),
+                frame_scorer: frame_scorer.cloned(),
+            });
+        }
+    }
+    check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, player};
+    use crate::network::{BallSnapshot, PlayerSnapshot};
+
+    fn frame(timestamp: f32, ball: (f32, f32, f32), cars: &[(usize, i64, f32)]) -> FrameSnapshot {
+        let players = cars
+            .iter()
+            .map(|&(slot, team, z)| PlayerSnapshot {
+                team,
+                position: (ball.0, ball.1 - 150.0, z),
+                ..player(slot)
+            })
+            .collect();
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position: ball,
+                velocity: (0.0, 1500.0, 0.0),
+                ..test_support::ball()
+            },
+            ..test_support::frame(timestamp, players)
+        }
+    }
+
+    #[test]
+    fn test_aerial_goal() {
+        let mut goals = GoalDetector::new();
+        let cars = [(0, 0, 500.0), (1, 1, 17.0)];
+        let touch = Touch { slot: 0, team: 0 };
+        assert!(goals
+            .observe(&frame(1.0, (0.0, 4000.0, 600.0), &cars), Some(&touch))
+            .is_none());
+        let goal = goals
+            .observe(&frame(2.0, (0.0, 5300.0, 400.0), &cars), None)
+            .unwrap();
+        assert_eq!(goal.scoring_team, 0);
+        assert_eq!(goal.scorer_slot, Some(0));
+        assert_eq!(goal.goal_type, GoalType::Aerial);
+        assert_eq!(goal.shot_speed, 1500.0);
+        assert_eq!(goal.shot.map(|shot| shot.distance), Some(1120.0));
+        // One event per crossing.
+        assert!(goals
+            .observe(&frame(2.1, (0.0, 5400.0, 400.0), &cars), None)
+            .is_none());
+    }
+
+    #[test]
+    fn test_big_ball_goal_line() {
+        let physics = Physics {
+            ball_radius: 200.0,
+            ..Physics::SOCCAR
+        };
+        let mut goals = GoalDetector::with_physics(&physics);
+        let cars = [(0, 0, 17.0)];
+        // 5300 uu is over the standard goal line but short of a 200 uu ball's.
+        assert!(goals
+            .observe(&frame(1.0, (0.0, 5300.0, 200.0), &cars), None)
+            .is_none());
+        let goal = goals
+            .observe(&frame(1.1, (0.0, 5400.0, 200.0), &cars), None)
+            .unwrap();
+        assert_eq!(goal.scoring_team, 0);
+    }
+
+    #[test]
+    fn test_frozen_goal_frame() {
+        let mut goals = GoalDetector::new();
+        let cars = [(0, 1, 17.0)];
+        goals.observe(
+            &frame(1.0, (0.0, -5000.0, 93.0), &cars),
+            Some(&Touch { slot: 0, team: 1 }),
+        );
+        let mut frozen = frame(1.1, (0.0, -5200.0, 93.0), &cars);
+        frozen.is_live = false;
+        frozen.ball.velocity = (0.0, 0.0, 0.0);
+        let goal = goals.observe(&frozen, None).unwrap();
+        assert_eq!(goal.scoring_team, 1);
+        assert_eq!(goal.scorer_slot, Some(0));
+        assert_eq!(goal.shot_speed, 1500.0);
+        assert!(goals.observe(&frozen, None).is_none());
+    }
+
+    #[test]
+    fn test_deflected_redirect() {
+        let mut goals = GoalDetector::new();
+        let cars = [(0, 1, 17.0), (1, 1, 17.0), (2, 0, 17.0)];
+        goals.observe(
+            &frame(1.0, (0.0, -3000.0, 93.0), &cars),
+            Some(&Touch { slot: 0, team: 1 }),
+        );
+        goals.observe(
+            &frame(1.5, (0.0, -4000.0, 93.0), &cars),
+            Some(&Touch { slot: 1, team: 1 }),
+        );
+        goals.observe(
+            &frame(1.8, (0.0, -5000.0, 93.0), &cars),
+            Some(&Touch { slot: 2, team: 0 }),
+        );
+        let goal = goals
+            .observe(&frame(1.9, (0.0, -5250.0, 93.0), &cars), None)
+            .unwrap();
+        assert_eq!(goal.scoring_team, 1);
+        assert_eq!(goal.scorer_slot, Some(1));
+        assert_eq!(goal.deflected_by_slot, Some(2));
+        assert_eq!(goal.goal_type, GoalType::Redirect);
+    }
+
+    #[test]
+    fn test_nearest_goal_frame() {
+        let goal_frames = [1200, 3400, 3460];
+        assert_eq!(nearest_goal_frame(&goal_frames, 1198), Some(1200));
+        assert_eq!(nearest_goal_frame(&goal_frames, 3430), Some(3400));
+        assert_eq!(nearest_goal_frame(&goal_frames, 3440), Some(3460));
+        assert_eq!(nearest_goal_frame(&goal_frames, 2000), None);
+        assert_eq!(nearest_goal_frame(&[], 10), None);
+    }
+
+    #[test]
+    fn test_check_scorer_mapping() {
+        let goal = |frame: usize, name: &str| HeaderGoal {
+            frame: Some(frame),
+            player_name: Some(name.to_string()),
+            team: Some(0),
+        };
+        let names = ["Alpha", "Bravo"].map(String::from);
+        let header = [goal(100, "Alpha"), goal(500, "Bravo"), goal(900, "Alpha")];
+        // The second goal is credited to slot 0, and the third has no credited touch.
+        let detected = [(102, Some(0)), (505, Some(0)), (901, None)];
+        let check = check_scorer_mapping(&header, &detected, &names);
+        assert_eq!(check.checked, 2);
+        assert_eq!(
+            check.mismatches,
+            vec![ScorerMismatch {
+                frame: 505,
+                header_scorer: "Bravo".to_string(),
+                frame_scorer: Some("Alpha".to_string()),
+            }]
+        );
+        assert_eq!(check.confidence(), Some(0.5));
+        assert_eq!(ScorerCheck::default().confidence(), None);
+    }
+}
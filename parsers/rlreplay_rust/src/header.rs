@@ -0,0 +1,606 @@
+/// Lightweight header (`replay.properties`) helpers shared by the header-only entry points
+/// and the network pass. Nothing here touches network frames.
+use boxcars::HeaderProp;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+pub fn find_prop<'a>(props: &'a [(String, HeaderProp)], key: &str) -> Option<&'a HeaderProp> {
+    props.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+/// Extract `(name, team)` pairs from the header `PlayerStats` array in header order.
+pub fn header_players(properties: &[(String, HeaderProp)]) -> Vec<(String, i64)> {
+    let mut players: Vec<(String, i64)> = Vec::new();
+    for (k, v) in properties {
+        if k != "PlayerStats" {
+            continue;
+        }
+        if let Some(arr) = v.as_array() {
+            for entry in arr {
+                let mut name: Option<String> = None;
+                let mut team: i64 = 0;
+                for (kk, vv) in entry {
+                    match (kk.as_str(), vv) {
+                        ("Name", hp) | ("PlayerName", hp) => {
+                            if let Some(s) = hp.as_string() {
+                                name = Some(s.to_string());
+                            }
+                        }
+                        ("Team", hp) | ("PlayerTeam", hp) => {
+                            if let Some(t) = hp.as_i32() {
+                                team = t as i64;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(n) = name {
+                    players.push((n, team));
+                }
+            }
+        }
+    }
+    players
+}
+
+/// A scalar property as text: Str / Name as-is, Int / QWord in decimal, Byte as its value.
+/// Identifier properties are stored as any of these depending on the build.
+pub fn prop_text(prop: &HeaderProp) -> Option<String> {
+    match prop {
+        HeaderProp::Str(s) | HeaderProp::Name(s) => Some(s.clone()),
+        HeaderProp::Int(i) => Some(i.to_string()),
+        HeaderProp::QWord(q) => Some(q.to_string()),
+        HeaderProp::Byte { value, .. } => value.clone(),
+        _ => None,
+    }
+}
+
+/// Read the `MapName` header property (empty string when absent).
+pub fn header_map_name(properties: &[(String, HeaderProp)]) -> String {
+    find_prop(properties, "MapName")
+        .and_then(prop_text)
+        .unwrap_or_default()
+}
+
+/// `PlaylistID` (string, name or integer), else a playlist inferred from `MatchType` and
+/// `TeamSize` for older replays without one.
+pub fn header_playlist_id(properties: &[(String, HeaderProp)]) -> Option<String> {
+    if let Some(id) = find_prop(properties, "PlaylistID").and_then(prop_text) {
+        return Some(id);
+    }
+    let team_size = find_prop(properties, "TeamSize")
+        .and_then(|p| p.as_i32())
+        .unwrap_or(0);
+    match find_prop(properties, "MatchType").and_then(|p| p.as_string())? {
+        "Online" => Some(format!("inferred_{}", team_size)),
+        "Tournament" => Some("tournament".to_string()),
+        "Private" | "Offline" => Some("private".to_string()),
+        _ => None,
+    }
+}
+
+/// Known numeric playlist codes (RLBot playlist ids) and their names.
+const PLAYLIST_NAMES: [(u32, &str); 24] = [
+    (1, "casual_duel"),
+    (2, "casual_doubles"),
+    (3, "casual_standard"),
+    (4, "casual_chaos"),
+    (6, "private_match"),
+    (7, "season"),
+    (8, "offline_splitscreen"),
+    (9, "training"),
+    (10, "ranked_duel"),
+    (11, "ranked_doubles"),
+    (12, "ranked_solo_standard"),
+    (13, "ranked_standard"),
+    (15, "snow_day"),
+    (16, "rocket_labs"),
+    (17, "hoops"),
+    (18, "rumble"),
+    (22, "tournament_match"),
+    (23, "dropshot"),
+    (27, "ranked_hoops"),
+    (28, "ranked_rumble"),
+    (29, "ranked_dropshot"),
+    (30, "ranked_snow_day"),
+    (33, "spike_rush"),
+    (34, "tournament"),
+];
+
+/// Readable name for a playlist id from `header_playlist_id`: known numeric codes map to
+/// their name, anything else (unknown codes, string ids, inferred playlists) is returned
+/// unchanged.
+pub fn playlist_name(playlist_id: &str) -> String {
+    playlist_id
+        .parse::<u32>()
+        .ok()
+        .and_then(|code| PLAYLIST_NAMES.iter().find(|(c, _)| *c == code))
+        .map_or_else(|| playlist_id.to_string(), |(_, name)| name.to_string())
+}
+
+/// Coarse match category for dataset partitioning: "tournament", "private", "ranked",
+/// "casual" or "unknown". A non-empty, non-zero `TournamentID` wins, then private flags
+/// (`MatchType` "Private" / "Offline" or a true `bPrivateMatch`), then the numeric
+/// `PlaylistID`. Online replays without a known playlist code are "unknown".
+pub fn match_category(properties: &[(String, HeaderProp)]) -> &'static str {
+    let tournament_id = ["TournamentID", "TournamentId"]
+        .iter()
+        .find_map(|key| find_prop(properties, key).and_then(prop_text));
+    if tournament_id.is_some_and(|id| !id.is_empty() && id != "0") {
+        return "tournament";
+    }
+    let match_type = find_prop(properties, "MatchType").and_then(|p| p.as_string());
+    let private_flag = matches!(
+        find_prop(properties, "bPrivateMatch"),
+        Some(HeaderProp::Bool(true))
+    );
+    if private_flag || matches!(match_type, Some("Private" | "Offline")) {
+        return "private";
+    }
+    if match_type == Some("Tournament") {
+        return "tournament";
+    }
+    let code = find_prop(properties, "PlaylistID")
+        .and_then(prop_text)
+        .and_then(|id| id.parse::<u32>().ok());
+    match code {
+        Some(10..=13 | 27..=30) => "ranked",
+        Some(1..=4 | 15..=18 | 23 | 33) => "casual",
+        Some(6 | 8) => "private",
+        Some(22 | 34) => "tournament",
+        _ => "unknown",
+    }
+}
+
+/// One entry of the header `Goals` array.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderGoal {
+    /// Network frame index the game recorded for the goal.
+    pub frame: Option<usize>,
+    pub player_name: Option<String>,
+    pub team: Option<i64>,
+}
+
+/// The header `Goals` array in order; missing fields are None.
+pub fn header_goals(properties: &[(String, HeaderProp)]) -> Vec<HeaderGoal> {
+    let Some(arr) = find_prop(properties, "Goals").and_then(|p| p.as_array()) else {
+        return Vec::new();
+    };
+    arr.iter()
+        .map(|entry| HeaderGoal {
+            frame: find_prop(entry, "frame")
+                .and_then(|p| p.as_i32())
+                .and_then(|frame| usize::try_from(frame).ok()),
+            player_name: find_prop(entry, "PlayerName")
+                .and_then(|p| p.as_string())
+                .map(str::to_string),
+            team: find_prop(entry, "PlayerTeam")
+                .and_then(|p| p.as_i32())
+                .map(i64::from),
+        })
+        .collect()
+}
+
+/// Largest team in `PlayerStats`, else the `TeamSize` property, else 0.
+pub fn header_team_size(properties: &[(String, HeaderProp)]) -> i64 {
+    let mut team_counts: HashMap<i64, i64> = HashMap::new();
+    for (_, team) in header_players(properties) {
+        *team_counts.entry(team).or_insert(0) += 1;
+    }
+    team_counts.values().copied().max().unwrap_or_else(|| {
+        find_prop(properties, "TeamSize")
+            .and_then(|p| p.as_i32())
+            .map_or(0, |ts| ts as i64)
+    })
+}
+
+/// Match length in seconds from `NumFrames` at 30 fps, 0 when absent.
+pub fn header_match_length(properties: &[(String, HeaderProp)]) -> f64 {
+    find_prop(properties, "NumFrames")
+        .and_then(|p| p.as_i32())
+        .map_or(0.0, |frames| frames as f64 / 30.0)
+}
+
+/// `[Team0Score, Team1Score]`, 0 when absent.
+pub fn header_team_scores(properties: &[(String, HeaderProp)]) -> [i64; 2] {
+    ["Team0Score", "Team1Score"].map(|key| {
+        find_prop(properties, key)
+            .and_then(|p| p.as_i32())
+            .map_or(0, |score| score as i64)
+    })
+}
+
+/// Winning team (0 or 1). The game's `WinningTeam` wins when recorded: on a forfeit it
+/// names the team that did not forfeit, whatever the score. Otherwise the team ahead on
+/// `header_team_scores`, which is also the fallback for a forfeit (`bForfeit`) without
+/// `WinningTeam`, as only the trailing team can forfeit. None on a tie (an unfinished or
+/// abandoned match).
+pub fn header_winner(properties: &[(String, HeaderProp)]) -> Option<i64> {
+    let recorded = find_prop(properties, "WinningTeam")
+        .and_then(|p| p.as_i32())
+        .filter(|team| matches!(team, 0 | 1));
+    if let Some(team) = recorded {
+        return Some(team as i64);
+    }
+    let [blue, orange] = header_team_scores(properties);
+    match blue.cmp(&orange) {
+        Ordering::Greater => Some(0),
+        Ordering::Less => Some(1),
+        Ordering::Equal => None,
+    }
+}
+
+/// "win" | "loss" for `team` given the match `winner`; None without a winner.
+pub fn team_result(winner: Option<i64>, team: i64) -> Option<&'static str> {
+    winner.map(|winner| if winner == team { "win" } else { "loss" })
+}
+
+/// Per-team sums of the integer `PlayerStats` field `key` ("Shots", "Saves", ...), indexed
+/// by team; None when no entry carries the field.
+pub fn header_team_totals(properties: &[(String, HeaderProp)], key: &str) -> Option<[i64; 2]> {
+    let arr = find_prop(properties, "PlayerStats")?.as_array()?;
+    let mut totals = [0; 2];
+    let mut found = false;
+    for entry in arr {
+        let team = find_prop(entry, "Team")
+            .or_else(|| find_prop(entry, "PlayerTeam"))
+            .and_then(|p| p.as_i32());
+        let value = find_prop(entry, key).and_then(|p| p.as_i32());
+        if let (Some(team @ 0..=1), Some(value)) = (team, value) {
+            totals[team as usize] += value as i64;
+            found = true;
+        }
+    }
+    found.then_some(totals)
+}
+
+/// Top-level properties that identify a replay, match or server, matched case-insensitively.
+/// Other keys ending in "Id" (`BuildID`, ...) describe the game build, not the match.
+pub const HEADER_IDENTIFIER_KEYS: [&str; 4] = ["Id", "MatchGUID", "SessionId", "ServerId"];
+
+/// Top-level identifier properties (`HEADER_IDENTIFIER_KEYS`) as `(key, text)` in header
+/// order, with the value rendered by `prop_text` (QWords in decimal). Only scalar
+/// properties that are present are returned; the first occurrence of a key wins.
+pub fn header_identifiers(properties: &[(String, HeaderProp)]) -> Vec<(String, String)> {
+    let mut out: Vec<(String, String)> = Vec::new();
+    for (k, v) in properties {
+        let listed = HEADER_IDENTIFIER_KEYS
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(k));
+        if !listed || out.iter().any(|(o, _)| o == k) {
+            continue;
+        }
+        if let Some(text) = prop_text(v) {
+            out.push((k.clone(), text));
+        }
+    }
+    out
+}
+
+/// One `PlayerStats` entry reduced to identity fields.
+#[derive(Clone, Debug)]
+pub struct RosterEntry {
+    pub name: String,
+    pub team: i64,
+    /// Platform account id as a decimal string (Steam / PSN / Xbox), or the Epic account id
+    /// for Epic players whose `OnlineID` is 0.
+    pub online_id: Option<String>,
+    /// Platform without the `OnlinePlatform_` prefix, e.g. "Steam", "Epic", "PS4".
+    pub platform: Option<String>,
+}
+
+fn epic_account_id(player_id: &HeaderProp) -> Option<String> {
+    if let HeaderProp::Struct { fields, .. } = player_id {
+        if let Some(s) = find_prop(fields, "EpicAccountId").and_then(|v| v.as_string()) {
+            if !s.is_empty() {
+                return Some(s.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Roster from `PlayerStats` only, in header order. Entries without a name are skipped,
+/// matching `header_players`.
+pub fn roster_entries(properties: &[(String, HeaderProp)]) -> Vec<RosterEntry> {
+    let mut out: Vec<RosterEntry> = Vec::new();
+    let Some(arr) = find_prop(properties, "PlayerStats").and_then(|p| p.as_array()) else {
+        return out;
+    };
+    for entry in arr {
+        let mut name: Option<String> = None;
+        let mut team: i64 = 0;
+        let mut online_id: Option<String> = None;
+        let mut epic_id: Option<String> = None;
+        let mut platform: Option<String> = None;
+        for (k, v) in entry {
+            match (k.as_str(), v) {
+                ("Name", hp) | ("PlayerName", hp) => {
+                    if let Some(s) = hp.as_string() {
+                        name = Some(s.to_string());
+                    }
+                }
+                ("Team", hp) | ("PlayerTeam", hp) => {
+                    if let Some(t) = hp.as_i32() {
+                        team = t as i64;
+                    }
+                }
+                ("OnlineID", HeaderProp::QWord(id)) if *id != 0 => {
+                    online_id = Some(id.to_string());
+                }
+                ("PlayerID", hp) => {
+                    epic_id = epic_account_id(hp);
+                }
+                ("Platform", HeaderProp::Byte { value: Some(v), .. }) => {
+                    platform = Some(v.strip_prefix("OnlinePlatform_").unwrap_or(v).to_string());
+                }
+                _ => {}
+            }
+        }
+        if let Some(name) = name {
+            out.push(RosterEntry {
+                name,
+                team,
+                online_id: online_id.or(epic_id),
+                platform,
+            });
+        }
+    }
+    out
+}
+
+/// The player whose client recorded the replay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recorder {
+    pub name: Option<String>,
+    /// As `RosterEntry::online_id`.
+    pub online_id: Option<String>,
+}
+
+/// Account id from a `UniqueNetId` struct (`Uid`, else `EpicAccountId`), a QWord or a
+/// string; None when empty or 0.
+fn unique_net_id(prop: &HeaderProp) -> Option<String> {
+    match prop {
+        HeaderProp::Struct { fields, .. } => find_prop(fields, "Uid")
+            .and_then(unique_net_id)
+            .or_else(|| epic_account_id(prop)),
+        HeaderProp::QWord(id) if *id != 0 => Some(id.to_string()),
+        HeaderProp::Str(id) if !id.is_empty() && id != "0" => Some(id.clone()),
+        _ => None,
+    }
+}
+
+/// The recorder of a client-side replay, from the top-level `PrimaryPlayerId` (account id)
+/// and `PlayerName` (owner name) properties, each completed from the matching `PlayerStats`
+/// entry when the other is missing. Without either, a `PrimaryPlayerTeam` with a single
+/// player on it names that player. None when nothing identifies the recorder (server-side
+/// replays).
+pub fn header_recorder(properties: &[(String, HeaderProp)]) -> Option<Recorder> {
+    let roster = roster_entries(properties);
+    let online_id = ["PrimaryPlayerId", "PrimaryPlayerID"]
+        .iter()
+        .find_map(|key| find_prop(properties, key).and_then(unique_net_id));
+    let name = find_prop(properties, "PlayerName")
+        .and_then(|p| p.as_string())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string);
+    let entry = match (&online_id, &name) {
+        (Some(id), _) => roster
+            .iter()
+            .find(|entry| entry.online_id.as_ref() == Some(id)),
+        (None, Some(name)) => roster.iter().find(|entry| &entry.name == name),
+        (None, None) => {
+            let team = find_prop(properties, "PrimaryPlayerTeam").and_then(|p| p.as_i32())?;
+            let mut on_team = roster.iter().filter(|entry| entry.team == team as i64);
+            match (on_team.next(), on_team.next()) {
+                (Some(only), None) => Some(only),
+                _ => return None,
+            }
+        }
+    };
+    Some(Recorder {
+        name: name.or_else(|| entry.map(|entry| entry.name.clone())),
+        online_id: online_id.or_else(|| entry.and_then(|entry| entry.online_id.clone())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(
+        name: &str,
+        team: i32,
+        online_id: u64,
+        epic: &str,
+        platform: &str,
+    ) -> Vec<(String, HeaderProp)> {
+        vec![
+            ("Name".to_string(), HeaderProp::Str(name.to_string())),
+            ("Team".to_string(), HeaderProp::Int(team)),
+            ("OnlineID".to_string(), HeaderProp::QWord(online_id)),
+            (
+                "PlayerID".to_string(),
+                HeaderProp::Struct {
+                    name: "UniqueNetId".to_string(),
+                    fields: vec![(
+                        "EpicAccountId".to_string(),
+                        HeaderProp::Str(epic.to_string()),
+                    )],
+                },
+            ),
+            (
+                "Platform".to_string(),
+                HeaderProp::Byte {
+                    kind: "OnlinePlatform".to_string(),
+                    value: Some(format!("OnlinePlatform_{}", platform)),
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_roster_entries_platform_and_epic_fallback() {
+        let props = vec![(
+            "PlayerStats".to_string(),
+            HeaderProp::Array(vec![
+                player("steam_player", 0, 76561198000000000, "", "Steam"),
+                player("epic_player", 1, 0, "abc123", "Epic"),
+            ]),
+        )];
+        let roster = roster_entries(&props);
+        assert_eq!(roster.len(), 2);
+        assert_eq!(roster[0].online_id.as_deref(), Some("76561198000000000"));
+        assert_eq!(roster[0].platform.as_deref(), Some("Steam"));
+        assert_eq!(roster[1].team, 1);
+        assert_eq!(roster[1].online_id.as_deref(), Some("abc123"));
+        assert_eq!(roster[1].platform.as_deref(), Some("Epic"));
+    }
+
+    #[test]
+    fn test_numeric_playlist_id() {
+        let props = |id: HeaderProp| vec![("PlaylistID".to_string(), id)];
+        assert_eq!(
+            header_playlist_id(&props(HeaderProp::QWord(10))).as_deref(),
+            Some("10")
+        );
+        assert_eq!(
+            header_playlist_id(&props(HeaderProp::Int(13))).as_deref(),
+            Some("13")
+        );
+        assert_eq!(
+            header_playlist_id(&props(HeaderProp::Name("Hoops".to_string()))).as_deref(),
+            Some("Hoops")
+        );
+        assert_eq!(playlist_name("10"), "ranked_duel");
+        assert_eq!(playlist_name("999"), "999");
+        assert_eq!(playlist_name("inferred_2"), "inferred_2");
+    }
+
+    #[test]
+    fn test_match_category() {
+        let prop = |key: &str, value: HeaderProp| (key.to_string(), value);
+        let online = prop("MatchType", HeaderProp::Name("Online".to_string()));
+        assert_eq!(match_category(std::slice::from_ref(&online)), "unknown");
+        assert_eq!(
+            match_category(&[online.clone(), prop("PlaylistID", HeaderProp::Int(11))]),
+            "ranked"
+        );
+        assert_eq!(
+            match_category(&[online.clone(), prop("PlaylistID", HeaderProp::Int(2))]),
+            "casual"
+        );
+        let private = prop("MatchType", HeaderProp::Name("Private".to_string()));
+        assert_eq!(match_category(std::slice::from_ref(&private)), "private");
+        assert_eq!(
+            match_category(&[private, prop("TournamentID", HeaderProp::QWord(4021))]),
+            "tournament"
+        );
+        assert_eq!(
+            match_category(&[online, prop("TournamentID", HeaderProp::QWord(0))]),
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn test_header_identifiers() {
+        let prop = |key: &str, value: HeaderProp| (key.to_string(), value);
+        let props = [
+            prop("Id", HeaderProp::Str("A1B2".to_string())),
+            prop("MatchGUID", HeaderProp::Str("C3D4".to_string())),
+            prop("ServerId", HeaderProp::QWord(18446744073709551615)),
+            prop("PlayerStats", HeaderProp::Array(Vec::new())),
+            prop("bForfeit", HeaderProp::Bool(false)),
+            prop("BuildID", HeaderProp::Int(151471783)),
+            prop("Id", HeaderProp::Str("later".to_string())),
+        ];
+        let ids = header_identifiers(&props);
+        let keys: Vec<&str> = ids.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["Id", "MatchGUID", "ServerId"]);
+        assert_eq!(ids[0].1, "A1B2");
+        assert_eq!(ids[2].1, "18446744073709551615");
+    }
+
+    #[test]
+    fn test_header_winner() {
+        let prop = |key: &str, value: i32| (key.to_string(), HeaderProp::Int(value));
+        let scores =
+            |blue: i32, orange: i32| vec![prop("Team0Score", blue), prop("Team1Score", orange)];
+        assert_eq!(header_winner(&scores(3, 1)), Some(0));
+        assert_eq!(header_winner(&scores(0, 2)), Some(1));
+        assert_eq!(header_winner(&scores(2, 2)), None);
+        assert_eq!(header_winner(&[]), None);
+        // Orange ahead but forfeited: the recorded winner stands.
+        let mut forfeit = scores(1, 4);
+        forfeit.push(("bForfeit".to_string(), HeaderProp::Bool(true)));
+        forfeit.push(prop("WinningTeam", 0));
+        assert_eq!(header_winner(&forfeit), Some(0));
+        assert_eq!(team_result(Some(0), 0), Some("win"));
+        assert_eq!(team_result(Some(0), 1), Some("loss"));
+        assert_eq!(team_result(None, 1), None);
+    }
+
+    #[test]
+    fn test_header_team_totals() {
+        let entry = |team: i32, shots: i32| {
+            vec![
+                ("Team".to_string(), HeaderProp::Int(team)),
+                ("Shots".to_string(), HeaderProp::Int(shots)),
+            ]
+        };
+        let props = [(
+            "PlayerStats".to_string(),
+            HeaderProp::Array(vec![entry(0, 2), entry(1, 4), entry(0, 1)]),
+        )];
+        assert_eq!(header_team_totals(&props, "Shots"), Some([3, 4]));
+        assert_eq!(header_team_totals(&props, "Saves"), None);
+        assert_eq!(header_team_totals(&[], "Shots"), None);
+    }
+
+    #[test]
+    fn test_header_recorder() {
+        let stats = (
+            "PlayerStats".to_string(),
+            HeaderProp::Array(vec![
+                player("steam_player", 0, 76561198000000000, "", "Steam"),
+                player("epic_player", 1, 0, "abc123", "Epic"),
+                player("epic_mate", 1, 0, "def456", "Epic"),
+            ]),
+        );
+        let primary = HeaderProp::Struct {
+            name: "UniqueNetId".to_string(),
+            fields: vec![("Uid".to_string(), HeaderProp::QWord(76561198000000000))],
+        };
+        let props = [stats.clone(), ("PrimaryPlayerId".to_string(), primary)];
+        let recorder = header_recorder(&props).unwrap();
+        assert_eq!(recorder.name.as_deref(), Some("steam_player"));
+        assert_eq!(recorder.online_id.as_deref(), Some("76561198000000000"));
+
+        let by_name = [
+            stats.clone(),
+            (
+                "PlayerName".to_string(),
+                HeaderProp::Str("epic_player".to_string()),
+            ),
+        ];
+        let recorder = header_recorder(&by_name).unwrap();
+        assert_eq!(recorder.online_id.as_deref(), Some("abc123"));
+
+        // A team of one names the recorder; a team of two does not.
+        let team = |t: i32| {
+            [
+                stats.clone(),
+                ("PrimaryPlayerTeam".to_string(), HeaderProp::Int(t)),
+            ]
+        };
+        let recorder = header_recorder(&team(0)).unwrap();
+        assert_eq!(recorder.name.as_deref(), Some("steam_player"));
+        assert_eq!(header_recorder(&team(1)), None);
+        assert_eq!(header_recorder(std::slice::from_ref(&stats)), None);
+    }
+
+    #[test]
+    fn test_roster_entries_missing_player_stats() {
+        assert!(roster_entries(&[]).is_empty());
+    }
+}
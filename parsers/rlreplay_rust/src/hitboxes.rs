@@ -0,0 +1,104 @@
+/// Car body product ids (from the PRI `ClientLoadout` / `ClientLoadouts` attributes) mapped
+/// to the six hitbox classes every body shares one of.
+///
+/// The table covers the standard bodies and common licensed ones; bodies missing from it
+/// (new releases, rare DLC) have no known hitbox.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hitbox {
+    Octane,
+    Dominus,
+    Plank,
+    Breakout,
+    Hybrid,
+    Merc,
+}
+
+impl Hitbox {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Hitbox::Octane => "octane",
+            Hitbox::Dominus => "dominus",
+            Hitbox::Plank => "plank",
+            Hitbox::Breakout => "breakout",
+            Hitbox::Hybrid => "hybrid",
+            Hitbox::Merc => "merc",
+        }
+    }
+}
+
+/// (body product id, hitbox).
+const BODY_HITBOXES: &[(u32, Hitbox)] = &[
+    (21, Hitbox::Octane),     // Backfire
+    (22, Hitbox::Breakout),   // Breakout
+    (23, Hitbox::Octane),     // Octane
+    (24, Hitbox::Plank),      // Paladin
+    (25, Hitbox::Octane),     // Road Hog
+    (26, Hitbox::Octane),     // Gizmo
+    (27, Hitbox::Octane),     // Sweet Tooth
+    (28, Hitbox::Hybrid),     // X-Devil
+    (29, Hitbox::Dominus),    // Hotshot
+    (30, Hitbox::Merc),       // Merc
+    (31, Hitbox::Hybrid),     // Venom
+    (402, Hitbox::Octane),    // Takumi
+    (403, Hitbox::Dominus),   // Dominus
+    (404, Hitbox::Octane),    // Scarab
+    (523, Hitbox::Octane),    // Zippy
+    (597, Hitbox::Dominus),   // DeLorean Time Machine
+    (600, Hitbox::Dominus),   // Ripper
+    (607, Hitbox::Octane),    // Grog
+    (625, Hitbox::Octane),    // Armadillo
+    (723, Hitbox::Octane),    // Warthog
+    (803, Hitbox::Plank),     // '16 Batmobile
+    (1018, Hitbox::Dominus),  // Dominus GT
+    (1159, Hitbox::Hybrid),   // X-Devil Mk2
+    (1171, Hitbox::Dominus),  // Masamune
+    (1172, Hitbox::Octane),   // Marauder
+    (1286, Hitbox::Dominus),  // Aftershock
+    (1295, Hitbox::Octane),   // Takumi RX-T
+    (1300, Hitbox::Octane),   // Road Hog XL
+    (1317, Hitbox::Hybrid),   // Esper
+    (1416, Hitbox::Breakout), // Breakout Type-S
+    (1475, Hitbox::Octane),   // Proteus
+    (1478, Hitbox::Octane),   // Triton
+    (1533, Hitbox::Octane),   // Vulcan
+    (1568, Hitbox::Octane),   // Octane ZSR
+    (1603, Hitbox::Dominus),  // Twin Mill III
+    (1623, Hitbox::Octane),   // Bone Shaker
+    (1624, Hitbox::Hybrid),   // Endo
+    (1675, Hitbox::Dominus),  // Ice Charger
+    (1691, Hitbox::Plank),    // Mantis
+    (1856, Hitbox::Hybrid),   // Jäger 619
+    (1919, Hitbox::Plank),    // Centio V17
+    (1932, Hitbox::Breakout), // Animus GP
+    (2070, Hitbox::Dominus),  // Werewolf
+    (2268, Hitbox::Octane),   // Dingo
+    (2269, Hitbox::Dominus),  // Diestro
+    (4284, Hitbox::Octane),   // Fennec
+];
+
+/// Hitbox class of the car body with product id `body`, if it is in the table.
+pub fn hitbox_for_body(body: u32) -> Option<Hitbox> {
+    BODY_HITBOXES
+        .iter()
+        .find(|(id, _)| *id == body)
+        .map(|(_, hitbox)| *hitbox)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hitbox_for_body() {
+        assert_eq!(hitbox_for_body(23), Some(Hitbox::Octane));
+        assert_eq!(hitbox_for_body(403), Some(Hitbox::Dominus));
+        assert_eq!(hitbox_for_body(30).map(|h| h.as_str()), Some("merc"));
+        assert_eq!(hitbox_for_body(0), None);
+        // Every body id is listed once.
+        let mut ids: Vec<u32> = BODY_HITBOXES.iter().map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), BODY_HITBOXES.len());
+    }
+}
@@ -0,0 +1,61 @@
+//! Lag spike detection: frames whose timestamp is more than a threshold past the previous
+//! frame's.
+//!
+//! Positions jump discontinuously across such a gap (a network stall, or the recording
+//! skipping the post-goal replay), so frame-to-frame derivatives over it are meaningless.
+
+/// Default gap (s) above which a frame is a lag spike: about three normal frames at the
+/// usual 30 fps.
+pub const DEFAULT_LAG_SPIKE_THRESHOLD_S: f32 = 0.1;
+
+pub struct LagSpikeDetector {
+    threshold: f32,
+    prev_timestamp: Option<f32>,
+    count: usize,
+}
+
+impl LagSpikeDetector {
+    pub fn new(threshold: f32) -> Self {
+        LagSpikeDetector {
+            threshold,
+            prev_timestamp: None,
+            count: 0,
+        }
+    }
+
+    /// Feed the next frame's timestamp; true when it is a lag spike.
+    pub fn observe(&mut self, timestamp: f32) -> bool {
+        let spike = self
+            .prev_timestamp
+            .replace(timestamp)
+            .is_some_and(|prev| is_lag_spike(prev, timestamp, self.threshold));
+        self.count += spike as usize;
+        spike
+    }
+
+    /// Lag spikes seen so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Whether a frame at `timestamp` following one at `prev` is a lag spike.
+pub fn is_lag_spike(prev: f32, timestamp: f32, threshold: f32) -> bool {
+    timestamp - prev > threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lag_spikes() {
+        let mut lag = LagSpikeDetector::new(DEFAULT_LAG_SPIKE_THRESHOLD_S);
+        let flags: Vec<bool> = [0.0, 0.033, 0.066, 0.25, 0.283, 9.0]
+            .iter()
+            .map(|t| lag.observe(*t))
+            .collect();
+        assert_eq!(flags, vec![false, false, false, true, false, true]);
+        assert_eq!(lag.count(), 2);
+    }
+}
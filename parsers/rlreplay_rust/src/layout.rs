@@ -0,0 +1,188 @@
+//! Byte layout of the replay file sections, for external seek indexes.
+//!
+//! File layout (all integers little-endian):
+//!   header_size u32, header_crc u32, header[header_size]
+//!   content_size u32, content_crc u32, then the content:
+//!     levels: u32 count + text strings
+//!     keyframes: u32 count + 12 bytes each (time f32, frame i32, bit position i32)
+//!     network_size u32, network data[network_size]
+//!
+//! boxcars keeps per-keyframe bit positions relative to the start of the network data but
+//! not its file offset, so it is recomputed here from the raw bytes.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetworkLayout {
+    /// Absolute file offset of the first network data byte.
+    pub offset: usize,
+    pub size: usize,
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn i32(&mut self) -> Option<i32> {
+        let bytes = self.data.get(self.pos..self.pos + 4)?;
+        self.pos += 4;
+        Some(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        let end = self.pos.checked_add(len)?;
+        if end > self.data.len() {
+            return None;
+        }
+        self.pos = end;
+        Some(())
+    }
+
+    /// Skip a length-prefixed string; negative lengths are UTF-16 character counts.
+    fn skip_text(&mut self) -> Option<()> {
+        let characters = self.i32()?;
+        let len = if characters < 0 {
+            characters.checked_mul(-2)?
+        } else {
+            characters
+        };
+        self.skip(len as usize)
+    }
+
+    fn len(&mut self) -> Option<usize> {
+        usize::try_from(self.i32()?).ok()
+    }
+}
+
+/// Locate the network data section. None when the file is truncated or malformed.
+pub fn network_data_layout(data: &[u8]) -> Option<NetworkLayout> {
+    let mut cursor = Cursor { data, pos: 0 };
+    let header_size = cursor.len()?;
+    cursor.skip(4 + header_size)?; // header crc + header
+    cursor.skip(8)?; // content size + content crc
+    let levels = cursor.len()?;
+    for _ in 0..levels {
+        cursor.skip_text()?;
+    }
+    let keyframes = cursor.len()?;
+    cursor.skip(keyframes.checked_mul(12)?)?;
+    let size = cursor.len()?;
+    let offset = cursor.pos;
+    cursor.skip(size)?;
+    Some(NetworkLayout { offset, size })
+}
+
+/// A copy of the header section followed by an empty, well-formed content section, so the
+/// header of a replay with a truncated or corrupt body can still be parsed. Parse it with
+/// CRC checks off.
+pub fn header_only_replay(data: &[u8]) -> Option<Vec<u8>> {
+    let mut cursor = Cursor { data, pos: 0 };
+    let header_size = cursor.len()?;
+    cursor.skip(4 + header_size)?;
+    // levels, keyframes, network size, debug info, tick marks, packages, objects, names,
+    // class indices, net cache: all empty.
+    const EMPTY_LISTS: usize = 10;
+    let mut out = data[..cursor.pos].to_vec();
+    out.extend_from_slice(&((EMPTY_LISTS * 4) as i32).to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes()); // content crc
+    out.extend_from_slice(&[0; EMPTY_LISTS * 4]);
+    Some(out)
+}
+
+/// Overwrite the header `NumFrames` property in place. boxcars stops decoding network
+/// frames after `NumFrames`, so lowering it skips everything from a bad frame onwards.
+/// The header CRC no longer matches afterwards. False when the property is not found.
+pub fn patch_num_frames(data: &mut [u8], frames: i32) -> bool {
+    const KEY: &[u8] = b"\x0a\x00\x00\x00NumFrames\x00\x0c\x00\x00\x00IntProperty\x00";
+    let Some(header_end) = Cursor { data, pos: 0 }
+        .len()
+        .and_then(|size| size.checked_add(8))
+    else {
+        return false;
+    };
+    let header = &data[..header_end.min(data.len())];
+    let Some(at) = header.windows(KEY.len()).position(|w| w == KEY) else {
+        return false;
+    };
+    // Skip the u64 value size.
+    let value = at + KEY.len() + 8;
+    match data.get_mut(value..value + 4) {
+        Some(bytes) if value + 4 <= header_end => {
+            bytes.copy_from_slice(&frames.to_le_bytes());
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_i32(buf: &mut Vec<u8>, v: i32) {
+        buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    #[test]
+    fn test_network_data_layout() {
+        let mut buf = Vec::new();
+        push_i32(&mut buf, 3); // header size
+        push_i32(&mut buf, 0); // header crc
+        buf.extend_from_slice(&[1, 2, 3]);
+        push_i32(&mut buf, 0); // content size (unchecked)
+        push_i32(&mut buf, 0); // content crc
+        push_i32(&mut buf, 2); // levels
+        push_i32(&mut buf, 4);
+        buf.extend_from_slice(b"abc\0");
+        push_i32(&mut buf, -2); // UTF-16, 2 chars
+        buf.extend_from_slice(&[b'a', 0, 0, 0]);
+        push_i32(&mut buf, 1); // keyframes
+        buf.extend_from_slice(&[0; 12]);
+        push_i32(&mut buf, 5); // network size
+        let offset = buf.len();
+        buf.extend_from_slice(&[9; 5]);
+
+        assert_eq!(
+            network_data_layout(&buf),
+            Some(NetworkLayout { offset, size: 5 })
+        );
+        assert_eq!(network_data_layout(&buf[..buf.len() - 1]), None);
+    }
+
+    fn push_text(buf: &mut Vec<u8>, text: &str) {
+        push_i32(buf, text.len() as i32 + 1);
+        buf.extend_from_slice(text.as_bytes());
+        buf.push(0);
+    }
+
+    #[test]
+    fn test_patch_num_frames_and_header_only() {
+        let mut header = Vec::new();
+        push_text(&mut header, "NumFrames");
+        push_text(&mut header, "IntProperty");
+        buf_u64(&mut header, 4);
+        push_i32(&mut header, 9000);
+        push_text(&mut header, "None");
+
+        let mut buf = Vec::new();
+        push_i32(&mut buf, header.len() as i32);
+        push_i32(&mut buf, 0); // header crc
+        buf.extend_from_slice(&header);
+        let header_end = buf.len();
+        buf.extend_from_slice(&[7; 3]); // truncated content
+
+        assert!(patch_num_frames(&mut buf, 120));
+        let value = header_end - 4 - 9;
+        assert_eq!(&buf[value..value + 4], &120i32.to_le_bytes());
+
+        let rebuilt = header_only_replay(&buf).unwrap();
+        assert_eq!(&rebuilt[..header_end], &buf[..header_end]);
+        assert_eq!(rebuilt.len(), header_end + 8 + 40);
+        assert_eq!(header_only_replay(&buf[..10]), None);
+        assert!(!patch_num_frames(&mut buf[..10].to_vec(), 1));
+    }
+
+    fn buf_u64(buf: &mut Vec<u8>, v: u64) {
+        buf.extend_from_slice(&v.to_le_bytes());
+    }
+}
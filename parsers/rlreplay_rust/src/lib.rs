@@ -1,28 +1,292 @@
 mod arena_tables;
+mod attributes;
+mod builds;
+mod bumps;
+mod ceiling;
+mod chat;
+mod columnar;
+mod commits;
+mod dribbles;
+mod events;
+mod geometry;
+mod goals;
+mod header;
+mod hitboxes;
+mod lag;
+mod layout;
+mod library;
+mod network;
 mod pads;
+mod passthrough;
+mod resets;
+mod rumble;
+mod series;
+mod shots;
+mod slots;
+mod smoothing;
+mod stalls;
+mod stats;
+mod teams;
+mod telemetry;
+mod timing;
+mod touches;
+mod whiffs;
+mod woodwork;
 
-use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::exceptions::{PyDeprecationWarning, PyIOError, PyIndexError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use pyo3::types::{PyDict, PyList, PyTuple};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::Infallible;
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufWriter, Read, Write};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::SystemTime;
 
 // Boxcars parsing
-use boxcars::{Attribute, NewActor, Vector3f};
-use boxcars::{HeaderProp, ParserBuilder, Replay};
+use boxcars::Attribute;
+use boxcars::{
+    ActorId, CrcCheck, Frame, HeaderProp, NetworkError, ParseError, ParserBuilder, Replay, Vector3f,
+};
 
-use pads::{PadEvent, PadRegistry};
+use arena_tables::{
+    goal_centers_for_slug, kickoff_spawns_for_slug, lookup_arena_slug, pad_respawn_for_slug,
+    pad_table_for_slug,
+};
+use attributes::is_attribute_name;
+use builds::{header_build_version, BuildCapabilities};
+use bumps::BumpDetector;
+use ceiling::CeilingShotDetector;
+use chat::is_chat_entry;
+use columnar::{FrameColumns, PlayerTrajectory, FLAT_FRAME_FIELDS, FLAT_PLAYER_FIELDS};
+use commits::DoubleCommitDetector;
+use dribbles::{DribbleDetector, CARRY_HEIGHT_BINS, CARRY_HEIGHT_BIN_EDGES_UU};
+use events::{
+    classify_kickoff_spawn, sort_timeline, write_timeline_csv, KickoffDetector,
+    KickoffOutcomeDetector, MatchEvent,
+};
+use geometry::{
+    goal_relative_offsets, half_turn, magnitude, pairwise_spacing, predict_landing, quat_to_euler,
+    CoordinateConvention, Physics, ATTACKING_THIRD_Y, BALL_RADIUS, GRAVITY_UU_S2,
+};
+use goals::{check_scorer_mapping, nearest_goal_frame, GoalDetector, GoalEvent};
+use header::{
+    find_prop, header_goals, header_identifiers, header_map_name, header_match_length,
+    header_players, header_playlist_id, header_recorder, header_team_scores, header_team_size,
+    header_team_totals, header_winner, match_category, playlist_name, prop_text, roster_entries,
+    team_result, RosterEntry,
+};
+use hitboxes::hitbox_for_body;
+use lag::{is_lag_spike, LagSpikeDetector, DEFAULT_LAG_SPIKE_THRESHOLD_S};
+use layout::{header_only_replay, network_data_layout, patch_num_frames};
+use library::{HeaderSummary, LibrarySummary};
+use network::{
+    BallSnapshot, FramePadEvent, FrameSnapshot, NetworkState, PhysicsPriority, PlayerSnapshot,
+};
+use pads::{PadEventCoalescer, PadEventStatus, PadLatencyStats, PAD_EVENT_COALESCE_WINDOW_S};
+use passthrough::{RawAttributeFilter, RawUpdate};
+use resets::FlipResetDetector;
+use rumble::RumbleTracker;
+use series::compare_rosters;
+use shots::{SaveDetector, Shot, ShotDetector, ShotQualityCoefficients};
+use smoothing::{VelocitySmoother, VELOCITY_SMOOTHING_WINDOW};
+use stalls::{BallStallDetector, STALL_MIN_DURATION_S};
+use stats::MatchStats;
+use teams::{TeamIdentity, TeamTracker, DEFAULT_TEAM_NAMES};
+use telemetry::TelemetryAggregator;
+use timing::CallTimer;
+use touches::{TouchDetector, MIN_VELOCITY_DELTA_UU_S, TOUCH_DISTANCE_UU};
+use whiffs::WhiffDetector;
+use woodwork::WoodworkDetector;
 
-fn read_file_bytes(path: &str) -> PyResult<Vec<u8>> {
-    let mut file = File::open(path)
-        .map_err(|e| PyIOError::new_err(format!("Failed to open replay file '{}': {}", path, e)))?;
+/// Version of the frame dict shape emitted by iter_frames / parse_all /
+/// parse_network_with_diagnostics. Bump whenever a frame key is added, removed or changes type.
+pub const FRAME_SCHEMA_VERSION: u32 = 12;
+
+/// Default upper bound on replay file size; real replays are a few MB.
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 512 * 1024 * 1024;
+
+static MAX_FILE_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MAX_FILE_BYTES);
+
+enum ReadError {
+    Io(String),
+    TooLarge(String),
+}
+
+impl From<ReadError> for String {
+    fn from(err: ReadError) -> Self {
+        match err {
+            ReadError::Io(msg) | ReadError::TooLarge(msg) => msg,
+        }
+    }
+}
+
+impl From<ReadError> for PyErr {
+    fn from(err: ReadError) -> Self {
+        match err {
+            ReadError::Io(msg) => PyIOError::new_err(msg),
+            ReadError::TooLarge(msg) => PyValueError::new_err(msg),
+        }
+    }
+}
+
+/// GIL-free file read. `read_file_bytes` raises the error as OSError (`ReadError::Io`) or,
+/// for a file over the size limit, ValueError (`ReadError::TooLarge`).
+fn read_bytes(path: &str) -> Result<Vec<u8>, ReadError> {
+    let limit = MAX_FILE_BYTES.load(Ordering::Relaxed);
+    let too_large = |size: u64| {
+        ReadError::TooLarge(format!(
+            "Replay file '{}' is {} bytes, over the {} byte limit",
+            path, size, limit
+        ))
+    };
+    let file = File::open(path)
+        .map_err(|e| ReadError::Io(format!("Failed to open replay file '{}': {}", path, e)))?;
+    // Check the reported size first, then cap the read in case it is wrong (pipes, procfs).
+    if let Ok(meta) = file.metadata() {
+        if meta.len() > limit {
+            return Err(too_large(meta.len()));
+        }
+    }
     let mut buf = Vec::new();
-    file.read_to_end(&mut buf)
-        .map_err(|e| PyIOError::new_err(format!("Failed to read replay file '{}': {}", path, e)))?;
+    file.take(limit + 1)
+        .read_to_end(&mut buf)
+        .map_err(|e| ReadError::Io(format!("Failed to read replay file '{}': {}", path, e)))?;
+    if buf.len() as u64 > limit {
+        return Err(too_large(buf.len() as u64));
+    }
     Ok(buf)
 }
 
+fn read_file_bytes(path: &str) -> PyResult<Vec<u8>> {
+    read_bytes(path).map_err(PyErr::from)
+}
+
+/// Set the maximum replay file size in bytes accepted by every reader. Larger files raise
+/// ValueError before being read into memory.
+#[pyfunction]
+fn set_max_file_size(max_bytes: u64) -> PyResult<()> {
+    if max_bytes == 0 {
+        return Err(PyValueError::new_err("max_bytes must be positive"));
+    }
+    MAX_FILE_BYTES.store(max_bytes, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Current maximum replay file size in bytes.
+#[pyfunction]
+fn max_file_size() -> u64 {
+    MAX_FILE_BYTES.load(Ordering::Relaxed)
+}
+
+/// Default network frame count above which a replay is flagged as large (about 16 minutes
+/// at 30 fps); freeplay recordings can run far longer.
+pub const DEFAULT_LARGE_REPLAY_FRAMES: usize = 30_000;
+const LARGE_REPLAY_WARNING: &str = "large_replay_consider_streaming";
+/// Warning code for a header goal whose scorer disagrees with the frame player mapping.
+const MAPPING_MISMATCH_WARNING: &str = "player_mapping_mismatch";
+
+static LARGE_REPLAY_FRAMES: AtomicUsize = AtomicUsize::new(DEFAULT_LARGE_REPLAY_FRAMES);
+static WARNING_CALLBACK: Mutex<Option<PyObject>> = Mutex::new(None);
+
+/// Set the network frame count above which frame-level calls report
+/// `large_replay_consider_streaming`.
+#[pyfunction]
+fn set_large_replay_threshold(frames: usize) -> PyResult<()> {
+    if frames == 0 {
+        return Err(PyValueError::new_err("frames must be positive"));
+    }
+    LARGE_REPLAY_FRAMES.store(frames, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Current large-replay network frame threshold.
+#[pyfunction]
+fn large_replay_threshold() -> usize {
+    LARGE_REPLAY_FRAMES.load(Ordering::Relaxed)
+}
+
+/// Register `callback(code, message)` to be called with soft warnings such as
+/// `large_replay_consider_streaming` or `player_mapping_mismatch`, or clear it with None.
+#[pyfunction]
+#[pyo3(signature = (callback = None))]
+fn set_warning_callback(callback: Option<PyObject>) {
+    *WARNING_CALLBACK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = callback;
+}
+
+/// `large_replay_consider_streaming` when `frames` is over the threshold, after passing
+/// it to the warning callback (if any). Errors raised by the callback propagate.
+fn large_replay_warning(
+    py: Python<'_>,
+    path: &str,
+    frames: usize,
+) -> PyResult<Option<&'static str>> {
+    let threshold = LARGE_REPLAY_FRAMES.load(Ordering::Relaxed);
+    if frames <= threshold {
+        return Ok(None);
+    }
+    let message = format!(
+        "Replay '{}' has {} network frames (threshold {}); building every frame in memory \
+         is expensive, consider only_player or frames_columnar",
+        path, frames, threshold
+    );
+    emit_warning(py, LARGE_REPLAY_WARNING, message)?;
+    Ok(Some(LARGE_REPLAY_WARNING))
+}
+
+/// Pass `(code, message)` to the warning callback, if any. Errors raised by the callback
+/// propagate.
+fn emit_warning(py: Python<'_>, code: &str, message: String) -> PyResult<()> {
+    let callback = WARNING_CALLBACK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .map(|callback| callback.clone_ref(py));
+    if let Some(callback) = callback {
+        callback.call1(py, (code, message))?;
+    }
+    Ok(())
+}
+
+fn network_frame_count(replay: &Replay) -> usize {
+    replay
+        .network_frames
+        .as_ref()
+        .map_or(0, |net| net.frames.len())
+}
+
+fn append_quality_warning(header: &PyDict, warning: &str) -> PyResult<()> {
+    if let Some(warnings) = header.get_item("quality_warnings")? {
+        warnings.downcast::<PyList>()?.append(warning)?;
+    }
+    Ok(())
+}
+
+/// `fallback_inferred_players:N` when N unclassified actors were enrolled as players. N
+/// counts actors, not players: a player whose car respawns as a new unclassified actor is
+/// counted once per actor.
+fn inferred_players_warning(state: &NetworkState) -> Option<String> {
+    let count = state.inferred_player_count();
+    (count > 0).then(|| format!("fallback_inferred_players:{}", count))
+}
+
+/// `lag_spikes:N` when N frames were flagged `lag_spike`.
+fn lag_spikes_warning(lag: &LagSpikeDetector) -> Option<String> {
+    let count = lag.count();
+    (count > 0).then(|| format!("lag_spikes:{}", count))
+}
+
+/// `network_derived_slots:N` when the header has no roster and N player slots were
+/// synthesised from the network car actors instead.
+fn network_slots_warning(state: &NetworkState) -> Option<String> {
+    let count = state.network_slot_count();
+    (count > 0).then(|| format!("network_derived_slots:{}", count))
+}
+
 fn looks_like_replay_header(bytes: &[u8]) -> bool {
     let needles: [&[u8]; 3] = [
         b"TAGame.Replay_Soccar_TA",
@@ -79,941 +343,3536 @@ fn header_prop_to_py(py: Python<'_>, prop: &HeaderProp) -> PyResult<PyObject> {
     })
 }
 
-#[pyfunction]
-fn parse_header(path: &str) -> PyResult<PyObject> {
-    Python::with_gil(|py| {
-        let data = read_file_bytes(path)?;
-        if data.len() < 100 {
-            return Err(PyValueError::new_err("File too short to be a valid replay"));
-        }
+/// `teams` list: index, name (custom name or "Blue"/"Orange"), score, club colour and
+/// result ("win" | "loss", None without a winner).
+fn teams_to_py<'py>(
+    py: Python<'py>,
+    scores: [i64; 2],
+    winner: Option<i64>,
+    identities: &[TeamIdentity; 2],
+) -> PyResult<&'py PyList> {
+    let teams = PyList::empty(py);
+    for (index, identity) in identities.iter().enumerate() {
+        let team = PyDict::new(py);
+        team.set_item("index", index)?;
+        team.set_item(
+            "name",
+            identity
+                .custom_name
+                .as_deref()
+                .unwrap_or(DEFAULT_TEAM_NAMES[index]),
+        )?;
+        team.set_item("score", scores[index])?;
+        team.set_item("color", identity.color)?;
+        team.set_item("result", team_result(winner, index as i64))?;
+        teams.append(team)?;
+    }
+    Ok(teams)
+}
 
-        // Parsed fields
-        let mut playlist_id: Option<String> = None;
-        let mut map_name: Option<String> = None;
-        let mut team0_score: i64 = 0;
-        let mut team1_score: i64 = 0;
-        let mut match_length: f64 = 0.0;
-        let mut players_vec: Vec<(String, i64)> = Vec::new();
-        let mut players_meta: Vec<PyObject> = Vec::new();
-        let highlights_list = PyList::empty(py);
-        let mut team_size: i64 = 0;
-        let mut warnings_vec: Vec<String> = Vec::new();
-
-        // Helper: get prop by key
-        fn find_prop<'a>(
-            props: &'a Vec<(String, HeaderProp)>,
-            key: &str,
-        ) -> Option<&'a HeaderProp> {
-            props.iter().find(|(k, _)| k == key).map(|(_, v)| v)
-        }
-
-        // Prepare a goals list to populate if available
-        let goals_list = PyList::empty(py);
-
-        match ParserBuilder::new(&data).never_parse_network_data().parse() {
-            Ok(Replay { properties, .. }) => {
-                if let Some(p) = find_prop(&properties, "MapName") {
-                    if let Some(s) = p.as_string() {
-                        map_name = Some(s.to_string());
-                    }
-                }
-                if let Some(p) = find_prop(&properties, "PlaylistID") {
-                    // PlaylistID can be string or integer - handle both
-                    if let Some(s) = p.as_string() {
-                        playlist_id = Some(s.to_string());
-                    } else if let Some(i) = p.as_i32() {
-                        playlist_id = Some(i.to_string());
-                    }
-                }
-                // Read TeamSize early (needed for playlist inference)
-                if let Some(p) = find_prop(&properties, "TeamSize") {
-                    if let Some(ts) = p.as_i32() {
-                        team_size = ts as i64;
-                    }
-                }
-                // Fallback: infer playlist from MatchType + TeamSize when PlaylistID is missing
-                if playlist_id.is_none() {
-                    let match_type = find_prop(&properties, "MatchType")
-                        .and_then(|p| p.as_string())
-                        .map(|s| s.to_string());
-                    if let Some(mt) = match_type {
-                        match mt.as_str() {
-                            "Online" => {
-                                // Use TeamSize to determine playlist
-                                playlist_id = Some(format!("inferred_{}", team_size));
-                            }
-                            "Tournament" => {
-                                playlist_id = Some("tournament".to_string());
-                            }
-                            "Private" | "Offline" => {
-                                playlist_id = Some("private".to_string());
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-                if let Some(p) = find_prop(&properties, "BuildVersion") {
-                    if let Some(s) = p.as_string() {
-                        warnings_vec.push(format!("build_version:{}", s));
-                    }
+/// Build the `parse_header` dict from already-parsed header properties. On a header parse
+/// failure (`Err(message)`) placeholder players are emitted with quality warnings.
+/// `compact` leaves out the per-player `stats` dicts, `goals` and `highlights`.
+fn header_to_py<'py>(
+    py: Python<'py>,
+    data: &[u8],
+    parsed: Result<&[(String, HeaderProp)], String>,
+    compact: bool,
+) -> PyResult<&'py PyDict> {
+    // Parsed fields
+    let mut playlist_id: Option<String> = None;
+    let mut category = "unknown";
+    let mut map_name: Option<String> = None;
+    let mut team0_score: i64 = 0;
+    let mut team1_score: i64 = 0;
+    let mut winner: Option<i64> = None;
+    let mut match_length: f64 = 0.0;
+    let mut record_fps: Option<f64> = None;
+    let mut players_vec: Vec<(String, i64)> = Vec::new();
+    let mut players_meta: Vec<PyObject> = Vec::new();
+    let highlights_list = PyList::empty(py);
+    let team_size: i64;
+    let mut warnings_vec: Vec<String> = Vec::new();
+
+    // Prepare a goals list to populate if available
+    let goals_list = PyList::empty(py);
+
+    match parsed {
+        Ok(properties) => {
+            map_name = find_prop(properties, "MapName").and_then(prop_text);
+            playlist_id = header_playlist_id(properties);
+            category = match_category(properties);
+            team_size = header_team_size(properties);
+            if let Some(p) = find_prop(properties, "BuildVersion") {
+                if let Some(s) = p.as_string() {
+                    warnings_vec.push(format!("build_version:{}", s));
                 }
-                if let Some(p) = find_prop(&properties, "NumFrames") {
-                    if let Some(fr) = p.as_i32() {
-                        match_length = (fr as f64) / 30.0;
-                    }
+            }
+            match_length = header_match_length(properties);
+            // Intended capture rate; left as None rather than assuming 30 when absent
+            if let Some(p) = find_prop(properties, "RecordFPS")
+                .or_else(|| find_prop(properties, "ReplicatedFPS"))
+            {
+                if let Some(fps) = p.as_float() {
+                    record_fps = Some(fps as f64);
+                } else if let Some(fps) = p.as_i32() {
+                    record_fps = Some(fps as f64);
                 }
-                if let Some(p) = find_prop(&properties, "Team0Score") {
-                    if let Some(s0) = p.as_i32() {
-                        team0_score = s0 as i64;
-                    }
+            }
+            if let Some(p) = find_prop(properties, "Team0Score") {
+                if let Some(s0) = p.as_i32() {
+                    team0_score = s0 as i64;
                 }
-                if let Some(p) = find_prop(&properties, "Team1Score") {
-                    if let Some(s1) = p.as_i32() {
-                        team1_score = s1 as i64;
-                    }
+            }
+            if let Some(p) = find_prop(properties, "Team1Score") {
+                if let Some(s1) = p.as_i32() {
+                    team1_score = s1 as i64;
                 }
+            }
+            winner = header_winner(properties);
 
-                if let Some(p) = find_prop(&properties, "PlayerStats") {
-                    if let Some(arr) = p.as_array() {
-                        for entry in arr {
-                            // Each entry is Vec<(String, HeaderProp)>
-                            let mut name: Option<String> = None;
-                            let mut team: i64 = 0;
-                            let stats_dict = PyDict::new(py);
-                            for (k, v) in entry {
-                                match (k.as_str(), v) {
-                                    ("Name", hp) | ("PlayerName", hp) => {
-                                        if let Some(s) = hp.as_string() {
-                                            name = Some(s.to_string());
-                                        }
-                                    }
-                                    ("Team", hp) | ("PlayerTeam", hp) => {
-                                        if let Some(t) = hp.as_i32() {
-                                            team = t as i64;
-                                        }
+            if let Some(p) = find_prop(properties, "PlayerStats") {
+                if let Some(arr) = p.as_array() {
+                    for entry in arr {
+                        // Each entry is Vec<(String, HeaderProp)>
+                        let mut name: Option<String> = None;
+                        let mut team: i64 = 0;
+                        let stats_dict = PyDict::new(py);
+                        for (k, v) in entry {
+                            match (k.as_str(), v) {
+                                ("Name", hp) | ("PlayerName", hp) => {
+                                    if let Some(s) = hp.as_string() {
+                                        name = Some(s.to_string());
                                     }
-                                    _ => {
-                                        let value = header_prop_to_py(py, v)?;
-                                        stats_dict.set_item(k.as_str(), value)?;
+                                }
+                                ("Team", hp) | ("PlayerTeam", hp) => {
+                                    if let Some(t) = hp.as_i32() {
+                                        team = t as i64;
                                     }
                                 }
+                                _ if compact => {}
+                                _ => {
+                                    let value = header_prop_to_py(py, v)?;
+                                    stats_dict.set_item(k.as_str(), value)?;
+                                }
                             }
+                        }
 
-                            if let Some(n) = name.clone() {
-                                players_vec.push((n.clone(), team));
-                                let player_dict = PyDict::new(py);
-                                player_dict.set_item("name", n)?;
-                                player_dict.set_item("team", team)?;
+                        if let Some(n) = name.clone() {
+                            players_vec.push((n.clone(), team));
+                            let player_dict = PyDict::new(py);
+                            player_dict.set_item("name", n)?;
+                            player_dict.set_item("team", team)?;
+                            if !compact {
                                 player_dict.set_item("stats", stats_dict)?;
-                                players_meta.push(player_dict.to_object(py));
                             }
+                            players_meta.push(player_dict.to_object(py));
                         }
                     }
                 }
+            }
 
-                // Goals (frame, PlayerName, PlayerTeam)
-                if let Some(p) = find_prop(&properties, "Goals") {
-                    if let Some(arr) = p.as_array() {
-                        for entry in arr {
-                            let mut g_frame: Option<i64> = None;
-                            let mut g_name: Option<String> = None;
-                            let mut g_team: Option<i64> = None;
-                            for (k, v) in entry {
-                                match (k.as_str(), v) {
-                                    ("frame", hp) => {
-                                        if let Some(i) = hp.as_i32() {
-                                            g_frame = Some(i as i64);
-                                        }
+            // Goals (frame, PlayerName, PlayerTeam)
+            if let Some(p) = find_prop(properties, "Goals").filter(|_| !compact) {
+                if let Some(arr) = p.as_array() {
+                    for entry in arr {
+                        let mut g_frame: Option<i64> = None;
+                        let mut g_name: Option<String> = None;
+                        let mut g_team: Option<i64> = None;
+                        for (k, v) in entry {
+                            match (k.as_str(), v) {
+                                ("frame", hp) => {
+                                    if let Some(i) = hp.as_i32() {
+                                        g_frame = Some(i as i64);
                                     }
-                                    ("PlayerName", hp) => {
-                                        if let Some(s) = hp.as_string() {
-                                            g_name = Some(s.to_string());
-                                        }
+                                }
+                                ("PlayerName", hp) => {
+                                    if let Some(s) = hp.as_string() {
+                                        g_name = Some(s.to_string());
                                     }
-                                    ("PlayerTeam", hp) => {
-                                        if let Some(i) = hp.as_i32() {
-                                            g_team = Some(i as i64);
-                                        }
+                                }
+                                ("PlayerTeam", hp) => {
+                                    if let Some(i) = hp.as_i32() {
+                                        g_team = Some(i as i64);
                                     }
-                                    _ => {}
                                 }
+                                _ => {}
                             }
-                            let g = PyDict::new(py);
-                            if let Some(fv) = g_frame {
-                                g.set_item("frame", fv)?;
-                            }
-                            if let Some(nv) = g_name {
-                                g.set_item("player_name", nv)?;
-                            }
-                            if let Some(tv) = g_team {
-                                g.set_item("player_team", tv)?;
-                            }
-                            goals_list.append(g)?;
                         }
+                        let g = PyDict::new(py);
+                        if let Some(fv) = g_frame {
+                            g.set_item("frame", fv)?;
+                        }
+                        if let Some(nv) = g_name {
+                            g.set_item("player_name", nv)?;
+                        }
+                        if let Some(tv) = g_team {
+                            g.set_item("player_team", tv)?;
+                        }
+                        goals_list.append(g)?;
                     }
                 }
+            }
 
-                if let Some(p) = find_prop(&properties, "HighLights") {
-                    if let Some(arr) = p.as_array() {
-                        for entry in arr {
-                            let mut h_frame: Option<i64> = None;
-                            let mut h_ball: Option<String> = None;
-                            let mut h_car: Option<String> = None;
-                            for (k, v) in entry {
-                                match (k.as_str(), v) {
-                                    ("frame", hp) => {
-                                        if let Some(i) = hp.as_i32() {
-                                            h_frame = Some(i as i64);
-                                        }
+            if let Some(p) = find_prop(properties, "HighLights").filter(|_| !compact) {
+                if let Some(arr) = p.as_array() {
+                    for entry in arr {
+                        let mut h_frame: Option<i64> = None;
+                        let mut h_ball: Option<String> = None;
+                        let mut h_car: Option<String> = None;
+                        for (k, v) in entry {
+                            match (k.as_str(), v) {
+                                ("frame", hp) => {
+                                    if let Some(i) = hp.as_i32() {
+                                        h_frame = Some(i as i64);
                                     }
-                                    ("BallName", hp) | ("Ball", hp) => {
-                                        if let Some(s) = hp.as_string() {
-                                            h_ball = Some(s.to_string());
-                                        }
+                                }
+                                ("BallName", hp) | ("Ball", hp) => {
+                                    if let Some(s) = hp.as_string() {
+                                        h_ball = Some(s.to_string());
                                     }
-                                    ("CarName", hp) | ("Car", hp) => {
-                                        if let Some(s) = hp.as_string() {
-                                            h_car = Some(s.to_string());
-                                        }
+                                }
+                                ("CarName", hp) | ("Car", hp) => {
+                                    if let Some(s) = hp.as_string() {
+                                        h_car = Some(s.to_string());
                                     }
-                                    _ => {}
                                 }
+                                _ => {}
                             }
-                            let h = PyDict::new(py);
-                            if let Some(fv) = h_frame {
-                                h.set_item("frame", fv)?;
-                            }
-                            if let Some(ball) = h_ball {
-                                h.set_item("ball_name", ball)?;
-                            }
-                            if let Some(car) = h_car {
-                                h.set_item("car_name", car)?;
-                            }
-                            highlights_list.append(h)?;
                         }
+                        let h = PyDict::new(py);
+                        if let Some(fv) = h_frame {
+                            h.set_item("frame", fv)?;
+                        }
+                        if let Some(ball) = h_ball {
+                            h.set_item("ball_name", ball)?;
+                        }
+                        if let Some(car) = h_car {
+                            h.set_item("car_name", car)?;
+                        }
+                        highlights_list.append(h)?;
                     }
                 }
-
-                if !players_vec.is_empty() {
-                    let mut team_counts: HashMap<i64, i64> = HashMap::new();
-                    for (_, t) in &players_vec {
-                        *team_counts.entry(*t).or_insert(0) += 1;
-                    }
-                    team_size = team_counts.values().cloned().max().unwrap_or(1);
-                } else {
-                    warnings_vec.push("boxcars_no_playerstats".to_string());
-                }
             }
-            Err(e) => {
-                warnings_vec.push(format!("boxcars_parse_error: {}", e));
-                let looks_like = looks_like_replay_header(&data);
-                if !looks_like {
-                    warnings_vec.push("rust_core_suspect_format".to_string());
-                }
-                players_vec.push(("Unknown Player 1".to_string(), 0));
-                players_vec.push(("Unknown Player 2".to_string(), 1));
-                team_size = 1;
+
+            if players_vec.is_empty() {
+                warnings_vec.push("boxcars_no_playerstats".to_string());
             }
         }
-
-        // Build Python dict
-        let header = PyDict::new(py);
-        header.set_item(
-            "playlist_id",
-            playlist_id.unwrap_or_else(|| "unknown".to_string()),
-        )?;
-        header.set_item(
-            "map_name",
-            map_name.unwrap_or_else(|| "unknown".to_string()),
-        )?;
-        header.set_item("team_size", team_size)?;
-        header.set_item("team0_score", team0_score)?;
-        header.set_item("team1_score", team1_score)?;
-        header.set_item("match_length", match_length)?;
-
-        if players_meta.is_empty() {
-            let players = PyList::empty(py);
-            for (name, team) in &players_vec {
-                let p = PyDict::new(py);
-                p.set_item("name", name)?;
-                p.set_item("team", team)?;
-                players.append(p)?;
+        Err(e) => {
+            warnings_vec.push(format!("boxcars_parse_error: {}", e));
+            let looks_like = looks_like_replay_header(data);
+            if !looks_like {
+                warnings_vec.push("rust_core_suspect_format".to_string());
             }
-            header.set_item("players", players)?;
-        } else {
-            header.set_item("players", PyList::new(py, players_meta))?;
+            players_vec.push(("Unknown Player 1".to_string(), 0));
+            players_vec.push(("Unknown Player 2".to_string(), 1));
+            team_size = 1;
         }
-        // Engine build (if captured in warnings)
-        if let Some(build) = warnings_vec
-            .iter()
-            .find_map(|w| w.strip_prefix("build_version:"))
-        {
-            header.set_item("engine_build", build)?;
+    }
+
+    // Build Python dict
+    let header = PyDict::new(py);
+    let playlist_id = playlist_id.unwrap_or_else(|| "unknown".to_string());
+    header.set_item("playlist_id", &playlist_id)?;
+    header.set_item("playlist_name", playlist_name(&playlist_id))?;
+    header.set_item("match_category", category)?;
+    header.set_item(
+        "map_name",
+        map_name.unwrap_or_else(|| "unknown".to_string()),
+    )?;
+    header.set_item("team_size", team_size)?;
+    header.set_item("team0_score", team0_score)?;
+    header.set_item("team1_score", team1_score)?;
+    header.set_item("winner", winner)?;
+    header.set_item("match_length", match_length)?;
+    header.set_item("record_fps", record_fps)?;
+    // Team names / colours only replicate over the network; parse_all fills them in.
+    header.set_item(
+        "teams",
+        teams_to_py(py, [team0_score, team1_score], winner, &Default::default())?,
+    )?;
+
+    if players_meta.is_empty() {
+        let players = PyList::empty(py);
+        for (name, team) in &players_vec {
+            let p = PyDict::new(py);
+            p.set_item("name", name)?;
+            p.set_item("team", team)?;
+            players.append(p)?;
         }
-        // Goals & highlights lists
+        header.set_item("players", players)?;
+    } else {
+        header.set_item("players", PyList::new(py, players_meta))?;
+    }
+    // Engine build (if captured in warnings)
+    if let Some(build) = warnings_vec
+        .iter()
+        .find_map(|w| w.strip_prefix("build_version:"))
+    {
+        header.set_item("engine_build", build)?;
+    }
+    // Goals & highlights lists
+    if !compact {
         header.set_item("goals", goals_list)?;
         header.set_item("highlights", highlights_list)?;
-        let warnings = PyList::empty(py);
-        warnings.append("parsed_with_rust_core")?;
-        for w in warnings_vec {
-            warnings.append(w)?;
+    }
+    let warnings = PyList::empty(py);
+    warnings.append("parsed_with_rust_core")?;
+    for w in warnings_vec {
+        warnings.append(w)?;
+    }
+    header.set_item("quality_warnings", warnings)?;
+
+    Ok(header)
+}
+
+/// `winner` is the winning team, 0 or 1, or None on a tie (unfinished match); each `teams`
+/// entry carries its `result`, "win" or "loss". A forfeiting team loses regardless of the
+/// score (see `header::header_winner`). Team names and club colours replicate only over
+/// the network, so every `teams` entry here has the default name ("Blue" / "Orange") and
+/// `color` None; parse_all reports each team's own replicated name and colour.
+///
+/// `recorder` is `{name, online_id}` for the player whose client recorded the replay (see
+/// `header::header_recorder`), either field None when unknown; None for server-side
+/// replays or when the header does not say.
+///
+/// `identifiers` maps each present top-level id property (`Id`, `MatchGUID`, `SessionId`,
+/// `ServerId`; see `header::HEADER_IDENTIFIER_KEYS`) to its value as a string, QWords in
+/// decimal; empty when the header failed to parse.
+///
+/// With `check_crc`, boxcars validates the header and body CRCs; a mismatch is reported
+/// like other header parse failures, plus a `crc_failed` quality warning.
+///
+/// With `include_raw_property_keys`, `raw_property_keys` lists every top-level header
+/// property key, sorted, for discovering build-specific names; None when the header
+/// failed to parse.
+///
+/// With `compact`, every scalar field and the `players` roster (name / team) are kept but
+/// the per-player `stats` dicts, `goals` and `highlights` are left out, for indexing many
+/// files between quick_probe and a full parse.
+#[pyfunction]
+#[pyo3(signature = (path, check_crc = false, include_raw_property_keys = false, compact = false))]
+fn parse_header(
+    path: &str,
+    check_crc: bool,
+    include_raw_property_keys: bool,
+    compact: bool,
+) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        if data.len() < 100 {
+            return Err(PyValueError::new_err("File too short to be a valid replay"));
+        }
+        let replay = ParserBuilder::new(&data)
+            .with_crc_check(crc_check(check_crc))
+            .never_parse_network_data()
+            .parse();
+        let parsed = replay
+            .as_ref()
+            .map(|r| r.properties.as_slice())
+            .map_err(|e| e.to_string());
+        let header = header_to_py(py, &data, parsed, compact)?;
+        if let Err(ParseError::CrcMismatch(..)) = replay {
+            append_quality_warning(header, "crc_failed")?;
+        }
+        let identifiers = PyDict::new(py);
+        if let Ok(replay) = &replay {
+            for (key, text) in header_identifiers(&replay.properties) {
+                identifiers.set_item(key, text)?;
+            }
+        }
+        header.set_item("identifiers", identifiers)?;
+        let recorder = replay
+            .as_ref()
+            .ok()
+            .and_then(|replay| header_recorder(&replay.properties));
+        match recorder {
+            Some(recorder) => {
+                let d = PyDict::new(py);
+                d.set_item("name", recorder.name)?;
+                d.set_item("online_id", recorder.online_id)?;
+                header.set_item("recorder", d)?;
+            }
+            None => header.set_item("recorder", py.None())?,
+        }
+        if include_raw_property_keys {
+            let keys = replay.as_ref().ok().map(|replay| {
+                let mut keys: Vec<&str> =
+                    replay.properties.iter().map(|(k, _)| k.as_str()).collect();
+                keys.sort_unstable();
+                keys
+            });
+            header.set_item("raw_property_keys", keys)?;
+        }
+        Ok(header.to_object(py))
+    })
+}
+
+type HeaderProperties = Arc<Vec<(String, HeaderProp)>>;
+
+/// The most recently header-parsed file, keyed by path, size and mtime so a rewritten file
+/// is parsed again. Lets callers probe several properties of one replay for one parse.
+struct CachedHeader {
+    path: String,
+    len: u64,
+    modified: Option<SystemTime>,
+    properties: HeaderProperties,
+}
+
+static HEADER_CACHE: Mutex<Option<CachedHeader>> = Mutex::new(None);
+
+fn cached_header_properties(path: &str) -> PyResult<HeaderProperties> {
+    let meta = std::fs::metadata(path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to open replay file '{}': {}", path, e)))?;
+    let (len, modified) = (meta.len(), meta.modified().ok());
+    let mut cache = HEADER_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(cached) = cache.as_ref() {
+        if cached.path == path && cached.len == len && cached.modified == modified {
+            return Ok(cached.properties.clone());
+        }
+    }
+    let data = read_file_bytes(path)?;
+    let replay = ParserBuilder::new(&data)
+        .never_parse_network_data()
+        .parse()
+        .map_err(|e| PyValueError::new_err(format!("Failed to parse replay header: {e}")))?;
+    let properties = Arc::new(replay.properties);
+    *cache = Some(CachedHeader {
+        path: path.to_string(),
+        len,
+        modified,
+        properties: properties.clone(),
+    });
+    Ok(properties)
+}
+
+#[pyfunction]
+fn header_property_keys(path: &str) -> PyResult<Vec<String>> {
+    let properties = cached_header_properties(path)?;
+    Ok(properties.iter().map(|(k, _)| k.clone()).collect())
+}
+
+/// One raw header property, or None. Deprecated in favour of `header_properties`: every
+/// call emits a DeprecationWarning. Repeated calls on the same unchanged file reuse one
+/// cached header parse.
+#[pyfunction]
+fn header_property(py: Python<'_>, path: &str, key: &str) -> PyResult<Option<PyObject>> {
+    PyErr::warn_bound(
+        py,
+        &py.get_type_bound::<PyDeprecationWarning>(),
+        "header_property is deprecated; use header_properties(path, [key])",
+        1,
+    )?;
+    let properties = cached_header_properties(path)?;
+    properties
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| header_prop_to_py(py, v))
+        .transpose()
+}
+
+/// Several raw header properties from one (cached) header parse: a dict of the requested
+/// keys that are present (first occurrence each), converted like `header_property`. Missing
+/// keys are left out.
+#[pyfunction]
+fn header_properties(py: Python<'_>, path: &str, keys: Vec<String>) -> PyResult<PyObject> {
+    let properties = cached_header_properties(path)?;
+    let mut wanted: HashSet<&str> = keys.iter().map(String::as_str).collect();
+    let out = PyDict::new(py);
+    for (k, v) in properties.iter() {
+        if wanted.is_empty() {
+            break;
+        }
+        if wanted.remove(k.as_str()) {
+            out.set_item(k.as_str(), header_prop_to_py(py, v)?)?;
+        }
+    }
+    Ok(out.into_py(py))
+}
+
+/// Which version-dependent attributes the network stream is expected to carry, from the
+/// header `BuildVersion` (see `builds.rs` for the threshold table). Unknown builds report
+/// every capability as present.
+#[pyfunction]
+fn build_capabilities(py: Python<'_>, path: &str) -> PyResult<PyObject> {
+    let data = read_file_bytes(path)?;
+    let replay = ParserBuilder::new(&data)
+        .never_parse_network_data()
+        .parse()
+        .map_err(|e| PyValueError::new_err(format!("Failed to parse replay header: {e}")))?;
+    let build_version = header_build_version(&replay.properties);
+    let capabilities = BuildCapabilities::from_build_version(build_version);
+
+    let out = PyDict::new(py);
+    out.set_item("build_version", build_version)?;
+    out.set_item("has_double_jump", capabilities.has_double_jump)?;
+    out.set_item(
+        "has_rigid_body_rotation",
+        capabilities.has_rigid_body_rotation,
+    )?;
+    out.set_item("uses_pickup_new", capabilities.uses_pickup_new)?;
+    Ok(out.into_py(py))
+}
+
+/// Canonical arena data for a header map name: slug, pad table and pad respawn durations.
+/// Unsupported arenas (Hoops, Dropshot, Rumble) report `supported=False` with no pads and
+/// `None` respawn durations.
+#[pyfunction]
+fn arena_info(py: Python<'_>, map_name: &str) -> PyResult<PyObject> {
+    let slug = lookup_arena_slug(map_name);
+    let pads = PyList::empty(py);
+    for pad in slug.and_then(pad_table_for_slug).unwrap_or_default() {
+        let p = PyDict::new(py);
+        p.set_item("pad_id", pad.id)?;
+        p.set_item("position", vec3_to_py(py, (pad.x, pad.y, pad.z))?)?;
+        p.set_item("is_big", pad.is_big)?;
+        p.set_item("side", pad.side)?;
+        pads.append(p)?;
+    }
+    let respawn = slug.and_then(pad_respawn_for_slug);
+
+    let out = PyDict::new(py);
+    out.set_item("map_name", map_name)?;
+    out.set_item("arena", slug)?;
+    out.set_item("supported", slug.is_some())?;
+    out.set_item("pads", pads)?;
+    out.set_item("big_pad_respawn_s", respawn.map(|r| r.big_s as f64))?;
+    out.set_item("small_pad_respawn_s", respawn.map(|r| r.small_s as f64))?;
+    Ok(out.into_py(py))
+}
+
+/// Lightweight roster (name, team, online_id, platform) from `PlayerStats` only.
+/// Skips goals, highlights and per-player stats dict conversion.
+#[pyfunction]
+fn roster(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = ParserBuilder::new(&data)
+            .never_parse_network_data()
+            .parse()
+            .map_err(|e| PyValueError::new_err(format!("Failed to parse replay header: {e}")))?;
+        let out = PyList::empty(py);
+        for entry in roster_entries(&replay.properties) {
+            out.append(roster_entry_to_py(py, &entry)?)?;
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+fn roster_entry_to_py(py: Python<'_>, entry: &RosterEntry) -> PyResult<PyObject> {
+    let p = PyDict::new(py);
+    p.set_item("name", &entry.name)?;
+    p.set_item("team", entry.team)?;
+    p.set_item("online_id", &entry.online_id)?;
+    p.set_item("platform", &entry.platform)?;
+    Ok(p.to_object(py))
+}
+
+/// Compare the `PlayerStats` rosters of two replays to group games into a series. Players
+/// match by online id (name when there is none), ignoring team. Returns `same_series`
+/// (similarity >= `threshold`, default 1.0: identical rosters), `similarity` (shared over
+/// distinct players) and the `shared` / `only_a` / `only_b` roster entries.
+#[pyfunction]
+#[pyo3(signature = (path_a, path_b, threshold = 1.0))]
+fn same_series(path_a: &str, path_b: &str, threshold: f32) -> PyResult<PyObject> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(PyValueError::new_err("threshold must be between 0 and 1"));
+    }
+    let roster_a = roster_entries(
+        &parse_header_only(path_a)
+            .map_err(PyValueError::new_err)?
+            .properties,
+    );
+    let roster_b = roster_entries(
+        &parse_header_only(path_b)
+            .map_err(PyValueError::new_err)?
+            .properties,
+    );
+    let comparison = compare_rosters(&roster_a, &roster_b);
+    Python::with_gil(|py| {
+        let entries = |entries: &[&RosterEntry]| -> PyResult<Vec<PyObject>> {
+            entries.iter().map(|e| roster_entry_to_py(py, e)).collect()
+        };
+        let out = PyDict::new(py);
+        out.set_item("same_series", comparison.same_series(threshold))?;
+        out.set_item("similarity", comparison.similarity())?;
+        out.set_item("shared", entries(&comparison.shared)?)?;
+        out.set_item("only_a", entries(&comparison.only_a)?)?;
+        out.set_item("only_b", entries(&comparison.only_b)?)?;
+        Ok(out.to_object(py))
+    })
+}
+
+fn map_network_error_code(message: &str) -> &'static str {
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("failed to open replay file")
+        || lower.contains("failed to read replay file")
+        || lower.contains("no such file")
+    {
+        "replay_io_error"
+    } else if lower.contains("unknown attributes for object") {
+        "boxcars_unknown_attribute_network_error"
+    } else {
+        "boxcars_network_error"
+    }
+}
+
+fn vec3_to_py<'py>(py: Python<'py>, v: (f32, f32, f32)) -> PyResult<&'py PyDict> {
+    let d = PyDict::new(py);
+    d.set_item("x", v.0)?;
+    d.set_item("y", v.1)?;
+    d.set_item("z", v.2)?;
+    Ok(d)
+}
+
+/// `parent[key]` when it is a dict, for refilling in place, else a new empty dict. The
+/// caller sets it back under `key`, which keeps a fresh parent's key order.
+fn reusable_dict<'py>(py: Python<'py>, parent: &'py PyDict, key: &str) -> PyResult<&'py PyDict> {
+    Ok(match parent.get_item(key)? {
+        Some(child) if child.is_instance_of::<PyDict>() => child.downcast::<PyDict>()?,
+        _ => PyDict::new(py),
+    })
+}
+
+/// `parent[key]` when it is a list, emptied, else a new empty list.
+fn reusable_list<'py>(py: Python<'py>, parent: &'py PyDict, key: &str) -> PyResult<&'py PyList> {
+    Ok(match parent.get_item(key)? {
+        Some(child) if child.is_instance_of::<PyList>() => {
+            let list = child.downcast::<PyList>()?;
+            list.del_slice(0, list.len())?;
+            list
+        }
+        _ => PyList::empty(py),
+    })
+}
+
+/// `parent[key] = {x, y, z}`, refilling an existing dict in place.
+fn set_vec3(py: Python<'_>, parent: &PyDict, key: &str, v: (f32, f32, f32)) -> PyResult<()> {
+    let d = reusable_dict(py, parent, key)?;
+    d.set_item("x", v.0)?;
+    d.set_item("y", v.1)?;
+    d.set_item("z", v.2)?;
+    parent.set_item(key, d)
+}
+
+fn player_snapshot_to_py(py: Python<'_>, player: &PlayerSnapshot) -> PyResult<PyObject> {
+    let p = PyDict::new(py);
+    fill_player_dict(py, p, player)?;
+    Ok(p.into_py(py))
+}
+
+/// Set the player dict keys on `p`, refilling its nested dicts in place.
+fn fill_player_dict(py: Python<'_>, p: &PyDict, player: &PlayerSnapshot) -> PyResult<()> {
+    p.set_item("player_id", player.player_id())?;
+    p.set_item("team", player.team)?;
+    let v = player.velocity;
+
+    // Use replicated rotation (RigidBody or compressed trajectory) if available, else fallback
+    // to velocity approximation
+    let prot = reusable_dict(py, p, "rotation")?;
+    if let Some(q) = player.rotation {
+        // Convert quaternion to euler angles (roll, pitch, yaw)
+        let (roll, pitch, yaw) = quat_to_euler(q);
+        prot.set_item("pitch", pitch)?;
+        prot.set_item("yaw", yaw)?;
+        prot.set_item("roll", roll)?;
+        // Also include raw quaternion for precision work
+        let quat = reusable_dict(py, prot, "quaternion")?;
+        quat.set_item("x", q.0 as f64)?;
+        quat.set_item("y", q.1 as f64)?;
+        quat.set_item("z", q.2 as f64)?;
+        quat.set_item("w", q.3 as f64)?;
+        prot.set_item("quaternion", quat)?;
+    } else {
+        // Fallback to velocity approximation when no rotation was replicated
+        let speed2 = v.0 * v.0 + v.1 * v.1 + v.2 * v.2;
+        let mut pitch = 0.0f64;
+        let mut yaw = 0.0f64;
+        if speed2 > 1e-6 {
+            let speed = speed2.sqrt();
+            yaw = (v.1 as f64).atan2(v.0 as f64);
+            pitch = (v.2 as f64 / speed as f64).asin();
+        }
+        prot.set_item("pitch", pitch)?;
+        prot.set_item("yaw", yaw)?;
+        prot.set_item("roll", 0.0f64)?;
+        if prot.contains("quaternion")? {
+            prot.del_item("quaternion")?;
+        }
+    }
+    set_vec3(py, p, "position", player.position)?;
+    set_vec3(py, p, "velocity", v)?;
+    p.set_item("angular_speed", magnitude(player.angular_velocity))?;
+    p.set_item("rotation", prot)?;
+    p.set_item("rotation_source", player.rotation_source.as_str())?;
+    p.set_item("boost_amount", player.boost_amount)?;
+    // Calculate speed for supersonic check
+    p.set_item("is_supersonic", player.speed() > 2300.0)?;
+    let (on_ground, ground_source) = player.on_ground();
+    p.set_item("is_on_ground", on_ground)?;
+    p.set_item("ground_source", ground_source.as_str())?;
+    p.set_item("is_demolished", player.is_demolished)?;
+    let flag = |set: bool| if set { true.into_py(py) } else { py.None() };
+    p.set_item("is_jumping", flag(player.is_jumping))?;
+    p.set_item("is_dodging", flag(player.is_dodging))?;
+    p.set_item("is_double_jumping", flag(player.is_double_jumping))?;
+    p.set_item("on_pad_id", player.on_pad_id)?;
+    p.set_item("inferred", player.inferred)?;
+    Ok(())
+}
+
+fn pad_event_to_py(py: Python<'_>, frame_event: &FramePadEvent) -> PyResult<PyObject> {
+    let event = &frame_event.event;
+    let pad_dict = PyDict::new(py);
+    pad_dict.set_item("pad_id", event.pad_id as i64)?;
+    pad_dict.set_item("is_big", event.is_big)?;
+    pad_dict.set_item("pad_side", event.pad_side)?;
+    pad_dict.set_item("arena", event.arena)?;
+    pad_dict.set_item("arena_supported", event.arena_supported)?;
+    pad_dict.set_item("status", event.status.as_str())?;
+    pad_dict.set_item("respawn_s", event.respawn_s as f64)?;
+    pad_dict.set_item("object_name", event.object_name.clone())?;
+    pad_dict.set_item("raw_state", event.raw_state)?;
+    pad_dict.set_item("timestamp", event.timestamp as f64)?;
+    pad_dict.set_item("position", vec3_to_py(py, event.position)?)?;
+
+    if let Some(raw_actor) = event.instigator_actor_id {
+        pad_dict.set_item("instigator_actor_id", raw_actor)?;
+    }
+    if let Some(resolved) = event.resolved_actor_id {
+        pad_dict.set_item("actor_id", resolved)?;
+        if let Some(idx) = frame_event.player_slot {
+            pad_dict.set_item("player_index", idx as i64)?;
+            pad_dict.set_item("player_id", format!("player_{}", idx))?;
+        }
+        if let Some(team) = frame_event.player_team {
+            pad_dict.set_item("player_team", team)?;
+        }
+        if let Some(boost) = frame_event.boost_before {
+            pad_dict.set_item("boost_before", boost)?;
+        }
+    }
+    if let Some(dist) = event.snap_distance {
+        pad_dict.set_item("snap_distance", dist as f64)?;
+    }
+    if let Some(err) = event.snap_error_uu {
+        pad_dict.set_item("snap_error_uu", err as f64)?;
+    }
+    pad_dict.set_item("emission_latency_s", event.emission_latency_s as f64)?;
+    Ok(pad_dict.into_py(py))
+}
+
+fn ball_snapshot_to_py<'py>(
+    py: Python<'py>,
+    ball: &BallSnapshot,
+    physics: &Physics,
+) -> PyResult<&'py PyDict> {
+    let b = PyDict::new(py);
+    fill_ball_dict(py, b, ball, physics)?;
+    Ok(b)
+}
+
+/// Set the ball dict keys on `b`, refilling its nested dicts in place.
+fn fill_ball_dict(
+    py: Python<'_>,
+    b: &PyDict,
+    ball: &BallSnapshot,
+    physics: &Physics,
+) -> PyResult<()> {
+    set_vec3(py, b, "position", ball.position)?;
+    set_vec3(py, b, "velocity", ball.velocity)?;
+    set_vec3(py, b, "angular_velocity", ball.angular_velocity)?;
+    b.set_item("angular_speed", magnitude(ball.angular_velocity))?;
+    // Ballistic next floor contact, ignoring bounces; None while grounded or asleep.
+    let landing = (!ball.sleeping)
+        .then(|| predict_landing(ball.position, ball.velocity, physics))
+        .flatten();
+    match landing {
+        Some((x, y, time_to_land)) => {
+            let l = reusable_dict(py, b, "predicted_landing")?;
+            l.set_item("x", x)?;
+            l.set_item("y", y)?;
+            l.set_item("time_to_land", time_to_land)?;
+            b.set_item("predicted_landing", l)?;
+        }
+        None => b.set_item("predicted_landing", py.None())?,
+    }
+    Ok(())
+}
+
+/// With `fixed_slots`, `players` is indexed by slot: at least that many entries, None
+/// where the slot has no car this frame. `physics` drives the ball landing prediction.
+/// `lag_spike` flags a frame arriving over the lag spike threshold after the previous one.
+fn frame_snapshot_to_py(
+    py: Python<'_>,
+    frame: &FrameSnapshot,
+    fixed_slots: Option<usize>,
+    physics: &Physics,
+    lag_spike: bool,
+) -> PyResult<PyObject> {
+    let f = PyDict::new(py);
+    fill_frame_dict(py, f, frame, fixed_slots, physics, lag_spike, None)?;
+    Ok(f.into_py(py))
+}
+
+/// Set the frame dict keys on `f` as frame_snapshot_to_py does, refilling the nested ball,
+/// players and metadata containers already in `f` in place. With `player_dicts`, the dict
+/// for slot i is `player_dicts[i]`, refilled (and created on first use) rather than new.
+fn fill_frame_dict(
+    py: Python<'_>,
+    f: &PyDict,
+    frame: &FrameSnapshot,
+    fixed_slots: Option<usize>,
+    physics: &Physics,
+    lag_spike: bool,
+    mut player_dicts: Option<&mut Vec<Py<PyDict>>>,
+) -> PyResult<()> {
+    f.set_item("timestamp", frame.timestamp as f64)?;
+    f.set_item("lag_spike", lag_spike)?;
+    let ball = reusable_dict(py, f, "ball")?;
+    fill_ball_dict(py, ball, &frame.ball, physics)?;
+    f.set_item("ball", ball)?;
+
+    let mut player_to_py = |player: &PlayerSnapshot| -> PyResult<PyObject> {
+        let Some(dicts) = player_dicts.as_mut() else {
+            return player_snapshot_to_py(py, player);
+        };
+        while dicts.len() <= player.slot {
+            dicts.push(PyDict::new(py).into());
+        }
+        let p = dicts[player.slot].as_ref(py);
+        fill_player_dict(py, p, player)?;
+        Ok(p.into_py(py))
+    };
+    let players = reusable_list(py, f, "players")?;
+    match fixed_slots {
+        Some(slots) => {
+            let len = frame
+                .players
+                .iter()
+                .map(|p| p.slot + 1)
+                .fold(slots, usize::max);
+            let mut by_slot: Vec<PyObject> = vec![py.None(); len];
+            for player in &frame.players {
+                by_slot[player.slot] = player_to_py(player)?;
+            }
+            for entry in by_slot {
+                players.append(entry)?;
+            }
+        }
+        None => {
+            for player in &frame.players {
+                players.append(player_to_py(player)?)?;
+            }
+        }
+    }
+    f.set_item("players", players)?;
+    let parser_meta = reusable_dict(py, f, "_parser_meta")?;
+    parser_meta.set_item("classification_source", frame.classification_source)?;
+    parser_meta.set_item("schema_version", FRAME_SCHEMA_VERSION)?;
+    f.set_item("_parser_meta", parser_meta)?;
+
+    let pad_list = reusable_list(py, f, "boost_pad_events")?;
+    for event in &frame.pad_events {
+        pad_list.append(pad_event_to_py(py, event)?)?;
+    }
+    f.set_item("boost_pad_events", pad_list)?;
+    Ok(())
+}
+
+// Raised when a replay fails boxcars' CRC validation (corrupt or tampered file). A
+// ValueError subclass, so existing `except ValueError` handlers still catch it.
+pyo3::create_exception!(rlreplay_rust, ReplayFormatError, PyValueError);
+
+/// boxcars CRC mode for a `check_crc` kwarg: `Always` when set, else boxcars' default
+/// (`OnError`: the CRC is only consulted to explain a decode failure).
+fn crc_check(check_crc: bool) -> CrcCheck {
+    if check_crc {
+        CrcCheck::Always
+    } else {
+        CrcCheck::OnError
+    }
+}
+
+fn parse_network_replay(data: &[u8]) -> PyResult<Replay> {
+    parse_network_replay_with_crc(data, false)
+}
+
+/// Like `parse_network_replay`; a CRC mismatch raises ReplayFormatError.
+fn parse_network_replay_with_crc(data: &[u8], check_crc: bool) -> PyResult<Replay> {
+    ParserBuilder::new(data)
+        .with_crc_check(crc_check(check_crc))
+        .must_parse_network_data()
+        .parse()
+        .map_err(|e| match e {
+            ParseError::CrcMismatch(..) => ReplayFormatError::new_err(format!("crc_failed: {e}")),
+            e => PyValueError::new_err(format!("Failed to parse network frames: {e}")),
+        })
+}
+
+/// Resolve an `only_player` selector to a header slot: an int slot, a "player_N" id,
+/// or a roster online id / name. A slot past the header roster raises ValueError; without a
+/// roster, slots are derived from the network and any slot is accepted.
+fn resolve_player_slot(
+    properties: &[(String, HeaderProp)],
+    selector: &Bound<'_, PyAny>,
+) -> PyResult<usize> {
+    let checked = |slot: usize| {
+        let roster = header_players(properties).len();
+        if roster > 0 && slot >= roster {
+            return Err(PyValueError::new_err(format!(
+                "Unknown player slot {} ({} players in the header)",
+                slot, roster
+            )));
+        }
+        Ok(slot)
+    };
+    if let Ok(slot) = selector.extract::<usize>() {
+        return checked(slot);
+    }
+    let key: String = selector
+        .extract()
+        .map_err(|_| PyValueError::new_err("player must be a player slot or id string"))?;
+    if let Some(slot) = key
+        .strip_prefix("player_")
+        .and_then(|n| n.parse::<usize>().ok())
+    {
+        return checked(slot);
+    }
+    roster_entries(properties)
+        .iter()
+        .position(|entry| entry.online_id.as_deref() == Some(key.as_str()) || entry.name == key)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown player '{}'", key)))
+}
+
+fn team_spacing_to_py<'py>(py: Python<'py>, players: &[PlayerSnapshot]) -> PyResult<&'py PyList> {
+    let out = PyList::empty(py);
+    for team in 0..=1 {
+        let positions: Vec<(f32, f32, f32)> = players
+            .iter()
+            .filter(|p| p.team == team && !p.is_demolished)
+            .map(|p| p.position)
+            .collect();
+        let spacing = pairwise_spacing(&positions);
+        let d = PyDict::new(py);
+        d.set_item("team", team)?;
+        d.set_item("players", positions.len())?;
+        d.set_item("min", spacing.map(|s| s.0))?;
+        d.set_item("mean", spacing.map(|s| s.1))?;
+        d.set_item("max", spacing.map(|s| s.2))?;
+        out.append(d)?;
+    }
+    Ok(out)
+}
+
+/// Resolve a `player_team` mapping of player selectors (as for `only_player`) to slot → team.
+fn resolve_team_overrides(
+    properties: &[(String, HeaderProp)],
+    mapping: &Bound<'_, PyDict>,
+) -> PyResult<HashMap<usize, i64>> {
+    let mut overrides = HashMap::new();
+    for (selector, team) in mapping.iter() {
+        let slot = resolve_player_slot(properties, &selector)?;
+        let team: i64 = team.extract()?;
+        if team != 0 && team != 1 {
+            return Err(PyValueError::new_err(format!(
+                "Team for player_{} must be 0 or 1, got {}",
+                slot, team
+            )));
+        }
+        overrides.insert(slot, team);
+    }
+    Ok(overrides)
+}
+
+/// Per-frame network state as `{schema_version, frames, warnings}`: one dict per emitted
+/// network frame in `frames`, `schema_version` the frame schema (`FRAME_SCHEMA_VERSION`)
+/// they follow, and `warnings` the soft warning codes raised for the replay, such as
+/// `large_replay_consider_streaming`; each is also passed to the warning callback.
+///
+/// With `only_player`, only frames where that player has a live (spawned, not demolished)
+/// car are emitted; all frames are still processed. A slot past the header roster, or a
+/// name / id not in it, raises ValueError. With `fixed_player_slots`, every frame's
+/// `players` list has one entry per slot (None when the slot has no car), so
+/// `frame["players"][i]` is always slot i.
+///
+/// Every frame carries `lag_spike`, true when it arrived more than `lag_spike_threshold`
+/// seconds (default 0.1, about three frames at 30 fps) after the previous network frame
+/// (emitted or not); positions jump across such gaps, so skip them in speed derivatives and
+/// other smoothness-sensitive analysis.
+///
+/// `player_team` maps player selectors to 0 (blue) or 1 (orange) for replays where team
+/// assignment goes wrong. Precedence: explicit override > TeamPaint > y-sign fallback. An
+/// overridden player is queued on its override team when cars are matched to roster slots,
+/// and its car reports that team (including on pad events) regardless of TeamPaint.
+///
+/// With `team_relative`, every player attacks +y: orange players' position, velocity and
+/// rotation are turned half a turn about the vertical axis (x and y negated, yaw + π).
+/// `ball` stays in the blue frame and `ball_orange_perspective` adds the turned copy;
+/// `_parser_meta.team_relative` is set to True on such frames.
+///
+/// With `team_spacing`, each frame gains `team_spacing`: one `{team, players, min, mean,
+/// max}` dict per team (index = team) summarising the pairwise distances (uu) between that
+/// team's cars on the field (spawned, not demolished). min / mean / max are None with fewer
+/// than two such cars.
+///
+/// With `include_names`, each player dict gains `name`: the header roster name for its slot,
+/// or None for cars beyond the roster (replays without a `PlayerStats` header).
+///
+/// With `goal_distances`, `ball` gains `dist_to_blue_goal` and `dist_to_orange_goal`: the
+/// (x, y) distance (uu) from the ball to each goal line centre, as used for shot distance.
+/// Both are None on arenas without a goal table (Hoops, Dropshot).
+///
+/// With `check_crc`, boxcars validates the replay CRCs first and a mismatch raises
+/// ReplayFormatError (a ValueError subclass) instead of decoding a corrupt file.
+///
+/// With `coalesce_pad_events`, a pad event repeating the same pad's previous status within
+/// `PAD_EVENT_COALESCE_WINDOW` seconds (0.5) of it is dropped, keeping the earliest. Real
+/// repeats are a respawn cycle apart, so this only removes re-triggered duplicates.
+///
+/// With `collected_pad`, each player dict gains `collected_pad`: the id of a pad that player
+/// collected on this frame (from the frame's COLLECTED pad events), else None.
+///
+/// With `last_man_back`, each frame gains `last_man_back`: one player_id per team (index =
+/// team) for the team's car on the field deepest toward its own goal, the lower slot on a
+/// tie, or None when the team has no car on the field. Computed in the blue frame, so it is
+/// unaffected by `team_relative`.
+///
+/// With `goal_relative`, each player dict gains positioning scalars in its own team's
+/// frame (uu): `depth`, the y offset from midfield toward the player's own goal (positive
+/// in its own half), and `width`, the x offset to its left when facing the opponent goal.
+/// Blue and orange values are directly comparable; positions themselves are unchanged.
+///
+/// `gravity` (uu/s², default 650) and `ball_radius` (uu, default 92.75) are the ball
+/// physics behind `ball.predicted_landing` and its grounded check, and `ball_radius` sets
+/// the goal line for `verify_mapping`'s goal detection; override them for low-gravity or
+/// big-ball mutator replays.
+///
+/// With `flat`, each frame is a plain tuple instead of a dict: the `FLAT_FRAME_FIELDS`
+/// (timestamp, is_live, lag_spike, ball_x .. ball_vz) followed by the `FLAT_PLAYER_FIELDS`
+/// (x, y, z, vx, vy, vz, boost, is_demolished) of every slot in order, None where the slot
+/// has no car; the same values as frames_columnar, one row per frame. Every tuple has the
+/// same length. `FLAT_COMPATIBLE_OPTIONS` still apply (`team_relative` half-turns orange
+/// cars) or have nothing to change in a tuple; the other options add dict keys and raise
+/// ValueError.
+///
+/// With `possession_prob`, `ball` gains `possession_prob`: a soft possession estimate from
+/// 0 (team 0 certain) to 1 (team 1 certain) blending the last touch (since the last
+/// restart, default touch thresholds) with which team has more and closer cars on the
+/// field; see `stats::possession_probability` for the weighting.
+///
+/// With `pressure`, each frame gains `pressure`: `{blue, orange}`, how threatening play is
+/// to each goal from 0 to 1, blending the ball's distance to that goal, its speed toward it
+/// and the share of attackers in the attacking third; see `stats::goal_pressure` for the
+/// formula and `PRESSURE_COEFFICIENTS` for its coefficients. None on arenas without a goal
+/// table (Hoops, Dropshot).
+///
+/// With `verify_mapping`, the heuristic car → player mapping is cross-checked against the
+/// header box score: each header goal's scorer is compared with the roster name of the
+/// player the frames credit with it (the last scoring-team touch before the crossing,
+/// default touch thresholds). The result gains `mapping_confidence`, the share of compared
+/// goals that agree (None when no goal could be compared), and `mapping_mismatches`, one
+/// `{frame, header_scorer, frame_scorer}` per disagreeing goal (`frame` the network frame
+/// of the crossing, `frame_scorer` None for a car beyond the roster). Any mismatch adds
+/// `player_mapping_mismatch` to `warnings`, and each is passed to the warning callback.
+/// Nothing is remapped.
+///
+/// With `smooth_velocity`, `ball` and each player dict gain `velocity_smoothed` next to the
+/// raw `velocity`: a trailing moving average over the last `VELOCITY_SMOOTHING_WINDOW`
+/// frames (5, current included) that damps the quantization jitter of replicated
+/// velocities, lagging real changes by about two frames. It never looks ahead. The ball's
+/// history restarts on non-live frames, a car's whenever it is demolished or absent, and
+/// orange cars' values follow `team_relative` like the raw ones.
+///
+/// With `merged_entities`, `ball` and `players` are replaced by one `entities` list: the
+/// ball first, then every car on the frame (fixed-slot placeholders are dropped). Each
+/// entity has `type` ("ball" or "car"), `id` ("ball" or the player_id), `position`,
+/// `velocity` and `rotation` (None for the ball, whose rotation is not replicated), plus
+/// the keys of its usual dict.
+///
+/// With `raw_attribute_objects`, a list of object name substrings, the decoded attribute
+/// updates of actors whose object name contains any of them are attached as
+/// `raw_attributes`: `[{actor_id, object_name, attribute, value}]`, values as in
+/// iter_attribute. Updates land on the ball dict for the ball, on a player dict for its car
+/// or the car's components, and on the frame for any other actor; every one of those gets
+/// the key, empty when nothing matched.
+///
+/// `coordinates` picks the output convention for interop: "rlcoach" (default), "rlbot" or
+/// "carball". All three keep the game's axes (uu, blue goal at -y, orange at +y, +z up,
+/// left-handed), so positions, velocities and the raw `quaternion` are never changed; only
+/// the players' Euler `roll` / `pitch` / `yaw` differ:
+/// - "rlcoach": the game's rotator convention (yaw 0 facing +x and π/2 facing +y, pitch
+///   positive nose-up, roll positive rolling right).
+/// - "rlbot": identical to "rlcoach"; RLBot reports the game's own rotator.
+/// - "carball": right-handed aerospace angles of the same quaternion, i.e. `roll` and
+///   `pitch` negated, `yaw` unchanged.
+/// Under a non-default convention `_parser_meta.coordinates` names it. Flat tuples carry no
+/// rotation and are unaffected.
+///
+/// When an actor gets both a RigidBody and a bare Location update in one frame, the
+/// RigidBody position is used. `prefer_location` flips that for debugging position flicker.
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    only_player = None,
+    fixed_player_slots = false,
+    team_relative = false,
+    player_team = None,
+    team_spacing = false,
+    include_names = false,
+    goal_distances = false,
+    check_crc = false,
+    coalesce_pad_events = false,
+    collected_pad = false,
+    last_man_back = false,
+    goal_relative = false,
+    gravity = GRAVITY_UU_S2,
+    ball_radius = BALL_RADIUS,
+    flat = false,
+    prefer_location = false,
+    possession_prob = false,
+    pressure = false,
+    verify_mapping = false,
+    smooth_velocity = false,
+    merged_entities = false,
+    raw_attribute_objects = None,
+    coordinates = "rlcoach",
+    lag_spike_threshold = DEFAULT_LAG_SPIKE_THRESHOLD_S,
+))]
+#[allow(clippy::too_many_arguments)]
+fn iter_frames(
+    path: &str,
+    only_player: Option<&Bound<'_, PyAny>>,
+    fixed_player_slots: bool,
+    team_relative: bool,
+    player_team: Option<&Bound<'_, PyDict>>,
+    team_spacing: bool,
+    include_names: bool,
+    goal_distances: bool,
+    check_crc: bool,
+    coalesce_pad_events: bool,
+    collected_pad: bool,
+    last_man_back: bool,
+    goal_relative: bool,
+    gravity: f32,
+    ball_radius: f32,
+    flat: bool,
+    prefer_location: bool,
+    possession_prob: bool,
+    pressure: bool,
+    verify_mapping: bool,
+    smooth_velocity: bool,
+    merged_entities: bool,
+    raw_attribute_objects: Option<Vec<String>>,
+    coordinates: &str,
+    lag_spike_threshold: f32,
+) -> PyResult<Py<PyAny>> {
+    let convention = CoordinateConvention::from_name(coordinates).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "coordinates must be 'rlcoach', 'rlbot' or 'carball', got '{}'",
+            coordinates
+        ))
+    })?;
+    let options = FrameOptions {
+        only_player: only_player.map(|selector| selector.clone().unbind()),
+        fixed_player_slots,
+        team_relative,
+        player_team: player_team.map(|mapping| mapping.clone().unbind()),
+        team_spacing,
+        include_names,
+        goal_distances,
+        check_crc,
+        coalesce_pad_events,
+        collected_pad,
+        last_man_back,
+        goal_relative,
+        physics: ball_physics(gravity, ball_radius)?,
+        flat,
+        prefer_location,
+        possession_prob,
+        pressure,
+        verify_mapping,
+        smooth_velocity,
+        merged_entities,
+        raw_attribute_objects,
+        coordinates: convention,
+        lag_spike_threshold: Some(crate::lag_spike_threshold(lag_spike_threshold)?),
+    };
+    if options.flat && options.dict_extras() {
+        return Err(PyValueError::new_err(format!(
+            "flat frames accept just these options: {}",
+            FLAT_COMPATIBLE_OPTIONS.join(", ")
+        )));
+    }
+    frames_with_options(path, &options)
+}
+
+/// iter_frames keywords that combine with `flat`; every other option adds keys to the frame
+/// dicts (`FrameOptions::dict_extras`).
+const FLAT_COMPATIBLE_OPTIONS: [&str; 11] = [
+    "only_player",
+    "fixed_player_slots",
+    "player_team",
+    "team_relative",
+    "prefer_location",
+    "check_crc",
+    "coalesce_pad_events",
+    "gravity",
+    "ball_radius",
+    "coordinates",
+    "lag_spike_threshold",
+];
+
+/// iter_frames keyword arguments, validated once per call; see iter_frames for what each
+/// one does. The default is a plain iter_frames(path).
+#[derive(Default)]
+struct FrameOptions {
+    only_player: Option<PyObject>,
+    fixed_player_slots: bool,
+    team_relative: bool,
+    player_team: Option<Py<PyDict>>,
+    team_spacing: bool,
+    include_names: bool,
+    goal_distances: bool,
+    check_crc: bool,
+    coalesce_pad_events: bool,
+    collected_pad: bool,
+    last_man_back: bool,
+    goal_relative: bool,
+    /// From `gravity` and `ball_radius`.
+    physics: Physics,
+    flat: bool,
+    prefer_location: bool,
+    possession_prob: bool,
+    pressure: bool,
+    verify_mapping: bool,
+    smooth_velocity: bool,
+    merged_entities: bool,
+    raw_attribute_objects: Option<Vec<String>>,
+    coordinates: CoordinateConvention,
+    /// None is `DEFAULT_LAG_SPIKE_THRESHOLD_S`.
+    lag_spike_threshold: Option<f32>,
+}
+
+impl FrameOptions {
+    /// Whether any option adds keys to the frame dicts beyond `team_relative`.
+    fn dict_extras(&self) -> bool {
+        self.team_spacing
+            || self.include_names
+            || self.goal_distances
+            || self.collected_pad
+            || self.last_man_back
+            || self.goal_relative
+            || self.possession_prob
+            || self.pressure
+            || self.verify_mapping
+            || self.smooth_velocity
+            || self.merged_entities
+            || self.raw_attribute_objects.is_some()
+    }
+}
+
+fn frames_with_options(path: &str, options: &FrameOptions) -> PyResult<Py<PyAny>> {
+    let FrameOptions {
+        ref only_player,
+        fixed_player_slots,
+        team_relative,
+        ref player_team,
+        team_spacing,
+        include_names,
+        goal_distances,
+        check_crc,
+        coalesce_pad_events,
+        collected_pad,
+        last_man_back,
+        goal_relative,
+        physics,
+        flat,
+        prefer_location,
+        possession_prob,
+        pressure,
+        verify_mapping,
+        smooth_velocity,
+        merged_entities,
+        ref raw_attribute_objects,
+        coordinates: convention,
+        lag_spike_threshold,
+    } = *options;
+    let dict_extras = options.dict_extras();
+    Python::with_gil(|py| {
+        let mut timer = CallTimer::start("iter_frames");
+        let data = read_file_bytes(path)?;
+        timer.read_done();
+        // Parse with network data enabled
+        let replay = parse_network_replay_with_crc(&data, check_crc)?;
+        timer.decode_done();
+        let only_slot = only_player
+            .as_ref()
+            .map(|selector| resolve_player_slot(&replay.properties, selector.bind(py)))
+            .transpose()?;
+        let team_overrides = player_team
+            .as_ref()
+            .map(|mapping| resolve_team_overrides(&replay.properties, mapping.bind(py)))
+            .transpose()?
+            .unwrap_or_default();
+        let mut warnings: Vec<&str> = Vec::new();
+        let frame_count = network_frame_count(&replay);
+        warnings.extend(large_replay_warning(py, path, frame_count)?);
+
+        let names: Vec<String> = if include_names {
+            header_players(&replay.properties)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let goal_centers =
+            lookup_arena_slug(&header_map_name(&replay.properties)).and_then(goal_centers_for_slug);
+        let mut state = NetworkState::with_team_overrides(&replay, team_overrides);
+        if prefer_location {
+            state.set_physics_priority(PhysicsPriority::Location);
+        }
+        let mut pad_coalescer = coalesce_pad_events.then(PadEventCoalescer::new);
+        let frame_extras = team_relative || dict_extras;
+        let mut flat_columns = flat.then(|| FrameColumns::new(state.slot_count()));
+        let mut possession_touches =
+            possession_prob.then(|| TouchDetector::new(TOUCH_DISTANCE_UU, MIN_VELOCITY_DELTA_UU_S));
+        let mut last_touch_team: Option<i64> = None;
+        let mut lag =
+            LagSpikeDetector::new(lag_spike_threshold.unwrap_or(DEFAULT_LAG_SPIKE_THRESHOLD_S));
+        let mut smoother = smooth_velocity.then(VelocitySmoother::new);
+        let mut raw_filter = raw_attribute_objects.clone().map(RawAttributeFilter::new);
+        let mut mapping_detectors = verify_mapping.then(|| {
+            (
+                TouchDetector::new(TOUCH_DISTANCE_UU, MIN_VELOCITY_DELTA_UU_S),
+                GoalDetector::with_physics(&physics),
+            )
+        });
+        // (network frame, credited scorer slot) per detected goal.
+        let mut detected_goals: Vec<(usize, Option<usize>)> = Vec::new();
+
+        let frames_out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for (index, nf) in net.frames.iter().enumerate() {
+                let mut frame = state.process_frame(nf);
+                let lag_spike = lag.observe(frame.timestamp);
+                if let Some(coalescer) = pad_coalescer.as_mut() {
+                    frame.pad_events.retain(|pad| coalescer.keep(&pad.event));
+                }
+                // Touches are tracked on every frame, including those only_player skips.
+                if let Some(touches) = possession_touches.as_mut() {
+                    if !frame.is_live {
+                        last_touch_team = None;
+                    }
+                    if let Some(touch) = touches.observe(&frame) {
+                        last_touch_team = Some(touch.team);
+                    }
+                }
+                if let Some((touches, goals)) = mapping_detectors.as_mut() {
+                    let touch = touches.observe(&frame);
+                    if let Some(goal) = goals.observe(&frame, touch.as_ref()) {
+                        detected_goals.push((index, goal.scorer_slot));
+                    }
+                }
+                // Histories need every frame, so smooth before only_player skips any.
+                let smoothed = smoother.as_mut().map(|s| s.observe(&frame));
+                let raw_updates = raw_filter
+                    .as_mut()
+                    .map(|filter| filter.observe(nf, &replay.objects));
+                if let Some(slot) = only_slot {
+                    let has_car = frame
+                        .players
+                        .iter()
+                        .any(|p| p.slot == slot && !p.is_demolished);
+                    if !has_car {
+                        continue;
+                    }
+                }
+                if let Some(columns) = flat_columns.as_mut() {
+                    if team_relative {
+                        for player in frame.players.iter_mut().filter(|p| p.team == 1) {
+                            *player = player.half_turned();
+                        }
+                    }
+                    columns.push(&frame, lag_spike);
+                    continue;
+                }
+                let fixed_slots = fixed_player_slots.then(|| state.slot_count());
+                if !frame_extras {
+                    frames_out.append(frame_snapshot_to_py(
+                        py,
+                        &frame,
+                        fixed_slots,
+                        &physics,
+                        lag_spike,
+                    )?)?;
+                    continue;
+                }
+                let last_man = last_man_back.then(|| stats::last_man_back(&frame.players));
+                let possession =
+                    possession_prob.then(|| stats::possession_probability(last_touch_team, &frame));
+                let goal_pressure = pressure
+                    .then(|| goal_centers.map(|centers| stats::goal_pressure(&frame, centers)));
+                if team_relative {
+                    for player in frame.players.iter_mut().filter(|p| p.team == 1) {
+                        *player = player.half_turned();
+                    }
+                }
+                let f = frame_snapshot_to_py(py, &frame, fixed_slots, &physics, lag_spike)?;
+                let f = f.downcast_bound::<PyDict>(py)?;
+                if team_relative {
+                    f.set_item(
+                        "ball_orange_perspective",
+                        ball_snapshot_to_py(py, &frame.ball.half_turned(), &physics)?,
+                    )?;
+                    if let Some(meta) = f.get_item("_parser_meta")? {
+                        meta.set_item("team_relative", true)?;
+                    }
+                }
+                if team_spacing {
+                    // Distances are unchanged by the team-relative half turn.
+                    f.set_item("team_spacing", team_spacing_to_py(py, &frame.players)?)?;
+                }
+                if goal_distances {
+                    if let Some(ball) = f.get_item("ball")? {
+                        let (x, y, _) = frame.ball.position;
+                        let dist = |goal: usize| {
+                            goal_centers.map(|centers| {
+                                let (goal_x, goal_y) = centers[goal];
+                                (goal_x - x).hypot(goal_y - y)
+                            })
+                        };
+                        ball.set_item("dist_to_blue_goal", dist(0))?;
+                        ball.set_item("dist_to_orange_goal", dist(1))?;
+                    }
+                }
+                if let Some(smoothed) = &smoothed {
+                    if let Some(ball) = f.get_item("ball")? {
+                        ball.set_item("velocity_smoothed", vec3_to_py(py, smoothed.ball)?)?;
+                    }
+                }
+                if let Some(possession) = possession {
+                    if let Some(ball) = f.get_item("ball")? {
+                        ball.set_item("possession_prob", possession)?;
+                    }
+                }
+                if let Some(goal_pressure) = goal_pressure {
+                    match goal_pressure {
+                        Some([blue, orange]) => {
+                            let d = PyDict::new(py);
+                            d.set_item("blue", blue)?;
+                            d.set_item("orange", orange)?;
+                            f.set_item("pressure", d)?;
+                        }
+                        None => f.set_item("pressure", py.None())?,
+                    }
+                }
+                if let Some(last_man) = last_man {
+                    let ids = last_man.map(|slot| slot.map(|slot| format!("player_{}", slot)));
+                    f.set_item("last_man_back", ids.to_vec())?;
+                }
+                if include_names || collected_pad || goal_relative || smoothed.is_some() {
+                    if let Some(players) = f.get_item("players")? {
+                        for (i, player) in frame.players.iter().enumerate() {
+                            // Fixed-slot lists are indexed by slot, others follow frame order.
+                            let index = if fixed_player_slots { player.slot } else { i };
+                            let p = players.get_item(index)?;
+                            if include_names {
+                                p.set_item("name", names.get(player.slot))?;
+                            }
+                            if collected_pad {
+                                let pad_id = frame
+                                    .pad_events
+                                    .iter()
+                                    .find(|pad| {
+                                        pad.player_slot == Some(player.slot)
+                                            && matches!(pad.event.status, PadEventStatus::Collected)
+                                    })
+                                    .map(|pad| pad.event.pad_id);
+                                p.set_item("collected_pad", pad_id)?;
+                            }
+                            if goal_relative {
+                                // Orange cars are already half-turned under team_relative.
+                                let position = if player.team == 1 && team_relative {
+                                    half_turn(player.position)
+                                } else {
+                                    player.position
+                                };
+                                let (depth, width) = goal_relative_offsets(player.team, position);
+                                p.set_item("depth", depth)?;
+                                p.set_item("width", width)?;
+                            }
+                            if let Some(smoothed) = &smoothed {
+                                let v = smoothed.car(player.slot).map(|v| {
+                                    if player.team == 1 && team_relative {
+                                        half_turn(v)
+                                    } else {
+                                        v
+                                    }
+                                });
+                                let v = v.map(|v| vec3_to_py(py, v)).transpose()?;
+                                p.set_item("velocity_smoothed", v)?;
+                            }
+                        }
+                    }
+                }
+                if let Some(raw_updates) = &raw_updates {
+                    attach_raw_attributes(py, f, &state, &frame, fixed_player_slots, raw_updates)?;
+                }
+                frames_out.append(f)?;
+            }
+        }
+        if let Some(columns) = &flat_columns {
+            append_flat_frames(py, frames_out, columns)?;
+        }
+        // Without a header roster, slots are assigned as cars appear; pad earlier frames
+        // to the final count so every list has the same length.
+        if fixed_player_slots && !flat {
+            let slots = state.slot_count();
+            for frame in frames_out.iter() {
+                if let Ok(players) = frame.get_item("players")?.downcast::<PyList>() {
+                    while players.len() < slots {
+                        players.append(py.None())?;
+                    }
+                }
+            }
+        }
+        let mut mapping = None;
+        if verify_mapping {
+            let slot_names: Vec<String> = header_players(&replay.properties)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+            let check = check_scorer_mapping(
+                &header_goals(&replay.properties),
+                &detected_goals,
+                &slot_names,
+            );
+            let mismatches = PyList::empty(py);
+            for mismatch in &check.mismatches {
+                let m = PyDict::new(py);
+                m.set_item("frame", mismatch.frame)?;
+                m.set_item("header_scorer", &mismatch.header_scorer)?;
+                m.set_item("frame_scorer", &mismatch.frame_scorer)?;
+                mismatches.append(m)?;
+                let credited = match &mismatch.frame_scorer {
+                    Some(name) => format!("'{}'", name),
+                    None => "a car beyond the roster".to_string(),
+                };
+                let message = format!(
+                    "Replay '{}': the header credits the goal at network frame {} to '{}' but \
+                     the frames credit {}; the car to player mapping may be shuffled",
+                    path, mismatch.frame, mismatch.header_scorer, credited,
+                );
+                emit_warning(py, MAPPING_MISMATCH_WARNING, message)?;
+            }
+            if !check.mismatches.is_empty() {
+                warnings.push(MAPPING_MISMATCH_WARNING);
+            }
+            mapping = Some((check.confidence(), mismatches));
+        }
+        if convention != CoordinateConvention::Rlcoach && !flat {
+            for frame in frames_out.iter() {
+                convert_frame_rotations(frame.downcast::<PyDict>()?, convention)?;
+            }
+        }
+        if merged_entities {
+            for frame in frames_out.iter() {
+                merge_frame_entities(py, frame.downcast::<PyDict>()?)?;
+            }
+        }
+
+        let out = PyDict::new(py);
+        out.set_item("schema_version", FRAME_SCHEMA_VERSION)?;
+        out.set_item("frames", frames_out)?;
+        out.set_item("warnings", warnings)?;
+        if let Some((confidence, mismatches)) = mapping {
+            out.set_item("mapping_confidence", confidence)?;
+            out.set_item("mapping_mismatches", mismatches)?;
+        }
+        timer.finish();
+        Ok(out.into())
+    })
+}
+
+/// Re-express the player rotations of an iter_frames dict in `convention` and record it in
+/// `_parser_meta.coordinates`.
+fn convert_frame_rotations(f: &PyDict, convention: CoordinateConvention) -> PyResult<()> {
+    if let Some(players) = f.get_item("players")? {
+        for player in players.downcast::<PyList>()?.iter() {
+            if player.is_none() {
+                continue;
+            }
+            let rotation = player.get_item("rotation")?;
+            let angle = |key: &str| -> PyResult<f64> { rotation.get_item(key)?.extract() };
+            let (roll, pitch, yaw) =
+                convention.euler((angle("roll")?, angle("pitch")?, angle("yaw")?));
+            rotation.set_item("roll", roll)?;
+            rotation.set_item("pitch", pitch)?;
+            rotation.set_item("yaw", yaw)?;
+        }
+    }
+    if let Some(meta) = f.get_item("_parser_meta")? {
+        meta.set_item("coordinates", convention.as_str())?;
+    }
+    Ok(())
+}
+
+/// Replace a frame dict's `ball` and `players` with the `entities` list of
+/// iter_frames(merged_entities=True).
+fn merge_frame_entities(py: Python<'_>, f: &PyDict) -> PyResult<()> {
+    let entities = PyList::empty(py);
+    if let Some(ball) = f.get_item("ball")? {
+        ball.set_item("type", "ball")?;
+        ball.set_item("id", "ball")?;
+        ball.set_item("rotation", py.None())?;
+        entities.append(ball)?;
+        f.del_item("ball")?;
+    }
+    if let Some(players) = f.get_item("players")? {
+        for player in players.downcast::<PyList>()?.iter() {
+            if player.is_none() {
+                continue;
+            }
+            player.set_item("type", "car")?;
+            player.set_item("id", player.get_item("player_id")?)?;
+            entities.append(player)?;
+        }
+        f.del_item("players")?;
+    }
+    f.set_item("entities", entities)
+}
+
+/// Attach iter_frames(raw_attribute_objects=...) updates to the frame dict `f`: on the
+/// ball, on the player dict of the car an actor belongs to, or on the frame.
+fn attach_raw_attributes(
+    py: Python<'_>,
+    f: &Bound<'_, PyDict>,
+    state: &NetworkState,
+    frame: &FrameSnapshot,
+    fixed_player_slots: bool,
+    updates: &[RawUpdate<'_>],
+) -> PyResult<()> {
+    let ball = PyList::empty(py);
+    let frame_level = PyList::empty(py);
+    let by_player: Vec<&PyList> = frame.players.iter().map(|_| PyList::empty(py)).collect();
+    for update in updates {
+        let d = PyDict::new(py);
+        d.set_item("actor_id", update.actor_id)?;
+        d.set_item("object_name", update.object_name)?;
+        d.set_item("attribute", update.attribute_name)?;
+        d.set_item("value", attribute_value_to_py(py, update.attribute)?)?;
+        let owner = state.resolve_owner(update.actor_id);
+        let player = state
+            .slot_for_actor(owner)
+            .and_then(|slot| frame.players.iter().position(|p| p.slot == slot));
+        if state.ball_actor() == Some(owner) {
+            ball.append(d)?;
+        } else if let Some(i) = player {
+            by_player[i].append(d)?;
+        } else {
+            frame_level.append(d)?;
+        }
+    }
+    if let Some(ball_dict) = f.get_item("ball")? {
+        ball_dict.set_item("raw_attributes", ball)?;
+    }
+    if let Some(players) = f.get_item("players")? {
+        for (i, player) in frame.players.iter().enumerate() {
+            // Fixed-slot lists are indexed by slot, others follow frame order.
+            let index = if fixed_player_slots { player.slot } else { i };
+            players
+                .get_item(index)?
+                .set_item("raw_attributes", by_player[i])?;
+        }
+    }
+    f.set_item("raw_attributes", frame_level)
+}
+
+/// Default network frames between `FrameSeeker` checkpoints (10 s at 30 Hz).
+const SEEK_CHECKPOINT_INTERVAL: usize = 300;
+
+/// Random access to one replay's frames for scrubbing. Construction decodes the replay and
+/// runs one pass, cloning the full network state (actors, teams, boost, positions, pads)
+/// every `checkpoint_interval` frames; `frame(index)` then restores the nearest earlier
+/// checkpoint and replays only the gap, or carries on from the previous call when seeking
+/// forward. Memory grows with the number of checkpoints.
+///
+/// Indexes count every network frame, so they match iter_frames with default options, and
+/// each frame dict is the one iter_frames would produce with the same
+/// `lag_spike_threshold`.
+#[pyclass]
+struct FrameSeeker {
+    frames: Vec<Frame>,
+    interval: usize,
+    lag_spike_threshold: f32,
+    /// `checkpoints[k]`: the state before frame `k * interval`.
+    checkpoints: Vec<NetworkState>,
+    /// The most recently produced frame's index and the state after it.
+    cursor: Option<(usize, NetworkState)>,
+}
+
+#[pymethods]
+impl FrameSeeker {
+    #[new]
+    #[pyo3(signature = (
+        path,
+        checkpoint_interval = SEEK_CHECKPOINT_INTERVAL,
+        lag_spike_threshold = DEFAULT_LAG_SPIKE_THRESHOLD_S,
+    ))]
+    fn new(path: &str, checkpoint_interval: usize, lag_spike_threshold: f32) -> PyResult<Self> {
+        if checkpoint_interval == 0 {
+            return Err(PyValueError::new_err(
+                "checkpoint_interval must be positive",
+            ));
+        }
+        let lag_spike_threshold = crate::lag_spike_threshold(lag_spike_threshold)?;
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let frames = replay
+            .network_frames
+            .map(|net| net.frames)
+            .unwrap_or_default();
+        let mut checkpoints = Vec::with_capacity(frames.len() / checkpoint_interval + 1);
+        for (i, nf) in frames.iter().enumerate() {
+            if i % checkpoint_interval == 0 {
+                checkpoints.push(state.clone());
+            }
+            state.process_frame(nf);
+        }
+        Ok(FrameSeeker {
+            frames,
+            interval: checkpoint_interval,
+            lag_spike_threshold,
+            checkpoints,
+            cursor: None,
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[getter]
+    fn checkpoint_count(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// The frame dict at network frame `index`; IndexError past the end.
+    fn frame(&mut self, py: Python<'_>, index: usize) -> PyResult<PyObject> {
+        let Some(target) = self.frames.get(index) else {
+            return Err(PyIndexError::new_err(format!(
+                "frame index {} out of range ({} frames)",
+                index,
+                self.frames.len()
+            )));
+        };
+        let checkpoint = index / self.interval;
+        let (mut next, mut state) = match self.cursor.take() {
+            Some((at, state)) if at < index && at >= checkpoint * self.interval => (at + 1, state),
+            _ => (
+                checkpoint * self.interval,
+                self.checkpoints[checkpoint].clone(),
+            ),
+        };
+        while next < index {
+            state.process_frame(&self.frames[next]);
+            next += 1;
+        }
+        let frame = state.process_frame(target);
+        self.cursor = Some((index, state));
+        let lag_spike = index > 0
+            && is_lag_spike(
+                self.frames[index - 1].time,
+                target.time,
+                self.lag_spike_threshold,
+            );
+        frame_snapshot_to_py(py, &frame, None, &Physics::SOCCAR, lag_spike)
+    }
+}
+
+/// Frames one at a time: `for frame in FrameStream(path): ...`. Construction decodes the
+/// replay; each step then processes one network frame and builds its dict, so no frame
+/// list is held. Frames are the ones iter_frames produces with default options and the same
+/// `lag_spike_threshold`.
+///
+/// With `reuse_dicts`, every step mutates and yields the *same* frame dict, whose nested
+/// ball, players and `_parser_meta` containers (and each player slot's dict) are refilled
+/// in place rather than rebuilt, cutting per-frame allocation for consumers that extract
+/// values straight away. The yielded dicts are borrowed: a frame kept past the next step
+/// (appended to a list, compared with the previous frame, ...) silently changes under you
+/// to the later frame, and so do any players or ball dicts taken out of it. Copy what you
+/// need to retain (`copy.deepcopy(frame)`, or the values themselves). Default is fresh
+/// dicts every frame.
+#[pyclass]
+struct FrameStream {
+    frames: std::vec::IntoIter<Frame>,
+    state: NetworkState,
+    lag: LagSpikeDetector,
+    /// With `reuse_dicts`: the frame dict yielded every step, and the player dict per slot.
+    reused: Option<(Py<PyDict>, Vec<Py<PyDict>>)>,
+}
+
+#[pymethods]
+impl FrameStream {
+    #[new]
+    #[pyo3(signature = (
+        path,
+        reuse_dicts = false,
+        lag_spike_threshold = DEFAULT_LAG_SPIKE_THRESHOLD_S,
+    ))]
+    fn new(
+        py: Python<'_>,
+        path: &str,
+        reuse_dicts: bool,
+        lag_spike_threshold: f32,
+    ) -> PyResult<Self> {
+        let lag = LagSpikeDetector::new(crate::lag_spike_threshold(lag_spike_threshold)?);
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let state = NetworkState::new(&replay);
+        let frames = replay
+            .network_frames
+            .map(|net| net.frames)
+            .unwrap_or_default();
+        Ok(FrameStream {
+            frames: frames.into_iter(),
+            state,
+            lag,
+            reused: reuse_dicts.then(|| (PyDict::new(py).into(), Vec::new())),
+        })
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let Some(nf) = self.frames.next() else {
+            return Ok(None);
+        };
+        let frame = self.state.process_frame(&nf);
+        let lag_spike = self.lag.observe(frame.timestamp);
+        let Some((f, player_dicts)) = self.reused.as_mut() else {
+            return frame_snapshot_to_py(py, &frame, None, &Physics::SOCCAR, lag_spike).map(Some);
+        };
+        let f = f.as_ref(py);
+        fill_frame_dict(
+            py,
+            f,
+            &frame,
+            None,
+            &Physics::SOCCAR,
+            lag_spike,
+            Some(player_dicts),
+        )?;
+        Ok(Some(f.into_py(py)))
+    }
+}
+
+/// One tuple per row of `columns`, in `FLAT_FRAME_FIELDS` then per-slot
+/// `FLAT_PLAYER_FIELDS` order.
+fn append_flat_frames(py: Python<'_>, out: &PyList, columns: &FrameColumns) -> PyResult<()> {
+    let width = FLAT_FRAME_FIELDS.len() + FLAT_PLAYER_FIELDS.len() * columns.players.len();
+    for row in 0..columns.len() {
+        let mut values: Vec<PyObject> = Vec::with_capacity(width);
+        values.push(columns.timestamp[row].into_py(py));
+        values.push(columns.is_live[row].into_py(py));
+        values.push(columns.lag_spike[row].into_py(py));
+        values.push(columns.ball_x[row].into_py(py));
+        values.push(columns.ball_y[row].into_py(py));
+        values.push(columns.ball_z[row].into_py(py));
+        values.push(columns.ball_vx[row].into_py(py));
+        values.push(columns.ball_vy[row].into_py(py));
+        values.push(columns.ball_vz[row].into_py(py));
+        for player in &columns.players {
+            values.push(player.x[row].into_py(py));
+            values.push(player.y[row].into_py(py));
+            values.push(player.z[row].into_py(py));
+            values.push(player.vx[row].into_py(py));
+            values.push(player.vy[row].into_py(py));
+            values.push(player.vz[row].into_py(py));
+            values.push(player.boost[row].into_py(py));
+            values.push(player.is_demolished[row].into_py(py));
+        }
+        out.append(PyTuple::new(py, values))?;
+    }
+    Ok(())
+}
+
+/// Index of the network frame boxcars failed on. boxcars keeps the frames decoded before
+/// the failure private, so the index is read from the error context's display text.
+fn failed_frame_index(err: &ParseError) -> Option<usize> {
+    let ParseError::NetworkError(network) = err else {
+        return None;
+    };
+    let NetworkError::FrameError(_, context) = network.as_ref() else {
+        return None;
+    };
+    context
+        .to_string()
+        .strip_prefix("Current frame: ")?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Header properties of a replay whose body may be truncated or corrupt.
+fn lenient_header_properties(data: &[u8]) -> Result<Vec<(String, HeaderProp)>, String> {
+    let header_only = |bytes: &[u8]| {
+        ParserBuilder::new(bytes)
+            .never_check_crc()
+            .never_parse_network_data()
+            .parse()
+            .map(|replay| replay.properties)
+    };
+    header_only(data).or_else(|e| {
+        header_only_replay(data)
+            .and_then(|bytes| header_only(&bytes).ok())
+            .ok_or_else(|| e.to_string())
+    })
+}
+
+/// Like iter_frames, but never raises on decode errors: returns `{schema_version, header,
+/// frames, truncated_at_frame, error}`. When boxcars fails on a network frame, the frames
+/// before it are salvaged by re-decoding with the header frame count lowered to the failing
+/// index. A file truncated on disk loses the object / name tables stored after the network
+/// stream, so no frames can be decoded and only the header is returned
+/// (`truncated_at_frame` 0). `truncated_at_frame` and `error` are None for clean replays.
+/// Read errors still raise. `lag_spike_threshold` is as in iter_frames.
+#[pyfunction]
+#[pyo3(signature = (path, lag_spike_threshold = DEFAULT_LAG_SPIKE_THRESHOLD_S))]
+fn iter_frames_lenient(path: &str, lag_spike_threshold: f32) -> PyResult<PyObject> {
+    let lag_spike_threshold = crate::lag_spike_threshold(lag_spike_threshold)?;
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let decode = |bytes: &[u8]| {
+            ParserBuilder::new(bytes)
+                .never_check_crc()
+                .must_parse_network_data()
+                .parse()
+        };
+        let (replay, truncated_at_frame, error) = match decode(&data) {
+            Ok(replay) => (Some(replay), None, None),
+            Err(e) => {
+                let salvaged = failed_frame_index(&e).and_then(|frame| {
+                    let mut patched = data.clone();
+                    let frames = i32::try_from(frame).ok()?;
+                    if !patch_num_frames(&mut patched, frames) {
+                        return None;
+                    }
+                    decode(&patched).ok().map(|replay| (replay, frame))
+                });
+                match salvaged {
+                    Some((replay, frame)) => (Some(replay), Some(frame), Some(e.to_string())),
+                    None => (None, Some(0), Some(e.to_string())),
+                }
+            }
+        };
+
+        let large_replay = match &replay {
+            Some(replay) => large_replay_warning(py, path, network_frame_count(replay))?,
+            None => None,
+        };
+        let frames_out = PyList::empty(py);
+        let mut inferred_players = None;
+        let mut network_slots = None;
+        let mut lag_spikes = None;
+        if let Some(replay) = &replay {
+            let mut state = NetworkState::new(replay);
+            let mut lag = LagSpikeDetector::new(lag_spike_threshold);
+            if let Some(net) = &replay.network_frames {
+                for nf in &net.frames {
+                    let frame = state.process_frame(nf);
+                    let lag_spike = lag.observe(frame.timestamp);
+                    frames_out.append(frame_snapshot_to_py(
+                        py,
+                        &frame,
+                        None,
+                        &Physics::SOCCAR,
+                        lag_spike,
+                    )?)?;
+                }
+            }
+            inferred_players = inferred_players_warning(&state);
+            network_slots = network_slots_warning(&state);
+            lag_spikes = lag_spikes_warning(&lag);
+        }
+        let properties = lenient_header_properties(&data);
+        let header = header_to_py(
+            py,
+            &data,
+            properties.as_deref().map_err(Clone::clone),
+            false,
+        )?;
+        if let Some(warning) = large_replay {
+            append_quality_warning(header, warning)?;
+        }
+        if let Some(warning) = inferred_players {
+            append_quality_warning(header, &warning)?;
+        }
+        if let Some(warning) = network_slots {
+            append_quality_warning(header, &warning)?;
+        }
+        if let Some(warning) = lag_spikes {
+            append_quality_warning(header, &warning)?;
+        }
+
+        let out = PyDict::new(py);
+        out.set_item("schema_version", FRAME_SCHEMA_VERSION)?;
+        out.set_item("header", header)?;
+        out.set_item("frames", frames_out)?;
+        out.set_item("truncated_at_frame", truncated_at_frame)?;
+        out.set_item("error", error)?;
+        Ok(out.to_object(py))
+    })
+}
+
+/// Frames as parallel columns for DataFrame construction: `timestamp`, `is_live`,
+/// `lag_spike`, `ball_x` .. `ball_vz`, and
+/// `player_{slot}_{x,y,z,vx,vy,vz,boost,is_demolished}` for every header slot (None where
+/// that slot has no car). `lag_spike_threshold` is as in iter_frames.
+#[pyfunction]
+#[pyo3(signature = (path, lag_spike_threshold = DEFAULT_LAG_SPIKE_THRESHOLD_S))]
+fn frames_columnar(path: &str, lag_spike_threshold: f32) -> PyResult<PyObject> {
+    let mut lag = LagSpikeDetector::new(crate::lag_spike_threshold(lag_spike_threshold)?);
+    Python::with_gil(|py| {
+        let mut timer = CallTimer::start("frames_columnar");
+        let data = read_file_bytes(path)?;
+        timer.read_done();
+        let replay = parse_network_replay(&data)?;
+        timer.decode_done();
+        let mut state = NetworkState::new(&replay);
+        let mut columns = FrameColumns::new(header_players(&replay.properties).len());
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                let lag_spike = lag.observe(frame.timestamp);
+                columns.push(&frame, lag_spike);
+            }
+        }
+
+        let out = PyDict::new(py);
+        out.set_item("schema_version", FRAME_SCHEMA_VERSION)?;
+        out.set_item("timestamp", &columns.timestamp)?;
+        out.set_item("is_live", &columns.is_live)?;
+        out.set_item("lag_spike", &columns.lag_spike)?;
+        out.set_item("ball_x", &columns.ball_x)?;
+        out.set_item("ball_y", &columns.ball_y)?;
+        out.set_item("ball_z", &columns.ball_z)?;
+        out.set_item("ball_vx", &columns.ball_vx)?;
+        out.set_item("ball_vy", &columns.ball_vy)?;
+        out.set_item("ball_vz", &columns.ball_vz)?;
+        for (slot, player) in columns.players.iter().enumerate() {
+            let key = |field: &str| format!("player_{}_{}", slot, field);
+            out.set_item(key("x"), &player.x)?;
+            out.set_item(key("y"), &player.y)?;
+            out.set_item(key("z"), &player.z)?;
+            out.set_item(key("vx"), &player.vx)?;
+            out.set_item(key("vy"), &player.vy)?;
+            out.set_item(key("vz"), &player.vz)?;
+            out.set_item(key("boost"), &player.boost)?;
+            out.set_item(key("is_demolished"), &player.is_demolished)?;
+        }
+        timer.finish();
+        Ok(out.to_object(py))
+    })
+}
+
+/// One player's trajectory as parallel columns: `timestamp`, `x`, `y`, `z`, `speed` and
+/// `boost`, over the frames where that player has a live car. `player` is a slot index,
+/// "player_N", or a roster online id / name. Raises ValueError if the player never has a car.
+#[pyfunction]
+fn player_trajectory(path: &str, player: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let py = player.py();
+    let mut timer = CallTimer::start("player_trajectory");
+    let data = read_file_bytes(path)?;
+    timer.read_done();
+    let replay = parse_network_replay(&data)?;
+    timer.decode_done();
+    let slot = resolve_player_slot(&replay.properties, player)?;
+    let mut state = NetworkState::new(&replay);
+    let mut trajectory = PlayerTrajectory::new(slot);
+    if let Some(net) = &replay.network_frames {
+        for nf in &net.frames {
+            trajectory.push(&state.process_frame(nf));
+        }
+    }
+    if trajectory.len() == 0 {
+        return Err(PyValueError::new_err(format!(
+            "Player slot {} has no car in this replay",
+            slot
+        )));
+    }
+
+    let out = PyDict::new(py);
+    out.set_item("player_id", format!("player_{}", slot))?;
+    out.set_item("timestamp", &trajectory.timestamp)?;
+    out.set_item("x", &trajectory.x)?;
+    out.set_item("y", &trajectory.y)?;
+    out.set_item("z", &trajectory.z)?;
+    out.set_item("speed", &trajectory.speed)?;
+    out.set_item("boost", &trajectory.boost)?;
+    timer.finish();
+    Ok(out.to_object(py))
+}
+
+/// Turn per-call phase timing on or off (off by default). Turning it off clears the last
+/// timings.
+#[pyfunction]
+fn set_parse_timing(enabled: bool) {
+    timing::set_enabled(enabled);
+}
+
+/// Phase timings of the most recent iter_frames / parse_all / frames_columnar /
+/// player_trajectory call while timing was enabled: `function`, `read_seconds`,
+/// `decode_seconds` (boxcars), `frames_seconds` (frame processing and Python object
+/// construction) and `total_seconds`. None when timing is off or nothing has run yet.
+#[pyfunction]
+fn last_parse_timings(py: Python<'_>) -> PyResult<PyObject> {
+    let Some(timings) = timing::last() else {
+        return Ok(py.None());
+    };
+    let d = PyDict::new(py);
+    d.set_item("function", timings.function)?;
+    d.set_item("read_seconds", timings.read_s)?;
+    d.set_item("decode_seconds", timings.decode_s)?;
+    d.set_item("frames_seconds", timings.frames_s)?;
+    d.set_item("total_seconds", timings.total_s())?;
+    Ok(d.to_object(py))
+}
+
+static SHOT_QUALITY: RwLock<ShotQualityCoefficients> =
+    RwLock::new(ShotQualityCoefficients::DEFAULT);
+
+fn shot_quality() -> ShotQualityCoefficients {
+    SHOT_QUALITY
+        .read()
+        .map_or(ShotQualityCoefficients::DEFAULT, |c| *c)
+}
+
+/// Current shot quality coefficients (`intercept`, `speed`, `distance`, `angle`).
+#[pyfunction]
+fn shot_quality_coefficients(py: Python<'_>) -> PyResult<PyObject> {
+    let coefficients = shot_quality();
+    let d = PyDict::new(py);
+    d.set_item("intercept", coefficients.intercept)?;
+    d.set_item("speed", coefficients.speed)?;
+    d.set_item("distance", coefficients.distance)?;
+    d.set_item("angle", coefficients.angle)?;
+    Ok(d.to_object(py))
+}
+
+/// Recalibrate the shot quality heuristic used by goal_events and iter_shots. Calling with
+/// no arguments restores the defaults. Speed is per 1000 uu/s, distance per 1000 uu, angle
+/// per radian.
+#[pyfunction]
+#[pyo3(signature = (
+    intercept = ShotQualityCoefficients::DEFAULT.intercept,
+    speed = ShotQualityCoefficients::DEFAULT.speed,
+    distance = ShotQualityCoefficients::DEFAULT.distance,
+    angle = ShotQualityCoefficients::DEFAULT.angle,
+))]
+fn set_shot_quality_coefficients(
+    intercept: f32,
+    speed: f32,
+    distance: f32,
+    angle: f32,
+) -> PyResult<()> {
+    if ![intercept, speed, distance, angle]
+        .iter()
+        .all(|c| c.is_finite())
+    {
+        return Err(PyValueError::new_err(
+            "shot quality coefficients must be finite",
+        ));
+    }
+    let coefficients = ShotQualityCoefficients {
+        intercept,
+        speed,
+        distance,
+        angle,
+    };
+    match SHOT_QUALITY.write() {
+        Ok(mut current) => *current = coefficients,
+        Err(poisoned) => *poisoned.into_inner() = coefficients,
+    }
+    Ok(())
+}
+
+fn goal_event_to_py(
+    py: Python<'_>,
+    goal: &GoalEvent,
+    coefficients: &ShotQualityCoefficients,
+) -> PyResult<PyObject> {
+    let slot_id = |slot: Option<usize>| slot.map(|slot| format!("player_{}", slot));
+    let d = PyDict::new(py);
+    d.set_item("timestamp", goal.timestamp as f64)?;
+    d.set_item("scoring_team", goal.scoring_team)?;
+    d.set_item("scorer_player_id", slot_id(goal.scorer_slot))?;
+    d.set_item("touch_time", goal.touch_time.map(|t| t as f64))?;
+    d.set_item("goal_type", goal.goal_type.as_str())?;
+    d.set_item("shot_speed", goal.shot_speed as f64)?;
+    d.set_item("deflected", goal.deflected_by_slot.is_some())?;
+    d.set_item("deflected_by_player_id", slot_id(goal.deflected_by_slot))?;
+    let quality = goal.shot.map(|shot| coefficients.score(&shot) as f64);
+    d.set_item("shot_quality", quality)?;
+    Ok(d.to_object(py))
+}
+
+fn touch_detector(touch_distance_uu: f32, min_velocity_delta: f32) -> PyResult<TouchDetector> {
+    let positive = |v: f32| v.is_finite() && v > 0.0;
+    if !positive(touch_distance_uu) || !positive(min_velocity_delta) {
+        return Err(PyValueError::new_err(
+            "touch_distance_uu and min_velocity_delta must be positive",
+        ));
+    }
+    Ok(TouchDetector::new(touch_distance_uu, min_velocity_delta))
+}
+
+fn ball_physics(gravity: f32, ball_radius: f32) -> PyResult<Physics> {
+    let positive = |v: f32| v.is_finite() && v > 0.0;
+    if !positive(gravity) || !positive(ball_radius) {
+        return Err(PyValueError::new_err(
+            "gravity and ball_radius must be positive",
+        ));
+    }
+    Ok(Physics {
+        gravity,
+        ball_radius,
+    })
+}
+
+fn lag_spike_threshold(seconds: f32) -> PyResult<f32> {
+    if !(seconds.is_finite() && seconds > 0.0) {
+        return Err(PyValueError::new_err(
+            "lag_spike_threshold must be positive",
+        ));
+    }
+    Ok(seconds)
+}
+
+/// Header and frames from a single network parse. Match-level aggregates computed
+/// during the frame pass are added to the header section; `goal_events` lists detected
+/// goals with scorer, goal type, shot speed and shot quality (None without a scoring touch).
+///
+/// Each header player gains `car_body_id`, the body product id from their replicated
+/// client loadout, and `hitbox` ("octane" | "dominus" | "plank" | "breakout" | "hybrid" |
+/// "merc"); both None when no loadout replicated, `hitbox` also for unknown bodies.
+///
+/// Each header player also gains route efficiency: `distance_uu` (live distance travelled,
+/// excluding respawns and corrections faster than `stats::ROUTE_MAX_SPEED_UU_S`),
+/// `big_pads` / `small_pads` (collected pad events), `boost_pickups` (live rises in the
+/// replicated boost amount) and `pads_per_100uu`, boost pickups per 100 uu travelled (None
+/// under `stats::ROUTE_MIN_DISTANCE_UU`, 1000 uu). Pickups rather than pad events are used
+/// because pad events can repeat or land on the wrong car.
+///
+/// Boost management: `boost_use_rate` is live boost consumed (0-100 units) per estimated
+/// second of boosting, None if the player never boosted; boost replicates in steps, so
+/// each drop is credited with the time since the previous change, capped at
+/// `stats::BOOST_USE_MAX_GAP_S` (0.5 s). `empty_supersonic_events` counts the runs of live
+/// frames where the player was supersonic holding `stats::EMPTY_SUPERSONIC_MAX_BOOST` (10)
+/// boost or less.
+///
+/// Touch detection thresholds:
+///   touch_distance_uu  — max car-to-ball centre distance in uu (default 300.0)
+///   min_velocity_delta — min frame-to-frame ball velocity change in uu/s (default 100.0)
+///
+/// Each `team_stats` entry has network-derived `shots` (as in iter_shots: at most one per
+/// player's run of touches) and `saves` (defensive touches clearing a ball due to cross the
+/// goal line within `shots::SAVE_HORIZON_S`, 1.5 s), next to `header_shots` and
+/// `header_saves`, the team's box score sums (None when the header has no such stat) for
+/// comparison.
+///
+/// With `check_crc`, a CRC mismatch raises ReplayFormatError before any frame is decoded.
+///
+/// Ball physics, as in iter_frames; `ball_radius` also moves the goal line for goal events
+/// and widens the goal mouth for shots and saves:
+///   gravity     — downward acceleration in uu/s² (default 650.0)
+///   ball_radius — ball radius in uu (default 92.75)
+///
+/// `lag_spike_threshold` is as in iter_frames.
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    touch_distance_uu = TOUCH_DISTANCE_UU,
+    min_velocity_delta = MIN_VELOCITY_DELTA_UU_S,
+    check_crc = false,
+    gravity = GRAVITY_UU_S2,
+    ball_radius = BALL_RADIUS,
+    lag_spike_threshold = DEFAULT_LAG_SPIKE_THRESHOLD_S,
+))]
+fn parse_all(
+    path: &str,
+    touch_distance_uu: f32,
+    min_velocity_delta: f32,
+    check_crc: bool,
+    gravity: f32,
+    ball_radius: f32,
+    lag_spike_threshold: f32,
+) -> PyResult<PyObject> {
+    let mut touches = touch_detector(touch_distance_uu, min_velocity_delta)?;
+    let physics = ball_physics(gravity, ball_radius)?;
+    let mut lag = LagSpikeDetector::new(crate::lag_spike_threshold(lag_spike_threshold)?);
+    Python::with_gil(|py| {
+        let mut timer = CallTimer::start("parse_all");
+        let data = read_file_bytes(path)?;
+        timer.read_done();
+        let replay = parse_network_replay_with_crc(&data, check_crc)?;
+        timer.decode_done();
+        let large_replay = large_replay_warning(py, path, network_frame_count(&replay))?;
+        let mut state = NetworkState::new(&replay);
+        let map_name = header_map_name(&replay.properties);
+        let field_thirds = lookup_arena_slug(&map_name).map(|_| ATTACKING_THIRD_Y);
+        let mut stats = MatchStats::new(field_thirds);
+        let mut teams = TeamTracker::new();
+        let mut goals = GoalDetector::with_physics(&physics);
+        let mut shot_detector = ShotDetector::with_physics(&physics);
+        let mut save_detector = SaveDetector::with_physics(&physics);
+        let mut shots = [0u32; 2];
+        let mut saves = [0u32; 2];
+        let coefficients = shot_quality();
+
+        let frames_out = PyList::empty(py);
+        let goal_events = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                teams.process_frame(nf, &replay.objects);
+                let touch = touches.observe(&frame);
+                stats.observe(&frame, touch.as_ref());
+                if let Some(goal) = goals.observe(&frame, touch.as_ref()) {
+                    goal_events.append(goal_event_to_py(py, &goal, &coefficients)?)?;
+                }
+                if let Some(shot) = shot_detector.observe(&frame, touch.as_ref()) {
+                    shots[shot.team.clamp(0, 1) as usize] += 1;
+                }
+                if let Some(save) = save_detector.observe(&frame, touch.as_ref()) {
+                    saves[save.team.clamp(0, 1) as usize] += 1;
+                }
+                let lag_spike = lag.observe(frame.timestamp);
+                frames_out.append(frame_snapshot_to_py(py, &frame, None, &physics, lag_spike)?)?;
+            }
+        }
+
+        let header = header_to_py(py, &data, Ok(replay.properties.as_slice()), false)?;
+        if let Some(warning) = large_replay {
+            append_quality_warning(header, warning)?;
+        }
+        if let Some(warning) = inferred_players_warning(&state) {
+            append_quality_warning(header, &warning)?;
+        }
+        if let Some(warning) = network_slots_warning(&state) {
+            append_quality_warning(header, &warning)?;
+        }
+        if let Some(warning) = lag_spikes_warning(&lag) {
+            append_quality_warning(header, &warning)?;
+        }
+        let team_stats = PyList::empty(py);
+        let pct = stats.possession_pct();
+        let header_shots = header_team_totals(&replay.properties, "Shots");
+        let header_saves = header_team_totals(&replay.properties, "Saves");
+        for (team, totals) in stats.teams.iter().enumerate() {
+            let d = PyDict::new(py);
+            d.set_item("team", team)?;
+            d.set_item("possession_seconds", totals.possession_seconds)?;
+            d.set_item("possession_pct", pct[team])?;
+            let attacking_third = stats
+                .has_field_thirds()
+                .then_some(totals.attacking_third_possession_seconds);
+            d.set_item("attacking_third_possession_seconds", attacking_third)?;
+            d.set_item("avg_boost", totals.avg_boost())?;
+            d.set_item("shots", shots[team])?;
+            d.set_item("saves", saves[team])?;
+            d.set_item("header_shots", header_shots.map(|totals| totals[team]))?;
+            d.set_item("header_saves", header_saves.map(|totals| totals[team]))?;
+            team_stats.append(d)?;
+        }
+        header.set_item("team_stats", team_stats)?;
+        let scores = header_team_scores(&replay.properties);
+        let winner = header_winner(&replay.properties);
+        header.set_item("teams", teams_to_py(py, scores, winner, &teams.teams)?)?;
+        if let Some(players) = header.get_item("players")? {
+            for (slot, player) in players.downcast::<PyList>()?.iter().enumerate() {
+                let totals = stats.player(slot);
+                player.set_item("demos_inflicted", totals.demos_inflicted)?;
+                player.set_item("demos_taken", totals.demos_taken)?;
+                player.set_item("goalside_seconds", totals.goalside_seconds)?;
+                player.set_item("last_man_seconds", totals.last_man_seconds)?;
+                player.set_item(
+                    "supersonic_ground_seconds",
+                    totals.supersonic_ground_seconds,
+                )?;
+                player.set_item("supersonic_air_seconds", totals.supersonic_air_seconds)?;
+                player.set_item("avg_boost", totals.avg_boost())?;
+                player.set_item("touches", totals.touches)?;
+                player.set_item("avg_touch_speed", totals.avg_touch_speed())?;
+                player.set_item("hardest_touch_speed", totals.hardest_touch_speed)?;
+                player.set_item("distance_uu", totals.distance_uu)?;
+                player.set_item("big_pads", totals.big_pads)?;
+                player.set_item("small_pads", totals.small_pads)?;
+                player.set_item("boost_pickups", totals.boost_pickups)?;
+                player.set_item("pads_per_100uu", totals.pads_per_100uu())?;
+                player.set_item("boost_use_rate", totals.boost_use_rate())?;
+                player.set_item("empty_supersonic_events", totals.empty_supersonic_events)?;
+                let body = state.car_body(slot);
+                player.set_item("car_body_id", body)?;
+                let hitbox = body.and_then(hitbox_for_body).map(|hitbox| hitbox.as_str());
+                player.set_item("hitbox", hitbox)?;
+            }
+        }
+
+        let out = PyDict::new(py);
+        out.set_item("schema_version", FRAME_SCHEMA_VERSION)?;
+        out.set_item("header", header)?;
+        out.set_item("frames", frames_out)?;
+        out.set_item("goal_events", goal_events)?;
+        timer.finish();
+        Ok(out.to_object(py))
+    })
+}
+
+/// Shots (see `shots`: the first goalward touch of each run of touches by one player):
+/// `timestamp`, `player_id`, `team`, `shot_speed`, `distance`, `angle`, `shot_quality`, and
+/// `scored` when a goal's scoring touch belongs to the shot's run. Touch thresholds match
+/// parse_all.
+#[pyfunction]
+#[pyo3(signature = (path, touch_distance_uu = TOUCH_DISTANCE_UU, min_velocity_delta = MIN_VELOCITY_DELTA_UU_S))]
+fn iter_shots(path: &str, touch_distance_uu: f32, min_velocity_delta: f32) -> PyResult<PyObject> {
+    let mut touches = touch_detector(touch_distance_uu, min_velocity_delta)?;
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut detector = ShotDetector::new();
+        let mut goals = GoalDetector::new();
+
+        let mut shots: Vec<(Shot, bool)> = Vec::new();
+        // Times a run of touches ended: another player touched the ball or play stopped.
+        let mut run_breaks: Vec<f32> = Vec::new();
+        let mut last_toucher: Option<usize> = None;
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                let touch = touches.observe(&frame);
+                if !frame.is_live && last_toucher.take().is_some() {
+                    run_breaks.push(frame.timestamp);
+                }
+                if let Some(touch) = &touch {
+                    if last_toucher
+                        .replace(touch.slot)
+                        .is_some_and(|slot| slot != touch.slot)
+                    {
+                        run_breaks.push(frame.timestamp);
+                    }
+                }
+                if let Some(shot) = detector.observe(&frame, touch.as_ref()) {
+                    shots.push((shot, false));
+                }
+                let Some(goal) = goals.observe(&frame, touch.as_ref()) else {
+                    continue;
+                };
+                let Some(touch_time) = goal.touch_time else {
+                    continue;
+                };
+                let scoring = shots.iter_mut().rev().find(|(shot, _)| {
+                    Some(shot.slot) == goal.scorer_slot && shot.timestamp <= touch_time
+                });
+                if let Some((shot, scored)) = scoring {
+                    let same_run = !run_breaks
+                        .iter()
+                        .any(|&t| t > shot.timestamp && t <= touch_time);
+                    *scored |= same_run;
+                }
+            }
+        }
+
+        let coefficients = shot_quality();
+        let out = PyList::empty(py);
+        for (shot, scored) in shots {
+            let d = PyDict::new(py);
+            d.set_item("timestamp", shot.timestamp as f64)?;
+            d.set_item("player_id", format!("player_{}", shot.slot))?;
+            d.set_item("team", shot.team)?;
+            d.set_item("shot_speed", shot.features.speed as f64)?;
+            d.set_item("distance", shot.features.distance as f64)?;
+            d.set_item("angle", shot.features.angle as f64)?;
+            d.set_item("shot_quality", coefficients.score(&shot.features) as f64)?;
+            d.set_item("scored", scored)?;
+            out.append(d)?;
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+fn match_event_to_py(
+    py: Python<'_>,
+    event: &MatchEvent,
+    coefficients: &ShotQualityCoefficients,
+) -> PyResult<PyObject> {
+    let slot_id = |slot: Option<usize>| slot.map(|slot| format!("player_{}", slot));
+    let d = PyDict::new(py);
+    d.set_item("timestamp", event.timestamp() as f64)?;
+    match *event {
+        MatchEvent::Goal(ref goal) => {
+            let goal = goal_event_to_py(py, goal, coefficients)?;
+            goal.downcast_bound::<PyDict>(py)?
+                .set_item("type", "goal")?;
+            return Ok(goal);
+        }
+        MatchEvent::Kickoff { .. } => d.set_item("type", "kickoff")?,
+        MatchEvent::Touch { slot, team, .. } => {
+            d.set_item("type", "touch")?;
+            d.set_item("player_id", slot_id(Some(slot)))?;
+            d.set_item("team", team)?;
+        }
+        MatchEvent::Demolition {
+            attacker_slot,
+            victim_slot,
+            ..
+        } => {
+            d.set_item("type", "demolition")?;
+            d.set_item("attacker_player_id", slot_id(attacker_slot))?;
+            d.set_item("victim_player_id", slot_id(victim_slot))?;
+        }
+        MatchEvent::BoostPickup {
+            slot,
+            team,
+            pad_id,
+            is_big,
+            ..
+        } => {
+            d.set_item("type", "boost_pickup")?;
+            d.set_item("player_id", slot_id(slot))?;
+            d.set_item("team", team)?;
+            d.set_item("pad_id", pad_id)?;
+            d.set_item("is_big", is_big)?;
+        }
+    }
+    Ok(d.to_object(py))
+}
+
+/// Run the match-event detectors in one pass over the network frames, handing each event
+/// to `emit` as it is detected (frame order; pad pickups carry their own timestamps, so
+/// this is not strictly time-sorted). Stops at the first error `emit` returns.
+fn detect_match_events<E>(
+    replay: &Replay,
+    mut touches: TouchDetector,
+    mut emit: impl FnMut(MatchEvent) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut state = NetworkState::new(replay);
+    let mut kickoffs = KickoffDetector::new();
+    let mut goals = GoalDetector::new();
+
+    if let Some(net) = &replay.network_frames {
+        for nf in &net.frames {
+            let frame = state.process_frame(nf);
+            let timestamp = frame.timestamp;
+            if kickoffs.observe(&frame) {
+                emit(MatchEvent::Kickoff { timestamp })?;
+            }
+            let touch = touches.observe(&frame);
+            if let Some(touch) = touch {
+                emit(MatchEvent::Touch {
+                    timestamp,
+                    slot: touch.slot,
+                    team: touch.team,
+                })?;
+            }
+            for demo in &frame.demolitions {
+                emit(MatchEvent::Demolition {
+                    timestamp,
+                    attacker_slot: demo.attacker_slot,
+                    victim_slot: demo.victim_slot,
+                })?;
+            }
+            let pickups = frame
+                .pad_events
+                .iter()
+                .filter(|pad| matches!(pad.event.status, PadEventStatus::Collected));
+            for pad in pickups {
+                emit(MatchEvent::BoostPickup {
+                    timestamp: pad.event.timestamp,
+                    slot: pad.player_slot,
+                    team: pad.player_team,
+                    pad_id: pad.event.pad_id,
+                    is_big: pad.event.is_big,
+                })?;
+            }
+            if let Some(goal) = goals.observe(&frame, touch.as_ref()) {
+                emit(MatchEvent::Goal(goal))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Detect the merged, time-sorted match timeline in one pass over the network frames.
+fn collect_match_events(replay: &Replay, touches: TouchDetector) -> Vec<MatchEvent> {
+    let mut events = Vec::new();
+    let Ok(()) = detect_match_events(replay, touches, |event| {
+        events.push(event);
+        Ok::<_, Infallible>(())
+    });
+    sort_timeline(&mut events);
+    events
+}
+
+/// Match timeline without per-frame state: one time-sorted list of `kickoff`, `touch`,
+/// `demolition`, `boost_pickup` and `goal` events, each with `type`, `timestamp` and
+/// type-specific fields (goals carry the goal_events fields). Touch thresholds match
+/// parse_all.
+#[pyfunction]
+#[pyo3(signature = (path, touch_distance_uu = TOUCH_DISTANCE_UU, min_velocity_delta = MIN_VELOCITY_DELTA_UU_S))]
+fn iter_events(path: &str, touch_distance_uu: f32, min_velocity_delta: f32) -> PyResult<PyObject> {
+    let touches = touch_detector(touch_distance_uu, min_velocity_delta)?;
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let events = collect_match_events(&replay, touches);
+
+        let coefficients = shot_quality();
+        let out = PyList::empty(py);
+        for event in &events {
+            out.append(match_event_to_py(py, event, &coefficients)?)?;
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+/// Streaming variant of iter_events: `callback(event)` is called with each event dict as it
+/// is detected during the single network pass, instead of building the timeline list. Events
+/// arrive in detection order (frame by frame) rather than sorted. The decoded replay is
+/// still held in memory, but no event list is. An exception raised by the callback aborts
+/// the pass and propagates. Touch thresholds match parse_all.
+#[pyfunction]
+#[pyo3(signature = (path, callback, touch_distance_uu = TOUCH_DISTANCE_UU, min_velocity_delta = MIN_VELOCITY_DELTA_UU_S))]
+fn stream_events(
+    path: &str,
+    callback: &Bound<'_, PyAny>,
+    touch_distance_uu: f32,
+    min_velocity_delta: f32,
+) -> PyResult<()> {
+    let touches = touch_detector(touch_distance_uu, min_velocity_delta)?;
+    let py = callback.py();
+    let data = read_file_bytes(path)?;
+    let replay = parse_network_replay(&data)?;
+    let coefficients = shot_quality();
+    detect_match_events(&replay, touches, |event| {
+        callback.call1((match_event_to_py(py, &event, &coefficients)?,))?;
+        Ok(())
+    })
+}
+
+/// One row per second of replay time (see `telemetry`), for charts too coarse to need every
+/// frame: `[{second, duration, ball_position, ball_speed_mean, ball_speed_max, players,
+/// touches, goals, demos}]` in time order. `duration` is the frame time credited to the
+/// second, a frame interval that crosses into the next second being split between them;
+/// `ball_position` and `ball_speed_mean` are dt-weighted means over it (None when it is 0),
+/// and `players` maps each player_id on the field to its mean `position` and `boost`. The
+/// counts are the touches, goals and demolitions detected during the second.
+#[pyfunction]
+fn telemetry_per_second(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut touches = TouchDetector::new(TOUCH_DISTANCE_UU, MIN_VELOCITY_DELTA_UU_S);
+        let mut goals = GoalDetector::new();
+        let mut telemetry = TelemetryAggregator::new();
+
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                let touch = touches.observe(&frame);
+                let goal = goals.observe(&frame, touch.as_ref());
+                telemetry.observe(&frame, touch.is_some(), goal.is_some());
+            }
+        }
+        let out = PyList::empty(py);
+        for bucket in telemetry.finish() {
+            let ball = bucket.ball_means();
+            let players = PyDict::new(py);
+            for (slot, position, boost) in bucket.player_means() {
+                let p = PyDict::new(py);
+                p.set_item("position", vec3_to_py(py, position)?)?;
+                p.set_item("boost", boost)?;
+                players.set_item(format!("player_{}", slot), p)?;
+            }
+            let d = PyDict::new(py);
+            d.set_item("second", bucket.second)?;
+            d.set_item("duration", bucket.seconds())?;
+            match ball {
+                Some((position, _)) => d.set_item("ball_position", vec3_to_py(py, position)?)?,
+                None => d.set_item("ball_position", py.None())?,
+            }
+            d.set_item("ball_speed_mean", ball.map(|(_, speed)| speed))?;
+            d.set_item("ball_speed_max", bucket.max_ball_speed)?;
+            d.set_item("players", players)?;
+            d.set_item("touches", bucket.touches)?;
+            d.set_item("goals", bucket.goals)?;
+            d.set_item("demos", bucket.demos)?;
+            out.append(d)?;
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+fn vector_to_py<'py>(py: Python<'py>, v: &Vector3f) -> PyResult<&'py PyDict> {
+    vec3_to_py(py, (v.x, v.y, v.z))
+}
+
+fn actor_to_py(py: Python<'_>, actor: Option<ActorId>) -> PyObject {
+    actor.map(i32::from).into_py(py)
+}
+
+/// Python rendering of one attribute value for `iter_attribute`. Scalars map to Python
+/// scalars, the variants the parser itself reads to dicts, and everything else to its
+/// boxcars debug text.
+fn attribute_value_to_py(py: Python<'_>, attribute: &Attribute) -> PyResult<PyObject> {
+    let d = PyDict::new(py);
+    match attribute {
+        Attribute::Boolean(b) => return Ok(b.into_py(py)),
+        Attribute::Byte(b) => return Ok(b.into_py(py)),
+        Attribute::Enum(e) | Attribute::PlayerHistoryKey(e) => return Ok(e.into_py(py)),
+        Attribute::Float(f) => return Ok(f.into_py(py)),
+        Attribute::Int(i) => return Ok(i.into_py(py)),
+        Attribute::Int64(i) => return Ok(i.into_py(py)),
+        Attribute::QWord(q) => return Ok(q.into_py(py)),
+        Attribute::String(s) => return Ok(s.into_py(py)),
+        Attribute::FlaggedByte(flag, b) => return Ok((*flag, *b).into_py(py)),
+        Attribute::GameMode(a, b) => return Ok((*a, *b).into_py(py)),
+        Attribute::Location(v) => return Ok(vector_to_py(py, v)?.into_py(py)),
+        Attribute::ActiveActor(active) => {
+            d.set_item("active", active.active)?;
+            d.set_item("actor", i32::from(active.actor))?;
+        }
+        Attribute::Pickup(pickup) => {
+            d.set_item("instigator", actor_to_py(py, pickup.instigator))?;
+            d.set_item("picked_up", pickup.picked_up)?;
+        }
+        Attribute::PickupNew(pickup) => {
+            d.set_item("instigator", actor_to_py(py, pickup.instigator))?;
+            d.set_item("picked_up", pickup.picked_up)?;
+        }
+        Attribute::ReplicatedBoost(boost) => {
+            d.set_item("grant_count", boost.grant_count)?;
+            d.set_item("boost_amount", boost.boost_amount)?;
+        }
+        Attribute::TeamPaint(paint) => {
+            d.set_item("team", paint.team)?;
+            d.set_item("primary_color", paint.primary_color)?;
+            d.set_item("accent_color", paint.accent_color)?;
+            d.set_item("primary_finish", paint.primary_finish)?;
+            d.set_item("accent_finish", paint.accent_finish)?;
+        }
+        Attribute::RigidBody(rb) => {
+            let rot = rb.rotation;
+            let q = PyDict::new(py);
+            q.set_item("x", rot.x)?;
+            q.set_item("y", rot.y)?;
+            q.set_item("z", rot.z)?;
+            q.set_item("w", rot.w)?;
+            d.set_item("sleeping", rb.sleeping)?;
+            d.set_item("location", vector_to_py(py, &rb.location)?)?;
+            d.set_item("rotation", q)?;
+            let optional = |v: Option<Vector3f>| -> PyResult<PyObject> {
+                Ok(match v {
+                    Some(v) => vector_to_py(py, &v)?.into_py(py),
+                    None => py.None(),
+                })
+            };
+            d.set_item("linear_velocity", optional(rb.linear_velocity)?)?;
+            d.set_item("angular_velocity", optional(rb.angular_velocity)?)?;
+        }
+        Attribute::Rotation(rot) => {
+            d.set_item("yaw", rot.yaw)?;
+            d.set_item("pitch", rot.pitch)?;
+            d.set_item("roll", rot.roll)?;
+        }
+        Attribute::Demolish(demo) => {
+            d.set_item("attacker", i32::from(demo.attacker))?;
+            d.set_item("victim", i32::from(demo.victim))?;
+            d.set_item("attack_velocity", vector_to_py(py, &demo.attack_velocity)?)?;
+            d.set_item("victim_velocity", vector_to_py(py, &demo.victim_velocity)?)?;
+        }
+        Attribute::StatEvent(stat) => {
+            d.set_item("unknown1", stat.unknown1)?;
+            d.set_item("object_id", stat.object_id)?;
+        }
+        other => return Ok(format!("{:?}", other).into_py(py)),
+    }
+    Ok(d.into_py(py))
+}
+
+/// Every update of one attribute variant, without building frames: a list of
+/// `(timestamp, actor_id, object_name, value)` tuples in stream order. `attribute_name` is
+/// a boxcars variant name such as "ReplicatedBoost" or "RigidBody" (case-sensitive);
+/// unknown names raise ValueError. `object_name` is None for actors whose spawn was not
+/// seen. Values are Python scalars, dicts for the structured variants the parser reads
+/// (ActiveActor, Pickup, PickupNew, ReplicatedBoost, TeamPaint, RigidBody, Rotation,
+/// Demolish, StatEvent, Location) and the debug text otherwise.
+#[pyfunction]
+fn iter_attribute(path: &str, attribute_name: &str) -> PyResult<PyObject> {
+    if !is_attribute_name(attribute_name) {
+        return Err(PyValueError::new_err(format!(
+            "Unknown attribute '{}'",
+            attribute_name
+        )));
+    }
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut actor_object: HashMap<i32, &str> = HashMap::new();
+
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                for deleted in &nf.deleted_actors {
+                    actor_object.remove(&i32::from(*deleted));
+                }
+                for na in &nf.new_actors {
+                    if let Some(name) = replay.objects.get(usize::from(na.object_id)) {
+                        actor_object.insert(na.actor_id.into(), name.as_str());
+                    }
+                }
+                for update in &nf.updated_actors {
+                    if attributes::attribute_name(&update.attribute) != attribute_name {
+                        continue;
+                    }
+                    let actor_id = i32::from(update.actor_id);
+                    out.append((
+                        nf.time,
+                        actor_id,
+                        actor_object.get(&actor_id).copied(),
+                        attribute_value_to_py(py, &update.attribute)?,
+                    ))?;
+                }
+            }
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+/// Ground dribbles (see `dribbles`) with carry heights: `{bin_edges, dribbles, players}`.
+/// `bin_edges` are the carry height histogram edges (uu of ball centre above car centre).
+/// `dribbles` lists `{player_id, timestamp, end, duration, carry_heights}` in end order,
+/// `carry_heights` counting the dribble's frames per bin; `players` maps each player_id
+/// with a dribble to its histogram summed over all of them.
+#[pyfunction]
+fn iter_dribbles(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut dribbles = DribbleDetector::new();
+        let mut totals: BTreeMap<usize, [u32; CARRY_HEIGHT_BINS]> = BTreeMap::new();
+
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                for dribble in dribbles.observe(&state.process_frame(nf)) {
+                    let total = totals.entry(dribble.slot).or_default();
+                    for (sum, count) in total.iter_mut().zip(dribble.carry_heights) {
+                        *sum += count;
+                    }
+                    let d = PyDict::new(py);
+                    d.set_item("player_id", format!("player_{}", dribble.slot))?;
+                    d.set_item("timestamp", dribble.start)?;
+                    d.set_item("end", dribble.end)?;
+                    d.set_item("duration", dribble.end - dribble.start)?;
+                    d.set_item("carry_heights", dribble.carry_heights.to_vec())?;
+                    out.append(d)?;
+                }
+            }
+        }
+        let players = PyDict::new(py);
+        for (slot, total) in totals {
+            players.set_item(format!("player_{}", slot), total.to_vec())?;
+        }
+        let d = PyDict::new(py);
+        d.set_item("bin_edges", CARRY_HEIGHT_BIN_EDGES_UU.to_vec())?;
+        d.set_item("dribbles", out)?;
+        d.set_item("players", players)?;
+        Ok(d.to_object(py))
+    })
+}
+
+/// Dead-ball moments with the ball stalled against a wall or in a corner (see `stalls`):
+/// `start_time`, `end_time`, `duration`, `location` ("side_wall", "back_wall" or "corner")
+/// and `position` (the ball centre when the stall began). Only stalls lasting at least
+/// `min_duration_s` seconds are listed.
+#[pyfunction]
+#[pyo3(signature = (path, min_duration_s = STALL_MIN_DURATION_S))]
+fn iter_ball_stalls(path: &str, min_duration_s: f32) -> PyResult<PyObject> {
+    if !(min_duration_s.is_finite() && min_duration_s >= 0.0) {
+        return Err(PyValueError::new_err("min_duration_s must be non-negative"));
+    }
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut stalls = BallStallDetector::new(min_duration_s);
+
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                if let Some(stall) = stalls.observe(&state.process_frame(nf)) {
+                    let d = PyDict::new(py);
+                    d.set_item("start_time", stall.start)?;
+                    d.set_item("end_time", stall.end)?;
+                    d.set_item("duration", stall.end - stall.start)?;
+                    d.set_item("location", stall.location.as_str())?;
+                    d.set_item("position", vec3_to_py(py, stall.position)?)?;
+                    out.append(d)?;
+                }
+            }
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+/// Car-on-car bumps that are not demolitions (see `bumps`): `timestamp`,
+/// `bumper_player_id`, `bumped_player_id`, `velocity_delta` (the bumped car's velocity
+/// change, uu/s) and `velocity_delta_magnitude`. A mutual bump is listed once per direction.
+#[pyfunction]
+fn iter_bumps(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut bumps = BumpDetector::new();
+
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                for bump in bumps.observe(&state.process_frame(nf)) {
+                    let d = PyDict::new(py);
+                    d.set_item("timestamp", bump.timestamp)?;
+                    d.set_item("bumper_player_id", format!("player_{}", bump.bumper_slot))?;
+                    d.set_item("bumped_player_id", format!("player_{}", bump.bumped_slot))?;
+                    d.set_item("velocity_delta", vec3_to_py(py, bump.velocity_delta)?)?;
+                    d.set_item("velocity_delta_magnitude", magnitude(bump.velocity_delta))?;
+                    out.append(d)?;
+                }
+            }
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+/// How long boost pad events were held back waiting for the pad's definition and position
+/// to resolve: `events`, `delayed_events` (emitted on a later frame than their timestamp),
+/// `max_latency_s` and `mean_latency_s`. Large values mean pad event timing is unreliable.
+#[pyfunction]
+fn pad_emission_latency(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut stats = PadLatencyStats::new();
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                for pad in &state.process_frame(nf).pad_events {
+                    stats.record(&pad.event);
+                }
+            }
+        }
+        let out = PyDict::new(py);
+        out.set_item("events", stats.events)?;
+        out.set_item("delayed_events", stats.delayed_events)?;
+        out.set_item("max_latency_s", stats.max_s)?;
+        out.set_item("mean_latency_s", stats.mean_s())?;
+        Ok(out.to_object(py))
+    })
+}
+
+/// Teammates challenging the same ball (see `commits`): `type` ("double_commit"), `team`,
+/// `player_ids` (the pair, lower slot first), `timestamp`, `end` and `duration` (seconds).
+#[pyfunction]
+fn iter_double_commits(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut commits = DoubleCommitDetector::new();
+
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                for commit in commits.observe(&state.process_frame(nf)) {
+                    let (a, b) = commit.slots;
+                    let d = PyDict::new(py);
+                    d.set_item("type", "double_commit")?;
+                    d.set_item("team", commit.team)?;
+                    d.set_item(
+                        "player_ids",
+                        vec![format!("player_{}", a), format!("player_{}", b)],
+                    )?;
+                    d.set_item("timestamp", commit.start)?;
+                    d.set_item("end", commit.end)?;
+                    d.set_item("duration", commit.end - commit.start)?;
+                    out.append(d)?;
+                }
+            }
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+/// Flip resets (see `resets`): `player_id`, `timestamp`, `ball_height` (ball centre, uu)
+/// and `used` (whether the player dodged before landing).
+#[pyfunction]
+fn iter_flip_resets(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut detector = FlipResetDetector::new();
+
+        let mut resets = Vec::new();
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                resets.extend(detector.observe(&state.process_frame(nf)));
+            }
+        }
+        resets.extend(detector.finish());
+
+        let out = PyList::empty(py);
+        for reset in resets {
+            let d = PyDict::new(py);
+            d.set_item("player_id", format!("player_{}", reset.slot))?;
+            d.set_item("timestamp", reset.timestamp)?;
+            d.set_item("ball_height", reset.ball_height)?;
+            d.set_item("used", reset.used)?;
+            out.append(d)?;
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+/// Near misses on the ball (see `whiffs`): `player_id`, `timestamp` (closest approach),
+/// `closest_distance` (car centre to ball centre, uu) and `approach_speed` (closing speed
+/// on the ball as the car came within range, uu/s).
+#[pyfunction]
+fn iter_whiffs(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut whiffs = WhiffDetector::new();
+
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                for whiff in whiffs.observe(&state.process_frame(nf)) {
+                    let d = PyDict::new(py);
+                    d.set_item("player_id", format!("player_{}", whiff.slot))?;
+                    d.set_item("timestamp", whiff.timestamp)?;
+                    d.set_item("closest_distance", whiff.closest_distance)?;
+                    d.set_item("approach_speed", whiff.approach_speed)?;
+                    out.append(d)?;
+                }
+            }
+        }
+        Ok(out.to_object(py))
+    })
+}
+
+/// Ball strikes on the goal frame (see `woodwork`): `type` ("woodwork"), `timestamp`,
+/// `goal_team` (0 for the blue goal, 1 for orange), `part` ("left_post" | "right_post" |
+/// "crossbar", from the attacker's point of view) and `ball_speed` (uu/s, just before the
+/// hit). Touch thresholds match parse_all. Empty on arenas without a goal table (Hoops,
+/// Dropshot).
+#[pyfunction]
+#[pyo3(signature = (path, touch_distance_uu = TOUCH_DISTANCE_UU, min_velocity_delta = MIN_VELOCITY_DELTA_UU_S))]
+fn iter_woodwork(
+    path: &str,
+    touch_distance_uu: f32,
+    min_velocity_delta: f32,
+) -> PyResult<PyObject> {
+    let mut touches = touch_detector(touch_distance_uu, min_velocity_delta)?;
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let out = PyList::empty(py);
+        let Some(goal_centers) =
+            lookup_arena_slug(&header_map_name(&replay.properties)).and_then(goal_centers_for_slug)
+        else {
+            return Ok(out.to_object(py));
+        };
+        let mut state = NetworkState::new(&replay);
+        let mut woodwork = WoodworkDetector::new(goal_centers);
+
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                let touch = touches.observe(&frame);
+                if let Some(hit) = woodwork.observe(&frame, touch.as_ref()) {
+                    let d = PyDict::new(py);
+                    d.set_item("type", "woodwork")?;
+                    d.set_item("timestamp", hit.timestamp)?;
+                    d.set_item("goal_team", hit.goal_team)?;
+                    d.set_item("part", hit.part.as_str())?;
+                    d.set_item("ball_speed", hit.ball_speed)?;
+                    out.append(d)?;
+                }
+            }
         }
-        header.set_item("quality_warnings", warnings)?;
-
-        Ok(header.to_object(py))
+        Ok(out.to_object(py))
     })
 }
 
+/// When each player slot had a car: one list per slot (index = slot) of `(start_time,
+/// end_time)` intervals, the timestamps of the first and last frame of each unbroken run
+/// of frames with a car actor mapped to the slot, demolished or not. Several intervals
+/// mean the car actor was destroyed in between (on builds that destroy demolished cars,
+/// leaving or reconnecting). Slots that never had a car get an empty list.
 #[pyfunction]
-fn header_property_keys(path: &str) -> PyResult<Vec<String>> {
-    let data = read_file_bytes(path)?;
-    let replay = ParserBuilder::new(&data)
-        .never_parse_network_data()
-        .parse()
-        .map_err(|e| PyValueError::new_err(format!("Failed to parse replay header: {e}")))?;
-    Ok(replay.properties.iter().map(|(k, _)| k.clone()).collect())
+fn player_presence(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        // Per slot: closed intervals, and the start / latest time of the open one.
+        let mut intervals: Vec<Vec<(f32, f32)>> = Vec::new();
+        let mut open: Vec<Option<(f32, f32)>> = Vec::new();
+
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                let slots = state.slot_count().max(open.len());
+                intervals.resize(slots, Vec::new());
+                open.resize(slots, None);
+                for (slot, run) in open.iter_mut().enumerate() {
+                    if frame.players.iter().any(|p| p.slot == slot) {
+                        let start = run.map_or(frame.timestamp, |(start, _)| start);
+                        *run = Some((start, frame.timestamp));
+                    } else if let Some(closed) = run.take() {
+                        intervals[slot].push(closed);
+                    }
+                }
+            }
+        }
+        for (slot, run) in open.into_iter().enumerate() {
+            intervals[slot].extend(run);
+        }
+        Ok(intervals.to_object(py))
+    })
 }
 
+/// Kickoffs (as detected for `iter_events`) with each on-field player's spawn:
+/// `[{timestamp, players: [{player_id, team, position, kickoff_position}], kickoff_outcome}]`.
+/// `kickoff_position` is "diagonal_left" | "diagonal_right" | "offset_left" |
+/// "offset_right" | "back", left / right from the player's own point of view, or None when
+/// the car is not near a canonical spawn or the arena has no spawn table (Hoops, Dropshot).
+/// `kickoff_outcome` is `[blue, orange]`, each "won" | "neutral" | "lost", from the touch and
+/// ball direction following the first contact (see `events::KickoffOutcomeDetector`); None
+/// when the kickoff ended without a contact.
 #[pyfunction]
-fn header_property(path: &str, key: &str) -> PyResult<Option<PyObject>> {
+fn iter_kickoffs(path: &str) -> PyResult<PyObject> {
     Python::with_gil(|py| {
         let data = read_file_bytes(path)?;
-        let replay = ParserBuilder::new(&data)
-            .never_parse_network_data()
-            .parse()
-            .map_err(|e| PyValueError::new_err(format!("Failed to parse replay header: {e}")))?;
-        for (k, v) in replay.properties {
-            if k == key {
-                let value = header_prop_to_py(py, &v)?;
-                return Ok(Some(value));
+        let replay = parse_network_replay(&data)?;
+        let spawns = lookup_arena_slug(&header_map_name(&replay.properties))
+            .and_then(kickoff_spawns_for_slug);
+        let mut state = NetworkState::new(&replay);
+        let mut kickoffs = KickoffDetector::new();
+        let mut touches = TouchDetector::new(TOUCH_DISTANCE_UU, MIN_VELOCITY_DELTA_UU_S);
+        let mut outcomes = KickoffOutcomeDetector::new();
+        let mut pending: Option<&PyDict> = None;
+
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                let kickoff = kickoffs.observe(&frame);
+                let touch = touches.observe(&frame);
+                if let Some(outcome) = outcomes.observe(&frame, kickoff, touch.as_ref()) {
+                    if let Some(d) = pending.take() {
+                        let outcome: Vec<&str> = outcome.iter().map(|o| o.as_str()).collect();
+                        d.set_item("kickoff_outcome", outcome)?;
+                    }
+                }
+                if !kickoff {
+                    continue;
+                }
+                let players = PyList::empty(py);
+                for player in frame.players.iter().filter(|p| !p.is_demolished) {
+                    let kickoff_position = spawns.and_then(|spawns| {
+                        classify_kickoff_spawn(spawns, player.position, player.team)
+                    });
+                    let p = PyDict::new(py);
+                    p.set_item("player_id", player.player_id())?;
+                    p.set_item("team", player.team)?;
+                    p.set_item("position", vec3_to_py(py, player.position)?)?;
+                    p.set_item("kickoff_position", kickoff_position.map(|k| k.as_str()))?;
+                    players.append(p)?;
+                }
+                let d = PyDict::new(py);
+                d.set_item("timestamp", frame.timestamp)?;
+                d.set_item("players", players)?;
+                d.set_item("kickoff_outcome", py.None())?;
+                out.append(d)?;
+                pending = Some(d);
             }
         }
-        Ok(None)
+        Ok(out.to_object(py))
     })
 }
 
-/// Convert quaternion (x, y, z, w) to Euler angles (roll, pitch, yaw) in radians.
-/// Uses the standard aerospace rotation sequence (ZYX).
-fn quat_to_euler(q: (f32, f32, f32, f32)) -> (f64, f64, f64) {
-    let (x, y, z, w) = (q.0 as f64, q.1 as f64, q.2 as f64, q.3 as f64);
-
-    // Roll (x-axis rotation)
-    let sinr_cosp = 2.0 * (w * x + y * z);
-    let cosr_cosp = 1.0 - 2.0 * (x * x + y * y);
-    let roll = sinr_cosp.atan2(cosr_cosp);
-
-    // Pitch (y-axis rotation)
-    let sinp = 2.0 * (w * y - z * x);
-    let pitch = if sinp.abs() >= 1.0 {
-        std::f64::consts::FRAC_PI_2.copysign(sinp)
-    } else {
-        sinp.asin()
-    };
-
-    // Yaw (z-axis rotation)
-    let siny_cosp = 2.0 * (w * z + x * y);
-    let cosy_cosp = 1.0 - 2.0 * (y * y + z * z);
-    let yaw = siny_cosp.atan2(cosy_cosp);
-
-    (roll, pitch, yaw)
+/// A header goal moved onto its network goal crossing; see goals_verified.
+struct VerifiedGoal {
+    timestamp: Option<f32>,
+    frame: Option<usize>,
+    team: Option<i64>,
+    scorer: Option<String>,
+    verified: bool,
 }
 
-fn map_network_error_code(message: &str) -> &'static str {
-    let lower = message.to_ascii_lowercase();
-    if lower.contains("failed to open replay file")
-        || lower.contains("failed to read replay file")
-        || lower.contains("no such file")
-    {
-        "replay_io_error"
-    } else if lower.contains("unknown attributes for object") {
-        "boxcars_unknown_attribute_network_error"
-    } else {
-        "boxcars_network_error"
+/// The header goals matched to network goal crossings, plus the timestamp of the last
+/// live frame (None without live play).
+fn verify_goals(replay: &Replay) -> (Vec<VerifiedGoal>, Option<f32>) {
+    let mut state = NetworkState::new(replay);
+    let mut goals = GoalDetector::new();
+    let mut timestamps: Vec<f32> = Vec::new();
+    let mut goal_frames: Vec<usize> = Vec::new();
+    let mut goal_teams: HashMap<usize, i64> = HashMap::new();
+    let mut last_live = None;
+    if let Some(net) = &replay.network_frames {
+        for (index, nf) in net.frames.iter().enumerate() {
+            let frame = state.process_frame(nf);
+            timestamps.push(frame.timestamp);
+            if frame.is_live {
+                last_live = Some(frame.timestamp);
+            }
+            if let Some(goal) = goals.observe(&frame, None) {
+                goal_frames.push(index);
+                goal_teams.insert(index, goal.scoring_team);
+            }
+        }
     }
+
+    let verified = header_goals(&replay.properties)
+        .into_iter()
+        .map(|goal| {
+            let crossing = goal
+                .frame
+                .and_then(|frame| nearest_goal_frame(&goal_frames, frame));
+            let frame = crossing.or(goal.frame);
+            VerifiedGoal {
+                timestamp: frame.and_then(|f| timestamps.get(f).copied()),
+                frame,
+                team: goal
+                    .team
+                    .or_else(|| crossing.and_then(|f| goal_teams.get(&f).copied())),
+                scorer: goal.player_name,
+                verified: crossing.is_some(),
+            }
+        })
+        .collect();
+    (verified, last_live)
 }
 
+/// Header `Goals` with frame-accurate timing, for clipping: `[{timestamp, frame, team,
+/// scorer, verified}]` in header order. Each header goal frame is replaced by the nearest
+/// network frame where the goal detector sees the ball cross the goal plane, within
+/// `GOAL_VERIFY_WINDOW_FRAMES` (30); `verified` is False when no crossing is that close and
+/// the header frame is kept. `scorer` is the header player name; `team` falls back to the
+/// side of the crossing when the header has none. `timestamp` is None only when the frame
+/// is outside the network stream.
 #[pyfunction]
-fn iter_frames(path: &str) -> PyResult<Py<PyAny>> {
+fn goals_verified(path: &str) -> PyResult<PyObject> {
     Python::with_gil(|py| {
         let data = read_file_bytes(path)?;
-        // Parse with network data enabled
-        let replay = ParserBuilder::new(&data)
-            .must_parse_network_data()
-            .parse()
-            .map_err(|e| PyValueError::new_err(format!("Failed to parse network frames: {e}")))?;
+        let replay = parse_network_replay(&data)?;
+        let (goals, _) = verify_goals(&replay);
 
-        // Extract map name for arena-aware pad snapping
-        let map_name: String = replay
-            .properties
-            .iter()
-            .find(|(k, _)| k == "MapName")
-            .and_then(|(_, v)| v.as_string())
-            .map(|s| s.to_string())
-            .unwrap_or_default();
+        let out = PyList::empty(py);
+        for goal in goals {
+            let d = PyDict::new(py);
+            d.set_item("timestamp", goal.timestamp)?;
+            d.set_item("frame", goal.frame)?;
+            d.set_item("team", goal.team)?;
+            d.set_item("scorer", goal.scorer)?;
+            d.set_item("verified", goal.verified)?;
+            out.append(d)?;
+        }
+        Ok(out.to_object(py))
+    })
+}
 
-        // Header-derived players with teams for mapping
-        let mut header_players: Vec<(String, i64)> = Vec::new();
-        for (k, v) in &replay.properties {
-            if k == "PlayerStats" {
-                if let Some(arr) = v.as_array() {
-                    for entry in arr {
-                        let mut name: Option<String> = None;
-                        let mut team: i64 = 0;
-                        for (kk, vv) in entry {
-                            match (kk.as_str(), vv) {
-                                ("Name", hp) | ("PlayerName", hp) => {
-                                    if let Some(s) = hp.as_string() {
-                                        name = Some(s.to_string());
-                                    }
-                                }
-                                ("Team", hp) | ("PlayerTeam", hp) => {
-                                    if let Some(t) = hp.as_i32() {
-                                        team = t as i64;
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                        if let Some(n) = name {
-                            header_players.push((n, team));
-                        }
+/// Highlight clips around each goals_verified goal: `[{timestamp, frame, team, scorer,
+/// verified, start_time, end_time, frames}]` in header order. `frames` holds the
+/// iter_frames dicts from `pre_seconds` before the goal timestamp to `post_seconds` after
+/// it, with `start_time` / `end_time` the window clamped to the replay's first and last
+/// frames. Goals without a timestamp (outside the network stream) are skipped.
+/// `lag_spike_threshold` is as in iter_frames.
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    pre_seconds = 10.0,
+    post_seconds = 2.0,
+    lag_spike_threshold = DEFAULT_LAG_SPIKE_THRESHOLD_S,
+))]
+fn goal_clips(
+    path: &str,
+    pre_seconds: f32,
+    post_seconds: f32,
+    lag_spike_threshold: f32,
+) -> PyResult<PyObject> {
+    let non_negative = |seconds: f32| seconds.is_finite() && seconds >= 0.0;
+    if !non_negative(pre_seconds) || !non_negative(post_seconds) {
+        return Err(PyValueError::new_err(
+            "pre_seconds and post_seconds must be non-negative",
+        ));
+    }
+    let lag_spike_threshold = crate::lag_spike_threshold(lag_spike_threshold)?;
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let (goals, _) = verify_goals(&replay);
+        let frames = replay
+            .network_frames
+            .as_ref()
+            .map_or(&[][..], |net| &net.frames[..]);
+        let (Some(first), Some(last)) = (frames.first(), frames.last()) else {
+            return Ok(PyList::empty(py).to_object(py));
+        };
+
+        let clips: Vec<(VerifiedGoal, f32, f32, &PyList)> = goals
+            .into_iter()
+            .filter_map(|goal| {
+                let timestamp = goal.timestamp?;
+                let start = (timestamp - pre_seconds).max(first.time);
+                let end = (timestamp + post_seconds).min(last.time);
+                Some((goal, start, end, PyList::empty(py)))
+            })
+            .collect();
+        if let Some(clip_end) = clips.iter().map(|clip| clip.2).reduce(f32::max) {
+            let mut state = NetworkState::new(&replay);
+            let mut lag = LagSpikeDetector::new(lag_spike_threshold);
+            for nf in frames.iter().take_while(|nf| nf.time <= clip_end) {
+                let frame = state.process_frame(nf);
+                let lag_spike = lag.observe(frame.timestamp);
+                let t = frame.timestamp;
+                for (_, start, end, clip_frames) in &clips {
+                    if (*start..=*end).contains(&t) {
+                        clip_frames.append(frame_snapshot_to_py(
+                            py,
+                            &frame,
+                            None,
+                            &Physics::SOCCAR,
+                            lag_spike,
+                        )?)?;
                     }
                 }
             }
         }
 
-        // Build mapping structures we maintain across frames
-        let objects = &replay.objects;
-        let mut actor_object_name: HashMap<i32, String> = HashMap::new();
-        #[derive(Clone, Default)]
-        struct ActorKind {
-            is_ball: bool,
-            is_car: bool,
-        }
-        #[derive(Clone, Copy, Default)]
-        struct ComponentKind {
-            is_jump: bool,
-            is_dodge: bool,
-            is_double_jump: bool,
-        }
-        let mut actor_kind: HashMap<i32, ActorKind> = HashMap::new();
-        let mut component_kind: HashMap<i32, ComponentKind> = HashMap::new();
-        let mut car_team: HashMap<i32, i64> = HashMap::new();
-        let mut car_boost: HashMap<i32, i64> = HashMap::new(); // 0-100
-        let mut car_pos: HashMap<i32, (f32, f32, f32)> = HashMap::new();
-        let mut car_vel: HashMap<i32, (f32, f32, f32)> = HashMap::new();
-        let mut car_rot: HashMap<i32, (f32, f32, f32, f32)> = HashMap::new(); // quaternion (x,y,z,w)
-        let mut car_demo: HashMap<i32, bool> = HashMap::new();
-        let mut component_owner: HashMap<i32, i32> = HashMap::new();
-        let mut pad_registry = PadRegistry::new_with_arena(&map_name);
-        let mut ball_actor: Option<i32> = None;
-        let mut ball_pos: (f32, f32, f32) = (0.0, 0.0, 93.15);
-        let mut ball_vel: (f32, f32, f32) = (0.0, 0.0, 0.0);
-        let mut ball_angvel: (f32, f32, f32) = (0.0, 0.0, 0.0);
-        let mut actor_to_player_index: HashMap<i32, usize> = HashMap::new();
-        let mut next_by_team: HashMap<i64, Vec<usize>> = HashMap::new();
-        let mut fallback_actor_index: HashMap<i32, usize> = HashMap::new();
-        let mut next_fallback_index: usize = 0;
-
-        // Prepare per-team header order indices
-        let mut team_zero: Vec<usize> = Vec::new();
-        let mut team_one: Vec<usize> = Vec::new();
-        for (idx, (_, team)) in header_players.iter().enumerate() {
-            if *team == 0 {
-                team_zero.push(idx);
-            } else {
-                team_one.push(idx);
-            }
-        }
-        next_by_team.insert(0, team_zero);
-        next_by_team.insert(1, team_one);
+        let out = PyList::empty(py);
+        for (goal, start, end, clip_frames) in clips {
+            let d = PyDict::new(py);
+            d.set_item("timestamp", goal.timestamp)?;
+            d.set_item("frame", goal.frame)?;
+            d.set_item("team", goal.team)?;
+            d.set_item("scorer", goal.scorer)?;
+            d.set_item("verified", goal.verified)?;
+            d.set_item("start_time", start)?;
+            d.set_item("end_time", end)?;
+            d.set_item("frames", clip_frames)?;
+            out.append(d)?;
+        }
+        Ok(out.to_object(py))
+    })
+}
 
-        let frames_out = PyList::empty(py);
+/// Running scoreline for momentum charts: `{goals, final_scores, end_time}`. `goals` is
+/// one `(timestamp, team0_score, team1_score)` tuple per goals_verified goal, giving the
+/// score just after it; goals without a team are skipped. `final_scores` is the header
+/// `[Team0Score, Team1Score]` and `end_time` the timestamp of the last live frame (the
+/// final whistle), or of the last goal if later (the game state leaves "Active" on the
+/// frame a match-winning goal crosses); None without either.
+#[pyfunction]
+fn score_timeline(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let (goals, mut end_time) = verify_goals(&replay);
 
-        // Helper: classify actors using object/class names
-        fn classify_object_name_lower(lname: &str) -> ActorKind {
-            let is_ball = lname.contains("ball_ta")
-                || lname.contains("ball_default")
-                || lname.contains("archetypes.ball")
-                || lname.ends_with("ball")
-                || (lname.contains("ball_") && !lname.contains("ballcam"));
-            let is_car = (lname.contains("archetypes.car.car_")
-                || lname.contains("car_default")
-                || lname.contains("car_ta")
-                || lname.contains("vehicle_ta")
-                || lname.contains("default__car_ta")
-                || lname.contains("default__carbody")
-                || lname.contains("tagame.car_")
-                // Additional patterns for modern replay builds
-                || lname.contains("pawntype_ta")
-                || lname.contains("rbactor_ta")
-                || lname.contains("body_ta"))
-                && !lname.contains("carcomponent");
-            ActorKind { is_ball, is_car }
-        }
-
-        fn classify_component_name_lower(lname: &str) -> Option<ComponentKind> {
-            if !lname.contains("carcomponent") {
-                return None;
-            }
-            Some(ComponentKind {
-                is_jump: lname.contains("carcomponent_jump"),
-                is_dodge: lname.contains("carcomponent_dodge"),
-                is_double_jump: lname.contains("carcomponent_doublejump"),
-            })
+        let mut score = [0i64; 2];
+        let out = PyList::empty(py);
+        for goal in goals {
+            let Some(team) = goal.team.filter(|team| (0..=1).contains(team)) else {
+                continue;
+            };
+            score[team as usize] += 1;
+            if let Some(timestamp) = goal.timestamp {
+                end_time = Some(end_time.map_or(timestamp, |end| end.max(timestamp)));
+            }
+            out.append((goal.timestamp, score[0], score[1]))?;
         }
+        let d = PyDict::new(py);
+        d.set_item("goals", out)?;
+        let final_scores = header_team_scores(&replay.properties);
+        d.set_item("final_scores", final_scores.to_vec())?;
+        d.set_item("end_time", end_time)?;
+        Ok(d.to_object(py))
+    })
+}
 
-        if let Some(net) = replay.network_frames {
-            for nf in net.frames {
-                let mut frame_pad_events: Vec<PadEvent> = Vec::new();
-                let mut frame_jumping_actors: HashSet<i32> = HashSet::new();
-                let mut frame_dodging_actors: HashSet<i32> = HashSet::new();
-                let mut frame_double_jumping_actors: HashSet<i32> = HashSet::new();
-                // Prune actors that were deleted before processing updates to avoid stale telemetry
-                for deleted in nf.deleted_actors {
-                    let aid: i32 = deleted.into();
-                    let team_for_return = car_team.get(&aid).copied();
-                    if ball_actor == Some(aid) {
-                        ball_actor = None;
-                        ball_pos = (0.0, 0.0, 93.15);
-                        ball_vel = (0.0, 0.0, 0.0);
-                        ball_angvel = (0.0, 0.0, 0.0);
-                    }
-                    if let Some(idx) = actor_to_player_index.remove(&aid) {
-                        if let Some(team) = team_for_return {
-                            if let Some(queue) = next_by_team.get_mut(&team) {
-                                queue.push(idx);
-                            }
-                        }
-                    }
-                    actor_object_name.remove(&aid);
-                    actor_kind.remove(&aid);
-                    component_kind.remove(&aid);
-                    car_team.remove(&aid);
-                    car_boost.remove(&aid);
-                    car_pos.remove(&aid);
-                    car_vel.remove(&aid);
-                    car_rot.remove(&aid);
-                    car_demo.remove(&aid);
-                    component_owner.retain(|comp, owner| *comp != aid && *owner != aid);
-                    pad_registry.remove_actor(aid);
-                }
-
-                // Update actor_object_name mapping with new actors in this frame
-                for NewActor {
-                    actor_id,
-                    object_id,
-                    ..
-                } in nf.new_actors
-                {
-                    let oid: usize = object_id.into();
-                    let obj_name = objects.get(oid).cloned().unwrap_or_default();
-                    let obj_name_lower = obj_name.to_ascii_lowercase();
-                    let aid: i32 = actor_id.into();
-                    actor_object_name.insert(aid, obj_name.clone());
-                    let kind = classify_object_name_lower(&obj_name_lower);
-                    if kind.is_ball {
-                        ball_actor = Some(aid);
-                        ball_pos = (0.0, 0.0, 93.15);
-                        ball_vel = (0.0, 0.0, 0.0);
-                        ball_angvel = (0.0, 0.0, 0.0);
-                    }
-                    if kind.is_ball || kind.is_car {
-                        actor_kind.insert(aid, kind);
-                    }
-                    if let Some(component) = classify_component_name_lower(&obj_name_lower) {
-                        component_kind.insert(aid, component);
-                    }
-                    pad_registry.track_new_actor(aid, &obj_name);
-                }
+/// Write the `iter_events` timeline to `out_path` as CSV with columns type, timestamp,
+/// player_id, team, victim_player_id, pad_id, is_big, goal_type, shot_speed and
+/// shot_quality (empty where they don't apply), overwriting any existing file. Returns the
+/// number of events written.
+#[pyfunction]
+#[pyo3(signature = (path, out_path, touch_distance_uu = TOUCH_DISTANCE_UU, min_velocity_delta = MIN_VELOCITY_DELTA_UU_S))]
+fn events_to_csv(
+    path: &str,
+    out_path: &str,
+    touch_distance_uu: f32,
+    min_velocity_delta: f32,
+) -> PyResult<usize> {
+    let touches = touch_detector(touch_distance_uu, min_velocity_delta)?;
+    let data = read_file_bytes(path)?;
+    let replay = parse_network_replay(&data)?;
+    let events = collect_match_events(&replay, touches);
 
-                // Process updates
-                for upd in nf.updated_actors {
-                    let aid: i32 = upd.actor_id.into();
-                    match upd.attribute {
-                        Attribute::ActiveActor(active) => {
-                            if let Some(component) = component_kind.get(&aid) {
-                                let owner_id: i32 = active.actor.into();
-                                component_owner.insert(aid, owner_id);
-                                if active.active {
-                                    if component.is_jump {
-                                        frame_jumping_actors.insert(owner_id);
-                                    }
-                                    if component.is_dodge {
-                                        frame_dodging_actors.insert(owner_id);
-                                    }
-                                    if component.is_double_jump {
-                                        frame_double_jumping_actors.insert(owner_id);
-                                    }
-                                }
-                            }
-                        }
-                        // Primary physics carrier observed across builds
-                        Attribute::RigidBody(rb) => {
-                            let loc = rb.location;
-                            let vel = rb.linear_velocity.unwrap_or(Vector3f {
-                                x: 0.0,
-                                y: 0.0,
-                                z: 0.0,
-                            });
-                            let ang = rb.angular_velocity.unwrap_or(Vector3f {
-                                x: 0.0,
-                                y: 0.0,
-                                z: 0.0,
-                            });
-                            // Update ball or car state depending on classification and fallback
-                            let is_ball = Some(aid) == ball_actor
-                                || actor_kind.get(&aid).map(|kind| kind.is_ball).unwrap_or(false);
-                            if is_ball {
-                                ball_actor = Some(aid);
-                                ball_pos = (loc.x, loc.y, loc.z);
-                                ball_vel = (vel.x, vel.y, vel.z);
-                                ball_angvel = (ang.x, ang.y, ang.z);
-                            } else {
-                                car_pos.insert(aid, (loc.x, loc.y, loc.z));
-                                car_vel.insert(aid, (vel.x, vel.y, vel.z));
-                                // Extract quaternion rotation from RigidBody
-                                let rot = rb.rotation;
-                                car_rot.insert(aid, (rot.x, rot.y, rot.z, rot.w));
-                            }
-                            let events = pad_registry.update_position(aid, (loc.x, loc.y, loc.z));
-                            frame_pad_events.extend(events);
-                        }
-                        // Some builds carry these separately
-                        Attribute::Location(loc) => {
-                            if let Some(component) = component_kind.get(&aid) {
-                                let target = component_owner.get(&aid).cloned().unwrap_or(aid);
-                                if component.is_jump {
-                                    frame_jumping_actors.insert(target);
-                                }
-                                if component.is_dodge {
-                                    frame_dodging_actors.insert(target);
-                                }
-                                if component.is_double_jump {
-                                    frame_double_jumping_actors.insert(target);
-                                }
-                            }
-                            if Some(aid) == ball_actor {
-                                ball_pos = (loc.x, loc.y, loc.z);
-                            } else {
-                                car_pos.insert(aid, (loc.x, loc.y, loc.z));
-                            }
-                            let events = pad_registry.update_position(aid, (loc.x, loc.y, loc.z));
-                            frame_pad_events.extend(events);
-                        }
+    let write = || -> std::io::Result<()> {
+        let mut out = BufWriter::new(File::create(out_path)?);
+        write_timeline_csv(&mut out, &events, &shot_quality())?;
+        out.flush()
+    };
+    write().map_err(|e| PyIOError::new_err(format!("Failed to write '{}': {}", out_path, e)))?;
+    Ok(events.len())
+}
 
-                        Attribute::PickupNew(pickup) => {
-                            let mut raw_actor_opt: Option<i32> = None;
-                            let mut resolved_actor: Option<i32> = None;
-                            if let Some(instigator) = pickup.instigator {
-                                let raw_actor: i32 = instigator.into();
-                                raw_actor_opt = Some(raw_actor);
-                                let mut resolved = raw_actor;
-                                let mut guard = 0;
-                                while let Some(owner) = component_owner.get(&resolved) {
-                                    if *owner == resolved {
-                                        break;
-                                    }
-                                    resolved = *owner;
-                                    guard += 1;
-                                    if guard > 8 {
-                                        break;
-                                    }
-                                }
-                                resolved_actor = Some(resolved);
-                            }
+/// Wall → ceiling → drop sequences, with whether the drop ended in a touch by the same
+/// player. Touch thresholds match parse_all. Empty when no such sequence occurs.
+#[pyfunction]
+#[pyo3(signature = (path, touch_distance_uu = TOUCH_DISTANCE_UU, min_velocity_delta = MIN_VELOCITY_DELTA_UU_S))]
+fn iter_ceiling_shots(
+    path: &str,
+    touch_distance_uu: f32,
+    min_velocity_delta: f32,
+) -> PyResult<Py<PyAny>> {
+    let mut touches = touch_detector(touch_distance_uu, min_velocity_delta)?;
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut detector = CeilingShotDetector::new();
 
-                            let events = pad_registry.handle_pickup(
-                                aid,
-                                pickup.picked_up,
-                                nf.time as f32,
-                                raw_actor_opt,
-                                resolved_actor,
-                                resolved_actor.and_then(|actor| car_pos.get(&actor).copied()),
-                            );
-                            frame_pad_events.extend(events);
-                        }
-                        // Team + visual paint data (use team assignment if present)
-                        Attribute::TeamPaint(tp) => {
-                            let t = (tp.team as i64).clamp(0, 1);
-                            let target = component_owner.get(&aid).cloned().unwrap_or(aid);
-                            car_team.insert(target, t);
-                            // Skip only if we positively know this actor is NOT a car.
-                            // Unclassified actors that receive TeamPaint are treated as cars
-                            // (TeamPaint is a car-exclusive attribute in Rocket League).
-                            if actor_kind
-                                .get(&target)
-                                .map(|kind| !kind.is_car)
-                                .unwrap_or(false)
-                            {
-                                continue;
-                            }
-                            // Register as car if not yet classified (TeamPaint implies car)
-                            if !actor_kind.contains_key(&target) {
-                                actor_kind.insert(target, ActorKind { is_ball: false, is_car: true });
-                            }
-                            if !actor_to_player_index.contains_key(&target) {
-                                if let Some(v) = next_by_team.get_mut(&t) {
-                                    if let Some(idx) = v.first().cloned() {
-                                        v.remove(0);
-                                        actor_to_player_index.insert(target, idx);
-                                    }
-                                }
-                            }
-                        }
-                        // Boost value replication (0..=255) → scale to 0..=100
-                        Attribute::ReplicatedBoost(rb) => {
-                            let amt = ((rb.boost_amount as f64) * (100.0 / 255.0)).round() as i64;
-                            let target = component_owner.get(&aid).cloned().unwrap_or(aid);
-                            car_boost.insert(target, amt.clamp(0, 100));
-                        }
-                        // Demolition signals (varies by build)
-                        Attribute::Demolish(_)
-                        | Attribute::DemolishExtended(_)
-                        | Attribute::DemolishFx(_) => {
-                            car_demo.insert(aid, true);
-                        }
-                        // Note: Jump/Dodge/Throttle/Steer/Handbrake attributes are not directly
-                        // exposed by boxcars 0.10.7. These mechanics will be inferred in Python
-                        // from physics state changes and position/velocity derivatives.
-                        _ => {}
-                    }
-                }
+        let mut shots = Vec::new();
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                let frame = state.process_frame(nf);
+                let touch = touches.observe(&frame);
+                shots.extend(detector.observe(&frame, touch.as_ref()));
+            }
+        }
+        shots.extend(detector.finish());
 
-                frame_pad_events.extend(pad_registry.flush_ready_events());
-
-                // Emit frame dict
-                let f = PyDict::new(py);
-                f.set_item("timestamp", nf.time as f64)?;
-                let ball = PyDict::new(py);
-                let bpos = PyDict::new(py);
-                bpos.set_item("x", ball_pos.0)?;
-                bpos.set_item("y", ball_pos.1)?;
-                bpos.set_item("z", ball_pos.2)?;
-                let bvel = PyDict::new(py);
-                bvel.set_item("x", ball_vel.0)?;
-                bvel.set_item("y", ball_vel.1)?;
-                bvel.set_item("z", ball_vel.2)?;
-                ball.set_item("position", bpos)?;
-                ball.set_item("velocity", bvel)?;
-                let ang = PyDict::new(py);
-                ang.set_item("x", ball_angvel.0)?;
-                ang.set_item("y", ball_angvel.1)?;
-                ang.set_item("z", ball_angvel.2)?;
-                ball.set_item("angular_velocity", ang)?;
-                f.set_item("ball", ball)?;
-
-                // Players: union of actors that have position or boost info
-                let mut actors: BTreeSet<i32> = BTreeSet::new();
-                for k in car_pos.keys() {
-                    actors.insert(*k);
-                }
-                for k in car_boost.keys() {
-                    actors.insert(*k);
-                }
-                for k in car_team.keys() {
-                    actors.insert(*k);
-                }
-                if let Some(ball_id) = ball_actor {
-                    actors.remove(&ball_id);
-                }
-                // Filter using classification when available; keep unclassified for fallback
-                actors = actors
-                    .into_iter()
-                    .filter(|aid| actor_kind.get(aid).map(|kind| kind.is_car).unwrap_or(true))
-                    .collect();
-
-                let mut players_map: BTreeMap<usize, PyObject> = BTreeMap::new();
-                let owned_actor_ids: HashSet<i32> = component_owner.values().copied().collect();
-                let mut frame_classification_source = "object_name";
-                for aid in actors {
-                    let mut actor_classification_source = "fallback_unclassified";
-                    if actor_kind.contains_key(&aid) {
-                        actor_classification_source = "object_name";
-                    } else if owned_actor_ids.contains(&aid) {
-                        actor_classification_source = "component_owner_chain";
-                    }
-                    if actor_classification_source == "fallback_unclassified" {
-                        frame_classification_source = "fallback_unclassified";
-                    } else if actor_classification_source == "component_owner_chain"
-                        && frame_classification_source == "object_name"
-                    {
-                        frame_classification_source = "component_owner_chain";
-                    }
+        let out = PyList::empty(py);
+        for shot in shots {
+            let d = PyDict::new(py);
+            d.set_item("player_id", format!("player_{}", shot.slot))?;
+            d.set_item("ceiling_arrival_time", shot.ceiling_arrival_time as f64)?;
+            d.set_item("drop_time", shot.drop_time as f64)?;
+            d.set_item("resulted_in_touch", shot.resulted_in_touch)?;
+            out.append(d)?;
+        }
+        Ok(out.into())
+    })
+}
 
-                    let (x, y, z) = car_pos.get(&aid).cloned().unwrap_or((0.0, 0.0, 17.0));
-                    // Determine team: prefer decoded team_paint else infer by y position sign
-                    let mut team = *car_team.get(&aid).unwrap_or(&-1);
-                    if team < 0 {
-                        team = if y > 0.0 { 1 } else { 0 };
-                    }
-                    // Assign player index if not assigned and team known
-                    if !actor_to_player_index.contains_key(&aid) && team >= 0 {
-                        if let Some(v) = next_by_team.get_mut(&team) {
-                            if let Some(idx) = v.first().cloned() {
-                                v.remove(0);
-                                actor_to_player_index.insert(aid, idx);
-                            } else if header_players.is_empty() {
-                                let fallback = *fallback_actor_index
-                                    .entry(aid)
-                                    .or_insert_with(|| {
-                                        let idx = next_fallback_index;
-                                        next_fallback_index += 1;
-                                        idx
-                                    });
-                                actor_to_player_index.insert(aid, fallback);
-                            }
-                        } else if header_players.is_empty() {
-                            let fallback = *fallback_actor_index.entry(aid).or_insert_with(|| {
-                                let idx = next_fallback_index;
-                                next_fallback_index += 1;
-                                idx
-                            });
-                            actor_to_player_index.insert(aid, fallback);
-                        }
-                    }
-                    if let Some(idx) = actor_to_player_index.get(&aid).cloned() {
-                        let p = PyDict::new(py);
-                        p.set_item("player_id", format!("player_{}", idx))?;
-                        p.set_item("team", team)?;
-                        let ppos = PyDict::new(py);
-                        ppos.set_item("x", x)?;
-                        ppos.set_item("y", y)?;
-                        ppos.set_item("z", z)?;
-                        let v = car_vel.get(&aid).cloned().unwrap_or((0.0, 0.0, 0.0));
-                        let pvel = PyDict::new(py);
-                        pvel.set_item("x", v.0)?;
-                        pvel.set_item("y", v.1)?;
-                        pvel.set_item("z", v.2)?;
-
-                        // Use true quaternion rotation if available, else fallback to velocity approximation
-                        let prot = PyDict::new(py);
-                        if let Some(q) = car_rot.get(&aid) {
-                            // Convert quaternion to euler angles (roll, pitch, yaw)
-                            let (roll, pitch, yaw) = quat_to_euler(*q);
-                            prot.set_item("pitch", pitch)?;
-                            prot.set_item("yaw", yaw)?;
-                            prot.set_item("roll", roll)?;
-                            // Also include raw quaternion for precision work
-                            let quat = PyDict::new(py);
-                            quat.set_item("x", q.0 as f64)?;
-                            quat.set_item("y", q.1 as f64)?;
-                            quat.set_item("z", q.2 as f64)?;
-                            quat.set_item("w", q.3 as f64)?;
-                            prot.set_item("quaternion", quat)?;
-                        } else {
-                            // Fallback to velocity approximation for older replays
-                            let speed2 = v.0 * v.0 + v.1 * v.1 + v.2 * v.2;
-                            let mut pitch = 0.0f64;
-                            let mut yaw = 0.0f64;
-                            if speed2 > 1e-6 {
-                                let speed = speed2.sqrt();
-                                yaw = (v.1 as f64).atan2(v.0 as f64);
-                                pitch = (v.2 as f64 / speed as f64).asin();
-                            }
-                            prot.set_item("pitch", pitch)?;
-                            prot.set_item("yaw", yaw)?;
-                            prot.set_item("roll", 0.0f64)?;
-                        }
-                        p.set_item("position", ppos)?;
-                        p.set_item("velocity", pvel)?;
-                        p.set_item("rotation", prot)?;
-                        let boost = *car_boost.get(&aid).unwrap_or(&33);
-                        p.set_item("boost_amount", boost)?;
-                        // Calculate speed for supersonic check
-                        let speed = (v.0 * v.0 + v.1 * v.1 + v.2 * v.2).sqrt();
-                        p.set_item("is_supersonic", speed > 2300.0)?;
-                        p.set_item("is_on_ground", z <= 18.0)?;
-                        p.set_item("is_demolished", *car_demo.get(&aid).unwrap_or(&false))?;
-                        if frame_jumping_actors.contains(&aid) {
-                            p.set_item("is_jumping", true)?;
-                        } else {
-                            p.set_item("is_jumping", py.None())?;
-                        }
-                        if frame_dodging_actors.contains(&aid) {
-                            p.set_item("is_dodging", true)?;
-                        } else {
-                            p.set_item("is_dodging", py.None())?;
-                        }
-                        if frame_double_jumping_actors.contains(&aid) {
-                            p.set_item("is_double_jumping", true)?;
-                        } else {
-                            p.set_item("is_double_jumping", py.None())?;
-                        }
+/// Rumble item grant / use events. Empty for non-Rumble replays.
+#[pyfunction]
+fn iter_rumble_items(path: &str) -> PyResult<Py<PyAny>> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let mut state = NetworkState::new(&replay);
+        let mut tracker = RumbleTracker::new();
 
-                        players_map.insert(idx, p.into_py(py));
-                    }
-                }
-                let players = PyList::empty(py);
-                for (_idx, pobj) in players_map.iter() {
-                    players.append(pobj.as_ref(py))?;
-                }
-                f.set_item("players", players)?;
-                let parser_meta = PyDict::new(py);
-                parser_meta.set_item("classification_source", frame_classification_source)?;
-                f.set_item("_parser_meta", parser_meta)?;
-
-                let pad_list = PyList::empty(py);
-                for event in frame_pad_events {
-                    let pad_dict = PyDict::new(py);
-                    pad_dict.set_item("pad_id", event.pad_id as i64)?;
-                    pad_dict.set_item("is_big", event.is_big)?;
-                    pad_dict.set_item("pad_side", event.pad_side)?;
-                    pad_dict.set_item("arena", event.arena)?;
-                    pad_dict.set_item("arena_supported", event.arena_supported)?;
-                    pad_dict.set_item("status", event.status.as_str())?;
-                    pad_dict.set_item("object_name", event.object_name.clone())?;
-                    pad_dict.set_item("raw_state", event.raw_state)?;
-                    pad_dict.set_item("timestamp", event.timestamp as f64)?;
-
-                    let pos_dict = PyDict::new(py);
-                    pos_dict.set_item("x", event.position.0)?;
-                    pos_dict.set_item("y", event.position.1)?;
-                    pos_dict.set_item("z", event.position.2)?;
-                    pad_dict.set_item("position", pos_dict)?;
-
-                    if let Some(raw_actor) = event.instigator_actor_id {
-                        pad_dict.set_item("instigator_actor_id", raw_actor)?;
-                    }
-                    if let Some(resolved) = event.resolved_actor_id {
-                        pad_dict.set_item("actor_id", resolved)?;
-                        if let Some(idx) = actor_to_player_index.get(&resolved) {
-                            pad_dict.set_item("player_index", *idx as i64)?;
-                            pad_dict.set_item("player_id", format!("player_{}", idx))?;
-                        }
-                        if let Some(team) = car_team.get(&resolved) {
-                            pad_dict.set_item("player_team", *team)?;
-                        }
-                    }
-                    if let Some(dist) = event.snap_distance {
-                        pad_dict.set_item("snap_distance", dist as f64)?;
-                    }
-                    if let Some(err) = event.snap_error_uu {
-                        pad_dict.set_item("snap_error_uu", err as f64)?;
+        let out = PyList::empty(py);
+        if let Some(net) = &replay.network_frames {
+            for nf in &net.frames {
+                state.process_frame(nf);
+                for event in tracker.process_frame(nf, &replay.objects) {
+                    let d = PyDict::new(py);
+                    d.set_item("timestamp", event.timestamp as f64)?;
+                    match event.car_actor_id.and_then(|car| state.slot_for_actor(car)) {
+                        Some(slot) => d.set_item("player_id", format!("player_{}", slot))?,
+                        None => d.set_item("player_id", py.None())?,
                     }
-
-                    pad_list.append(pad_dict)?;
+                    d.set_item("item", event.item)?;
+                    d.set_item("event", event.event.as_str())?;
+                    out.append(d)?;
                 }
-                f.set_item("boost_pad_events", pad_list)?;
-
-                frames_out.append(f)?;
             }
         }
+        Ok(out.into())
+    })
+}
+
+/// Chat / ticker messages from the replay's debug log: `[{frame, timestamp, player,
+/// message}]` in file order. `timestamp` is the network time of `frame`, None when the frame
+/// is outside the network stream. Chat is not replicated as a network attribute, so the
+/// debug log boxcars exposes is the only source; the game rarely writes to it and the
+/// BakkesMod metadata usually found there is skipped, so most replays return an empty list.
+#[pyfunction]
+fn chat_messages(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = parse_network_replay(&data)?;
+        let frames = replay
+            .network_frames
+            .as_ref()
+            .map(|net| net.frames.as_slice());
 
-        Ok(frames_out.into())
+        let out = PyList::empty(py);
+        for entry in replay.debug_info.iter().filter(|e| is_chat_entry(&e.user)) {
+            let timestamp = usize::try_from(entry.frame)
+                .ok()
+                .and_then(|frame| frames?.get(frame))
+                .map(|nf| nf.time);
+            let d = PyDict::new(py);
+            d.set_item("frame", entry.frame)?;
+            d.set_item("timestamp", timestamp)?;
+            d.set_item("player", &entry.user)?;
+            d.set_item("message", &entry.text)?;
+            out.append(d)?;
+        }
+        Ok(out.to_object(py))
     })
 }
 
+/// With `check_crc`, a CRC mismatch is reported as error_code "crc_failed" and no fallback
+/// frames are decoded from the corrupt file.
 #[pyfunction]
-fn parse_network_with_diagnostics(path: &str) -> PyResult<PyObject> {
+#[pyo3(signature = (path, check_crc = false))]
+fn parse_network_with_diagnostics(path: &str, check_crc: bool) -> PyResult<PyObject> {
     Python::with_gil(|py| {
         let result = PyDict::new(py);
+        result.set_item("schema_version", FRAME_SCHEMA_VERSION)?;
         let diagnostics = PyDict::new(py);
         diagnostics.set_item("attempted_backends", vec!["boxcars"])?;
 
-        match iter_frames(path) {
-            Ok(frames_any) => {
-                let frames_len = frames_any.as_ref(py).len().unwrap_or(0);
+        let options = FrameOptions {
+            check_crc,
+            ..FrameOptions::default()
+        };
+        match frames_with_options(path, &options) {
+            Ok(out) => {
+                let frames_any = out.as_ref(py).get_item("frames")?;
+                let frames_len = frames_any.len().unwrap_or(0);
                 diagnostics.set_item("status", "ok")?;
                 diagnostics.set_item("error_code", py.None())?;
                 diagnostics.set_item("error_detail", py.None())?;
@@ -1022,6 +3881,15 @@ fn parse_network_with_diagnostics(path: &str) -> PyResult<PyObject> {
                 result.set_item("diagnostics", diagnostics)?;
                 Ok(result.to_object(py))
             }
+            Err(primary_err) if primary_err.is_instance_of::<ReplayFormatError>(py) => {
+                diagnostics.set_item("status", "unavailable")?;
+                diagnostics.set_item("error_code", "crc_failed")?;
+                diagnostics.set_item("error_detail", primary_err.to_string())?;
+                diagnostics.set_item("frames_emitted", 0i64)?;
+                result.set_item("frames", PyList::empty(py))?;
+                result.set_item("diagnostics", diagnostics)?;
+                Ok(result.to_object(py))
+            }
             Err(primary_err) => {
                 let primary_message = primary_err.to_string();
                 let error_code = map_network_error_code(&primary_message);
@@ -1054,15 +3922,19 @@ fn parse_network_with_diagnostics(path: &str) -> PyResult<PyObject> {
                                     ball.set_item("position", bpos)?;
                                     ball.set_item("velocity", bvel)?;
                                     ball.set_item("angular_velocity", bang)?;
+                                    ball.set_item("angular_speed", 0.0f64)?;
+                                    ball.set_item("predicted_landing", py.None())?;
                                     f.set_item("ball", ball)?;
                                     f.set_item("players", PyList::empty(py))?;
                                     f.set_item("boost_pad_events", PyList::empty(py))?;
+                                    f.set_item("lag_spike", false)?;
 
                                     let parser_meta = PyDict::new(py);
                                     parser_meta.set_item(
                                         "classification_source",
                                         "fallback_unclassified",
                                     )?;
+                                    parser_meta.set_item("schema_version", FRAME_SCHEMA_VERSION)?;
                                     f.set_item("_parser_meta", parser_meta)?;
                                     fallback_frames.append(f)?;
                                 }
@@ -1423,6 +4295,162 @@ pub fn debug_first_frames(path: &str, max_frames: usize) -> PyResult<Py<PyAny>>
     })
 }
 
+/// Network data file offsets for seek indexes. boxcars does not expose per-frame
+/// positions, so this returns the keyframe positions: `bit_position` is relative to the start
+/// of the network data, `byte_offset` / `bit_in_byte` locate it in the file.
+#[pyfunction]
+fn frame_offsets(path: &str) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let data = read_file_bytes(path)?;
+        let replay = ParserBuilder::new(&data)
+            .never_parse_network_data()
+            .parse()
+            .map_err(|e| PyValueError::new_err(format!("Failed to parse replay: {e}")))?;
+        let layout = network_data_layout(&data)
+            .ok_or_else(|| PyValueError::new_err("Could not locate network data section"))?;
+
+        let keyframes = PyList::empty(py);
+        for kf in &replay.keyframes {
+            let bit_position = kf.position.max(0) as usize;
+            let d = PyDict::new(py);
+            d.set_item("frame", kf.frame)?;
+            d.set_item("time", kf.time as f64)?;
+            d.set_item("bit_position", bit_position)?;
+            d.set_item("byte_offset", layout.offset + bit_position / 8)?;
+            d.set_item("bit_in_byte", bit_position % 8)?;
+            keyframes.append(d)?;
+        }
+
+        let out = PyDict::new(py);
+        out.set_item("network_data_offset", layout.offset)?;
+        out.set_item("network_data_size", layout.size)?;
+        out.set_item("keyframes", keyframes)?;
+        Ok(out.to_object(py))
+    })
+}
+
+/// Header-only parse of one file; Err carries the read or boxcars error.
+fn parse_header_only(path: &str) -> Result<Replay, String> {
+    let data = read_bytes(path)?;
+    ParserBuilder::new(&data)
+        .never_parse_network_data()
+        .parse()
+        .map_err(|e| e.to_string())
+}
+
+/// Run `f` over `paths` on `workers` threads (default: available cores) with the GIL
+/// released. Results are in input order; None where a worker panicked.
+fn map_paths_parallel<T: Send>(
+    py: Python<'_>,
+    paths: &[String],
+    workers: Option<usize>,
+    f: impl Fn(&str) -> T + Sync,
+) -> Vec<Option<T>> {
+    let workers = workers
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, paths.len().max(1));
+    let next = AtomicUsize::new(0);
+    py.allow_threads(|| {
+        let mut results: Vec<Option<T>> = (0..paths.len()).map(|_| None).collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(path) = paths.get(idx) else {
+                                break;
+                            };
+                            done.push((idx, f(path)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            for handle in handles {
+                for (idx, result) in handle.join().unwrap_or_default() {
+                    results[idx] = Some(result);
+                }
+            }
+        });
+        results
+    })
+}
+
+/// Cheap triage pass: header-parse every path on `workers` threads (default: available
+/// cores) without building header dicts. Returns `{path, ok, error}` per path, in order.
+#[pyfunction]
+#[pyo3(signature = (paths, workers = None))]
+fn scan_integrity(
+    py: Python<'_>,
+    paths: Vec<String>,
+    workers: Option<usize>,
+) -> PyResult<PyObject> {
+    let results = map_paths_parallel(py, &paths, workers, |path| {
+        parse_header_only(path).map(|_| ())
+    });
+
+    let out = PyList::empty(py);
+    for (path, result) in paths.iter().zip(results) {
+        let result = result.unwrap_or_else(|| Err("scan worker panicked".to_string()));
+        let d = PyDict::new(py);
+        d.set_item("path", path)?;
+        d.set_item("ok", result.is_ok())?;
+        d.set_item("error", result.err())?;
+        out.append(d)?;
+    }
+    Ok(out.to_object(py))
+}
+
+/// Header-parse a batch on `workers` threads and count it: `replays`, `failed` (unreadable
+/// or unparseable paths, excluded from the rest), `total_duration_seconds`, and
+/// `by_playlist` / `by_map` / `by_team_size` dicts of replay counts.
+///
+/// `maps` gives each distinct map name `{count, arena_supported}`, the flag False for
+/// arenas without pad / geometry tables (Hoops, Dropshot; see arena_info), and
+/// `unsupported_arena_replays` totals the replays on such maps.
+#[pyfunction]
+#[pyo3(signature = (paths, workers = None))]
+fn library_summary(
+    py: Python<'_>,
+    paths: Vec<String>,
+    workers: Option<usize>,
+) -> PyResult<PyObject> {
+    let headers = map_paths_parallel(py, &paths, workers, |path| {
+        parse_header_only(path).map(|replay| HeaderSummary::from_properties(&replay.properties))
+    });
+    let mut summary = LibrarySummary::default();
+    for header in headers {
+        match header {
+            Some(Ok(header)) => summary.add(&header),
+            _ => summary.failed += 1,
+        }
+    }
+
+    let maps = PyDict::new(py);
+    for (map_name, count) in &summary.by_map {
+        let m = PyDict::new(py);
+        m.set_item("count", count)?;
+        m.set_item("arena_supported", lookup_arena_slug(map_name).is_some())?;
+        maps.set_item(map_name, m)?;
+    }
+
+    let out = PyDict::new(py);
+    out.set_item("replays", summary.replays)?;
+    out.set_item("failed", summary.failed)?;
+    out.set_item("total_duration_seconds", summary.total_duration_s)?;
+    out.set_item(
+        "unsupported_arena_replays",
+        summary.unsupported_arena_replays(),
+    )?;
+    out.set_item("by_playlist", summary.by_playlist.into_py(py))?;
+    out.set_item("by_map", summary.by_map.into_py(py))?;
+    out.set_item("by_team_size", summary.by_team_size.into_py(py))?;
+    out.set_item("maps", maps)?;
+    Ok(out.to_object(py))
+}
+
 #[pyfunction]
 fn net_frame_count(path: &str) -> PyResult<usize> {
     let data = read_file_bytes(path)?;
@@ -1433,14 +4461,109 @@ fn net_frame_count(path: &str) -> PyResult<usize> {
     Ok(replay.network_frames.map(|nf| nf.frames.len()).unwrap_or(0))
 }
 
+/// Why a full network decode of `data` is expected to fail, judged from a header-only
+/// parse; None when it should succeed.
+fn network_data_problem(data: &[u8]) -> Option<String> {
+    let replay = match ParserBuilder::new(data).never_parse_network_data().parse() {
+        Ok(replay) => replay,
+        Err(e) => return Some(format!("header-only parse failed: {e}")),
+    };
+    let num_frames = find_prop(&replay.properties, "NumFrames").and_then(|p| p.as_i32());
+    match num_frames {
+        None => return Some("header has no NumFrames property".to_string()),
+        Some(frames) if frames <= 0 => return Some(format!("header NumFrames is {frames}")),
+        Some(_) => {}
+    }
+    match network_data_layout(data) {
+        None => return Some("network data section is truncated".to_string()),
+        Some(layout) if layout.size == 0 => {
+            return Some("network data section is empty".to_string())
+        }
+        Some(_) => {}
+    }
+    if replay.objects.is_empty() || replay.net_cache.is_empty() {
+        return Some("replay has no object table or class net cache".to_string());
+    }
+    None
+}
+
+/// Cheap pre-check before `iter_frames`: `(ok, reason)`, where `ok` predicts whether the
+/// network data will decode and `reason` says why not (None when ok). Only reads the
+/// header and content tables, so it cannot catch corruption inside the frame stream.
+#[pyfunction]
+fn has_network_data(path: &str) -> PyResult<(bool, Option<String>)> {
+    let data = read_file_bytes(path)?;
+    let problem = network_data_problem(&data);
+    Ok((problem.is_none(), problem))
+}
+
 #[pymodule]
-fn rlreplay_rust(_py: Python, m: &PyModule) -> PyResult<()> {
+fn rlreplay_rust(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("FRAME_SCHEMA_VERSION", FRAME_SCHEMA_VERSION)?;
+    m.add("DEFAULT_MAX_FILE_BYTES", DEFAULT_MAX_FILE_BYTES)?;
+    m.add("DEFAULT_LARGE_REPLAY_FRAMES", DEFAULT_LARGE_REPLAY_FRAMES)?;
+    m.add("PAD_EVENT_COALESCE_WINDOW", PAD_EVENT_COALESCE_WINDOW_S)?;
+    m.add("VELOCITY_SMOOTHING_WINDOW", VELOCITY_SMOOTHING_WINDOW)?;
+    m.add("FLAT_FRAME_FIELDS", FLAT_FRAME_FIELDS.to_vec())?;
+    m.add("FLAT_PLAYER_FIELDS", FLAT_PLAYER_FIELDS.to_vec())?;
+    let pressure_coefficients = PyDict::new(py);
+    for (name, value) in stats::PRESSURE_COEFFICIENTS {
+        pressure_coefficients.set_item(name, value)?;
+    }
+    m.add("PRESSURE_COEFFICIENTS", pressure_coefficients)?;
+    m.add("ReplayFormatError", py.get_type::<ReplayFormatError>())?;
     m.add_function(wrap_pyfunction!(parse_header, m)?)?;
+    m.add_class::<FrameSeeker>()?;
+    m.add_class::<FrameStream>()?;
     m.add_function(wrap_pyfunction!(iter_frames, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_frames_lenient, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_all, m)?)?;
+    m.add_function(wrap_pyfunction!(frames_columnar, m)?)?;
+    m.add_function(wrap_pyfunction!(player_trajectory, m)?)?;
+    m.add_function(wrap_pyfunction!(set_max_file_size, m)?)?;
+    m.add_function(wrap_pyfunction!(max_file_size, m)?)?;
+    m.add_function(wrap_pyfunction!(set_large_replay_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(large_replay_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(set_warning_callback, m)?)?;
+    m.add_function(wrap_pyfunction!(set_parse_timing, m)?)?;
+    m.add_function(wrap_pyfunction!(last_parse_timings, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_shots, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_events, m)?)?;
+    m.add_function(wrap_pyfunction!(stream_events, m)?)?;
+    m.add_function(wrap_pyfunction!(telemetry_per_second, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_kickoffs, m)?)?;
+    m.add_function(wrap_pyfunction!(goals_verified, m)?)?;
+    m.add_function(wrap_pyfunction!(score_timeline, m)?)?;
+    m.add_function(wrap_pyfunction!(goal_clips, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_bumps, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_ball_stalls, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_dribbles, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_whiffs, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_double_commits, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_flip_resets, m)?)?;
+    m.add_function(wrap_pyfunction!(pad_emission_latency, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_woodwork, m)?)?;
+    m.add_function(wrap_pyfunction!(player_presence, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_attribute, m)?)?;
+    m.add_function(wrap_pyfunction!(events_to_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(shot_quality_coefficients, m)?)?;
+    m.add_function(wrap_pyfunction!(set_shot_quality_coefficients, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_ceiling_shots, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_rumble_items, m)?)?;
+    m.add_function(wrap_pyfunction!(chat_messages, m)?)?;
     m.add_function(wrap_pyfunction!(parse_network_with_diagnostics, m)?)?;
     m.add_function(wrap_pyfunction!(header_property_keys, m)?)?;
     m.add_function(wrap_pyfunction!(header_property, m)?)?;
+    m.add_function(wrap_pyfunction!(header_properties, m)?)?;
+    m.add_function(wrap_pyfunction!(roster, m)?)?;
+    m.add_function(wrap_pyfunction!(same_series, m)?)?;
+    m.add_function(wrap_pyfunction!(arena_info, m)?)?;
+    m.add_function(wrap_pyfunction!(build_capabilities, m)?)?;
     m.add_function(wrap_pyfunction!(net_frame_count, m)?)?;
+    m.add_function(wrap_pyfunction!(has_network_data, m)?)?;
+    m.add_function(wrap_pyfunction!(scan_integrity, m)?)?;
+    m.add_function(wrap_pyfunction!(library_summary, m)?)?;
+    m.add_function(wrap_pyfunction!(frame_offsets, m)?)?;
     m.add_function(wrap_pyfunction!(debug_first_frames, m)?)?;
     // Expose a simple health flag
     m.add("RUST_CORE", true)?;
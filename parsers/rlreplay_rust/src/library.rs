@@ -0,0 +1,108 @@
+/// Aggregate counts over a batch of replay headers, for `library_summary`.
+use crate::arena_tables::lookup_arena_slug;
+use crate::header::{header_map_name, header_match_length, header_playlist_id, header_team_size};
+use boxcars::HeaderProp;
+use std::collections::BTreeMap;
+
+/// The header fields a library summary counts, from one replay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderSummary {
+    pub playlist_id: String,
+    pub map_name: String,
+    pub team_size: i64,
+    pub match_length: f64,
+}
+
+impl HeaderSummary {
+    /// Missing playlist / map fall back to "unknown", as in `parse_header`.
+    pub fn from_properties(properties: &[(String, HeaderProp)]) -> Self {
+        let map_name = header_map_name(properties);
+        HeaderSummary {
+            playlist_id: header_playlist_id(properties).unwrap_or_else(|| "unknown".to_string()),
+            map_name: if map_name.is_empty() {
+                "unknown".to_string()
+            } else {
+                map_name
+            },
+            team_size: header_team_size(properties),
+            match_length: header_match_length(properties),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LibrarySummary {
+    pub replays: usize,
+    /// Paths that could not be read or header-parsed; not counted anywhere else.
+    pub failed: usize,
+    pub total_duration_s: f64,
+    pub by_playlist: BTreeMap<String, usize>,
+    pub by_map: BTreeMap<String, usize>,
+    pub by_team_size: BTreeMap<i64, usize>,
+}
+
+impl LibrarySummary {
+    pub fn add(&mut self, header: &HeaderSummary) {
+        self.replays += 1;
+        self.total_duration_s += header.match_length;
+        *self
+            .by_playlist
+            .entry(header.playlist_id.clone())
+            .or_default() += 1;
+        *self.by_map.entry(header.map_name.clone()).or_default() += 1;
+        *self.by_team_size.entry(header.team_size).or_default() += 1;
+    }
+
+    /// Replays on arenas without pad / geometry tables (Hoops, Dropshot, ...), per
+    /// `lookup_arena_slug`.
+    pub fn unsupported_arena_replays(&self) -> usize {
+        self.by_map
+            .iter()
+            .filter(|(map, _)| lookup_arena_slug(map).is_none())
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(playlist: &str, map: &str, team_size: i64) -> HeaderSummary {
+        HeaderSummary {
+            playlist_id: playlist.to_string(),
+            map_name: map.to_string(),
+            team_size,
+            match_length: 300.0,
+        }
+    }
+
+    #[test]
+    fn test_summary_counts() {
+        let mut summary = LibrarySummary::default();
+        summary.add(&header("13", "Stadium_P", 3));
+        summary.add(&header("13", "cs_day_p", 3));
+        summary.add(&header("11", "Stadium_P", 2));
+
+        assert_eq!(summary.replays, 3);
+        assert_eq!(summary.total_duration_s, 900.0);
+        assert_eq!(summary.by_playlist["13"], 2);
+        assert_eq!(summary.by_map["Stadium_P"], 2);
+        assert_eq!(summary.by_team_size[&2], 1);
+        assert_eq!(summary.by_team_size[&3], 2);
+        assert_eq!(summary.unsupported_arena_replays(), 0);
+
+        summary.add(&header("27", "HoopsStadium_P", 2));
+        summary.add(&header("27", "HoopsStadium_P", 2));
+        assert_eq!(summary.unsupported_arena_replays(), 2);
+    }
+
+    #[test]
+    fn test_header_summary_defaults() {
+        let summary = HeaderSummary::from_properties(&[]);
+        assert_eq!(summary.playlist_id, "unknown");
+        assert_eq!(summary.map_name, "unknown");
+        assert_eq!(summary.team_size, 0);
+        assert_eq!(summary.match_length, 0.0);
+    }
+}
@@ -0,0 +1,1437 @@
+/// Single-pass network frame decoding shared by the frame-level entry points.
+///
+/// `NetworkState` owns the actor bookkeeping that used to live inline in
+/// `iter_frames`: actor classification, component ownership, team / boost /
+/// physics state and the actor → header player slot assignment. Each call to
+/// `process_frame` applies one boxcars network frame and returns a plain Rust
+/// snapshot, which the Python-facing functions then render however they need.
+use crate::geometry::{
+    byte_rotator_to_quat, car_on_ground, distance, euler_to_quat, half_turn, half_turn_quat,
+};
+use crate::header::{header_map_name, header_players};
+use crate::pads::{PadEvent, PadEventStatus, PadRegistry};
+use crate::slots::NetworkSlots;
+use crate::teams::FallbackTeams;
+use boxcars::{Attribute, Frame, NewActor, Replay, Rotation, Vector3f};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+/// Ball rest position used whenever the ball actor is (re)created or deleted.
+pub const BALL_REST_POSITION: (f32, f32, f32) = (0.0, 0.0, 93.15);
+
+const ATTR_GAME_STATE_NAME: &str = "TAGame.GameEvent_TA:ReplicatedStateName";
+/// Game state name during live play; "Countdown" and "PostGoalScored" are dead time.
+const GAME_STATE_ACTIVE: &str = "Active";
+const ATTR_PAWN_PRI: &str = "Engine.Pawn:PlayerReplicationInfo";
+const ATTR_PLAYER_NAME: &str = "Engine.PlayerReplicationInfo:PlayerName";
+/// Distance (uu) a demolished car's RigidBody must move from the wreck to count as its
+/// respawn rather than a late update of the wreck itself.
+const DEMO_RESPAWN_MIN_MOVE_UU: f32 = 100.0;
+
+/// Whether `attr_name` is a boolean wheel-contact property (`...:bWheelContact`,
+/// `...:bOnGround`). No build boxcars 0.10 decodes is known to replicate one; cars then
+/// fall back to the height / orientation heuristic.
+fn is_wheel_contact_attribute(attr_name: &str) -> bool {
+    let property = attr_name
+        .rsplit(':')
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    property.contains("wheelcontact") || property == "bonground"
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ActorKind {
+    pub is_ball: bool,
+    pub is_car: bool,
+}
+
+#[derive(Clone, Copy, Default)]
+struct ComponentKind {
+    is_jump: bool,
+    is_dodge: bool,
+    is_double_jump: bool,
+}
+
+/// Classify actors using lower-cased object/class names.
+pub fn classify_object_name_lower(lname: &str) -> ActorKind {
+    // Rumble item and item-holder actors (e.g. SpecialPickup_BallFreeze) carry ball/car-like
+    // names but are neither; they are tracked separately by the rumble module.
+    if lname.contains("specialpickup_")
+        || lname.contains("rumblepickups")
+        || is_spectator_object_name_lower(lname)
+    {
+        return ActorKind::default();
+    }
+    let is_ball = lname.contains("ball_ta")
+        || lname.contains("ball_default")
+        || lname.contains("archetypes.ball")
+        || lname.ends_with("ball")
+        || (lname.contains("ball_") && !lname.contains("ballcam"));
+    let is_car = (lname.contains("archetypes.car.car_")
+        || lname.contains("car_default")
+        || lname.contains("car_ta")
+        || lname.contains("vehicle_ta")
+        || lname.contains("default__car_ta")
+        || lname.contains("default__carbody")
+        || lname.contains("tagame.car_")
+        // Additional patterns for modern replay builds
+        || lname.contains("pawntype_ta")
+        || lname.contains("rbactor_ta")
+        || lname.contains("body_ta"))
+        && !lname.contains("carcomponent");
+    ActorKind { is_ball, is_car }
+}
+
+/// Spectator and free-camera actors. They can carry car-like names and replicate a
+/// location, but never belong to a player.
+pub fn is_spectator_object_name_lower(lname: &str) -> bool {
+    lname.contains("spectator") || lname.contains("freecam")
+}
+
+fn classify_component_name_lower(lname: &str) -> Option<ComponentKind> {
+    if !lname.contains("carcomponent") {
+        return None;
+    }
+    Some(ComponentKind {
+        is_jump: lname.contains("carcomponent_jump"),
+        is_dodge: lname.contains("carcomponent_dodge"),
+        is_double_jump: lname.contains("carcomponent_doublejump"),
+    })
+}
+
+/// (attacker car actor, victim car actor) from a demolition attribute. The attacker is
+/// None for self-demos and environmental demos such as goal explosions.
+fn demolition_actors(attribute: &Attribute) -> Option<(Option<i32>, i32)> {
+    match attribute {
+        Attribute::Demolish(demo) if demo.victim_flag => {
+            let attacker = demo.attacker_flag.then(|| demo.attacker.into());
+            Some((attacker, demo.victim.into()))
+        }
+        Attribute::DemolishFx(demo) if demo.victim_flag => {
+            let attacker = demo.attacker_flag.then(|| demo.attacker.into());
+            Some((attacker, demo.victim.into()))
+        }
+        Attribute::DemolishExtended(demo) if demo.victim.active => {
+            let attacker =
+                (demo.attacker.active && !demo.self_demolish).then(|| demo.attacker.actor.into());
+            Some((attacker, demo.victim.actor.into()))
+        }
+        _ => None,
+    }
+}
+
+/// Ball state as of the end of a network frame.
+#[derive(Clone, Copy, Debug)]
+pub struct BallSnapshot {
+    pub position: (f32, f32, f32),
+    pub velocity: (f32, f32, f32),
+    pub angular_velocity: (f32, f32, f32),
+    /// RigidBody sleeping flag: the physics engine has put the ball at rest.
+    pub sleeping: bool,
+}
+
+impl BallSnapshot {
+    /// The ball as seen from the orange end (`half_turn`).
+    pub fn half_turned(&self) -> Self {
+        BallSnapshot {
+            position: half_turn(self.position),
+            velocity: half_turn(self.velocity),
+            angular_velocity: half_turn(self.angular_velocity),
+            sleeping: self.sleeping,
+        }
+    }
+}
+
+/// Where a car's `rotation` came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RotationSource {
+    /// RigidBody state: a quaternion, or on net version < 7 a compressed rotator.
+    RigidBody,
+    /// Compressed byte rotator from the spawn trajectory or an `Engine.Actor:Rotation`
+    /// update, used until the car's first RigidBody.
+    Trajectory,
+    /// No rotation replicated; consumers approximate it from velocity.
+    VelocityFallback,
+}
+
+impl RotationSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RotationSource::RigidBody => "rigid_body",
+            RotationSource::Trajectory => "trajectory",
+            RotationSource::VelocityFallback => "velocity_fallback",
+        }
+    }
+}
+
+/// Where a car's `is_on_ground` came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroundSource {
+    /// A replicated wheel-contact attribute.
+    WheelContact,
+    /// Car height, plus orientation when a rotation is known.
+    ZHeuristic,
+}
+
+impl GroundSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GroundSource::WheelContact => "wheel_contact",
+            GroundSource::ZHeuristic => "z_heuristic",
+        }
+    }
+}
+
+/// One on-field car mapped to a header player slot.
+#[derive(Clone, Debug)]
+pub struct PlayerSnapshot {
+    pub slot: usize,
+    pub team: i64,
+    pub position: (f32, f32, f32),
+    pub velocity: (f32, f32, f32),
+    /// Replicated RigidBody angular velocity (same units as the ball's); zero until replicated.
+    pub angular_velocity: (f32, f32, f32),
+    /// Quaternion (x, y, z, w), when replicated.
+    pub rotation: Option<(f32, f32, f32, f32)>,
+    /// `VelocityFallback` exactly when `rotation` is None.
+    pub rotation_source: RotationSource,
+    /// 0-100 scale; defaults to 33 (kickoff boost) until first replication.
+    pub boost_amount: i64,
+    /// Between a demolition and the respawn (see `NetworkState::car_demo`).
+    pub is_demolished: bool,
+    pub is_jumping: bool,
+    pub is_dodging: bool,
+    pub is_double_jumping: bool,
+    /// Replicated wheel contact, on builds that carry a wheel-contact attribute.
+    pub wheel_contact: Option<bool>,
+    /// Canonical id of the boost pad the car is overlapping, if any.
+    pub on_pad_id: Option<usize>,
+    /// The car's actor was unclassified when it was enrolled into this slot, so it is a
+    /// car only by inference (position / team fallback) and may be a phantom.
+    pub inferred: bool,
+}
+
+impl PlayerSnapshot {
+    pub fn player_id(&self) -> String {
+        format!("player_{}", self.slot)
+    }
+
+    pub fn speed(&self) -> f32 {
+        let v = self.velocity;
+        (v.0 * v.0 + v.1 * v.1 + v.2 * v.2).sqrt()
+    }
+
+    /// Whether the car's wheels are on the floor, and what that is based on: replicated
+    /// wheel contact when present, else height and orientation (`car_on_ground`).
+    pub fn on_ground(&self) -> (bool, GroundSource) {
+        match self.wheel_contact {
+            Some(contact) => (contact, GroundSource::WheelContact),
+            None => (
+                car_on_ground(self.position, self.rotation),
+                GroundSource::ZHeuristic,
+            ),
+        }
+    }
+
+    /// The car as seen from the orange end (`half_turn`). Pad ids stay canonical.
+    pub fn half_turned(&self) -> Self {
+        PlayerSnapshot {
+            position: half_turn(self.position),
+            velocity: half_turn(self.velocity),
+            angular_velocity: half_turn(self.angular_velocity),
+            rotation: self.rotation.map(half_turn_quat),
+            ..self.clone()
+        }
+    }
+}
+
+/// Pad event plus the player slot / team its collector resolved to at emission time.
+#[derive(Clone, Debug)]
+pub struct FramePadEvent {
+    pub event: PadEvent,
+    pub player_slot: Option<usize>,
+    pub player_team: Option<i64>,
+    /// COLLECTED events only: the collector's boost (0-100) at the start of the emitting
+    /// frame, before any boost update on it; None when never replicated.
+    pub boost_before: Option<i64>,
+}
+
+/// A demolition, with attacker / victim car actors resolved to player slots at emission
+/// time. `attacker_slot` is None for self-demos and environmental (goal explosion) demos.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameDemolition {
+    pub attacker_slot: Option<usize>,
+    pub victim_slot: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FrameSnapshot {
+    pub timestamp: f32,
+    pub ball: BallSnapshot,
+    /// On-field players ordered by slot.
+    pub players: Vec<PlayerSnapshot>,
+    pub pad_events: Vec<FramePadEvent>,
+    pub demolitions: Vec<FrameDemolition>,
+    /// "object_name" | "component_owner_chain" | "fallback_unclassified"
+    pub classification_source: &'static str,
+    /// True during live play: a ball exists and the replicated game state is "Active".
+    /// Builds that never replicate the game state are treated as live whenever a ball exists.
+    pub is_live: bool,
+}
+
+/// Which physics attribute sets an actor's position when it gets both a RigidBody and a
+/// bare Location update in the same frame; otherwise the last one would win and jitter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PhysicsPriority {
+    /// The RigidBody position; a Location update for the actor that frame is ignored.
+    #[default]
+    RigidBody,
+    /// The Location position (velocities still come from the RigidBody). For debugging.
+    Location,
+}
+
+/// Cloning is cheap enough to checkpoint: the replay's object and name tables are shared.
+#[derive(Clone)]
+pub struct NetworkState {
+    objects: Arc<[String]>,
+    names: Arc<[String]>,
+    game_state: Option<String>,
+    header_players: Vec<(String, i64)>,
+    actor_object_name: HashMap<i32, String>,
+    actor_kind: HashMap<i32, ActorKind>,
+    component_kind: HashMap<i32, ComponentKind>,
+    car_team: HashMap<i32, i64>,
+    car_boost: HashMap<i32, i64>,
+    car_pos: HashMap<i32, (f32, f32, f32)>,
+    car_vel: HashMap<i32, (f32, f32, f32)>,
+    car_angvel: HashMap<i32, (f32, f32, f32)>,
+    car_rot: HashMap<i32, (f32, f32, f32, f32)>,
+    car_rot_source: HashMap<i32, RotationSource>,
+    /// Demolished car → (frame time, position) of its demolition, until it respawns: the
+    /// actor is re-created or an awake RigidBody moves it away from where it was destroyed.
+    car_demo: HashMap<i32, (f32, (f32, f32, f32))>,
+    /// Car → its last demolition payload. The payload is re-replicated on the car after it
+    /// respawns too, and those copies are not new demolitions.
+    car_last_demo: HashMap<i32, Attribute>,
+    /// Car → wheels touching a surface, from a wheel-contact attribute (see
+    /// `is_wheel_contact_attribute`).
+    car_wheel_contact: HashMap<i32, bool>,
+    car_pri: HashMap<i32, i32>,
+    pri_name: HashMap<i32, String>,
+    /// PRI → car body product id for the (blue, orange) team, from its client loadout.
+    pri_bodies: HashMap<i32, [u32; 2]>,
+    /// Header slot → car body of the player in it, once their PRI name is known.
+    slot_body: HashMap<usize, u32>,
+    component_owner: HashMap<i32, i32>,
+    pad_registry: PadRegistry,
+    ball_actor: Option<i32>,
+    ball_pos: (f32, f32, f32),
+    ball_vel: (f32, f32, f32),
+    ball_angvel: (f32, f32, f32),
+    ball_sleeping: bool,
+    actor_to_player_index: HashMap<i32, usize>,
+    next_by_team: HashMap<i64, Vec<usize>>,
+    /// Explicit slot → team assignments; win over TeamPaint and the y-sign fallback.
+    team_overrides: HashMap<usize, i64>,
+    /// Slots for cars when the header has no roster.
+    network_slots: NetworkSlots,
+    /// Unclassified actors enrolled as players, and how many have been enrolled so far.
+    inferred_actors: HashSet<i32>,
+    inferred_player_count: usize,
+    /// Sticky y-sign team guesses for cars without TeamPaint.
+    fallback_teams: FallbackTeams,
+    physics_priority: PhysicsPriority,
+}
+
+impl NetworkState {
+    pub fn new(replay: &Replay) -> Self {
+        Self::with_team_overrides(replay, HashMap::new())
+    }
+
+    /// Like `new`, with slots in `team_overrides` forced onto the given team (0 or 1). The
+    /// override replaces the header roster team when queueing slots and the reported team of
+    /// the slot's car, whatever its TeamPaint or position says.
+    pub fn with_team_overrides(replay: &Replay, team_overrides: HashMap<usize, i64>) -> Self {
+        let mut header_players = header_players(&replay.properties);
+        for (slot, team) in &team_overrides {
+            if let Some(player) = header_players.get_mut(*slot) {
+                player.1 = *team;
+            }
+        }
+        // Extract map name for arena-aware pad snapping
+        let map_name = header_map_name(&replay.properties);
+        // Prepare per-team header order indices
+        let mut team_zero: Vec<usize> = Vec::new();
+        let mut team_one: Vec<usize> = Vec::new();
+        for (idx, (_, team)) in header_players.iter().enumerate() {
+            if *team == 0 {
+                team_zero.push(idx);
+            } else {
+                team_one.push(idx);
+            }
+        }
+        let mut next_by_team: HashMap<i64, Vec<usize>> = HashMap::new();
+        next_by_team.insert(0, team_zero);
+        next_by_team.insert(1, team_one);
+
+        NetworkState {
+            objects: replay.objects.as_slice().into(),
+            names: replay.names.as_slice().into(),
+            game_state: None,
+            header_players,
+            actor_object_name: HashMap::new(),
+            actor_kind: HashMap::new(),
+            component_kind: HashMap::new(),
+            car_team: HashMap::new(),
+            car_boost: HashMap::new(),
+            car_pos: HashMap::new(),
+            car_vel: HashMap::new(),
+            car_angvel: HashMap::new(),
+            car_rot: HashMap::new(),
+            car_rot_source: HashMap::new(),
+            car_demo: HashMap::new(),
+            car_last_demo: HashMap::new(),
+            car_wheel_contact: HashMap::new(),
+            car_pri: HashMap::new(),
+            pri_name: HashMap::new(),
+            pri_bodies: HashMap::new(),
+            slot_body: HashMap::new(),
+            component_owner: HashMap::new(),
+            pad_registry: PadRegistry::new_with_arena(&map_name),
+            ball_actor: None,
+            ball_pos: BALL_REST_POSITION,
+            ball_vel: (0.0, 0.0, 0.0),
+            ball_angvel: (0.0, 0.0, 0.0),
+            ball_sleeping: false,
+            actor_to_player_index: HashMap::new(),
+            next_by_team,
+            team_overrides,
+            network_slots: NetworkSlots::default(),
+            inferred_actors: HashSet::new(),
+            inferred_player_count: 0,
+            fallback_teams: FallbackTeams::new(),
+            physics_priority: PhysicsPriority::default(),
+        }
+    }
+
+    /// Set which physics attribute wins within a frame (RigidBody by default).
+    pub fn set_physics_priority(&mut self, priority: PhysicsPriority) {
+        self.physics_priority = priority;
+    }
+
+    /// Number of player slots: the header roster size, or the slots assigned so far when
+    /// the header has no roster.
+    pub fn slot_count(&self) -> usize {
+        self.header_players.len().max(self.network_slots.count())
+    }
+
+    /// Number of player slots synthesised from network car actors because the header has
+    /// no roster (0 with a roster).
+    pub fn network_slot_count(&self) -> usize {
+        self.network_slots.count()
+    }
+
+    /// Number of unclassified actors enrolled as players so far (see
+    /// `PlayerSnapshot::inferred`). This counts actors, not players: a car that respawns as
+    /// a new unclassified actor counts again for the same slot.
+    pub fn inferred_player_count(&self) -> usize {
+        self.inferred_player_count
+    }
+
+    /// The current ball actor, if one is alive.
+    pub fn ball_actor(&self) -> Option<i32> {
+        self.ball_actor
+    }
+
+    /// Header player slot currently mapped to `actor_id`, if any.
+    pub fn slot_for_actor(&self, actor_id: i32) -> Option<usize> {
+        self.actor_to_player_index.get(&actor_id).copied()
+    }
+
+    /// Header slot of the player driving `car`, preferring the car's replicated
+    /// PlayerReplicationInfo name over the team-order slot assignment.
+    pub fn slot_for_car(&self, car: i32) -> Option<usize> {
+        self.car_pri
+            .get(&car)
+            .and_then(|pri| self.pri_name.get(pri))
+            .and_then(|name| self.header_players.iter().position(|(n, _)| n == name))
+            .or_else(|| self.slot_for_actor(car))
+    }
+
+    /// Car body product id of the player in header slot `slot`, from their client loadout
+    /// (the one for their header team when the loadout is per team).
+    pub fn car_body(&self, slot: usize) -> Option<u32> {
+        self.slot_body.get(&slot).copied()
+    }
+
+    /// Follow the component → owner chain up to the owning actor.
+    pub fn resolve_owner(&self, actor_id: i32) -> i32 {
+        let mut resolved = actor_id;
+        let mut guard = 0;
+        while let Some(owner) = self.component_owner.get(&resolved) {
+            if *owner == resolved {
+                break;
+            }
+            resolved = *owner;
+            guard += 1;
+            if guard > 8 {
+                break;
+            }
+        }
+        resolved
+    }
+
+    fn reset_ball(&mut self) {
+        self.ball_pos = BALL_REST_POSITION;
+        self.ball_vel = (0.0, 0.0, 0.0);
+        self.ball_angvel = (0.0, 0.0, 0.0);
+        self.ball_sleeping = false;
+    }
+
+    /// Record a compressed byte rotator for a car that has no RigidBody rotation yet.
+    fn set_compressed_rotation(&mut self, aid: i32, rot: Rotation) {
+        if self.car_rot_source.get(&aid) != Some(&RotationSource::RigidBody) {
+            // The bytes are serialized in FRotator order (pitch, yaw, roll), which boxcars
+            // labels yaw / pitch / roll.
+            let quat = byte_rotator_to_quat(rot.yaw, rot.pitch, rot.roll);
+            self.car_rot.insert(aid, quat);
+            self.car_rot_source.insert(aid, RotationSource::Trajectory);
+        }
+    }
+
+    /// Apply one network frame and return the resulting snapshot.
+    pub fn process_frame(&mut self, nf: &Frame) -> FrameSnapshot {
+        let objects = Arc::clone(&self.objects);
+        let mut frame_pad_events: Vec<PadEvent> = Vec::new();
+        // (attacker car actor, victim car actor)
+        let mut frame_demolitions: Vec<(Option<i32>, i32)> = Vec::new();
+        let mut frame_jumping_actors: HashSet<i32> = HashSet::new();
+        let mut frame_dodging_actors: HashSet<i32> = HashSet::new();
+        let mut frame_double_jumping_actors: HashSet<i32> = HashSet::new();
+        // Actors that got each physics attribute this frame, for `physics_priority`.
+        let mut frame_rigid_body_actors: HashSet<i32> = HashSet::new();
+        let mut frame_location_actors: HashSet<i32> = HashSet::new();
+        // Car → boost before its first boost update this frame (None when it had none yet).
+        let mut frame_boost_before: HashMap<i32, Option<i64>> = HashMap::new();
+        self.pad_registry.set_frame_time(nf.time);
+
+        // Prune actors that were deleted before processing updates to avoid stale telemetry
+        for deleted in &nf.deleted_actors {
+            let aid: i32 = (*deleted).into();
+            let team_for_return = self.car_team.get(&aid).copied();
+            if self.ball_actor == Some(aid) {
+                self.ball_actor = None;
+                self.reset_ball();
+            }
+            // Network-derived slots follow the PRI instead of returning to a team queue.
+            let roster_slot = self
+                .actor_to_player_index
+                .remove(&aid)
+                .filter(|_| !self.header_players.is_empty());
+            if let Some(idx) = roster_slot {
+                let team_for_return = self.team_overrides.get(&idx).copied().or(team_for_return);
+                if let Some(team) = team_for_return {
+                    if let Some(queue) = self.next_by_team.get_mut(&team) {
+                        queue.push(idx);
+                    }
+                }
+            }
+            if let Some(pri) = self.car_pri.get(&aid) {
+                self.network_slots.link(aid, *pri);
+            }
+            self.network_slots.remove(aid);
+            self.inferred_actors.remove(&aid);
+            self.actor_object_name.remove(&aid);
+            self.actor_kind.remove(&aid);
+            self.component_kind.remove(&aid);
+            self.car_team.remove(&aid);
+            self.fallback_teams.remove(aid);
+            self.car_boost.remove(&aid);
+            self.car_pos.remove(&aid);
+            self.car_vel.remove(&aid);
+            self.car_angvel.remove(&aid);
+            self.car_rot.remove(&aid);
+            self.car_rot_source.remove(&aid);
+            self.car_demo.remove(&aid);
+            self.car_last_demo.remove(&aid);
+            self.car_wheel_contact.remove(&aid);
+            self.car_pri.remove(&aid);
+            self.pri_name.remove(&aid);
+            self.pri_bodies.remove(&aid);
+            self.component_owner
+                .retain(|comp, owner| *comp != aid && *owner != aid);
+            self.pad_registry.remove_actor(aid);
+        }
+
+        // Update actor_object_name mapping with new actors in this frame
+        for NewActor {
+            actor_id,
+            object_id,
+            initial_trajectory,
+            ..
+        } in &nf.new_actors
+        {
+            let oid: usize = (*object_id).into();
+            let obj_name = objects.get(oid).cloned().unwrap_or_default();
+            let obj_name_lower = obj_name.to_ascii_lowercase();
+            let aid: i32 = (*actor_id).into();
+            let kind = classify_object_name_lower(&obj_name_lower);
+            if kind.is_ball {
+                self.ball_actor = Some(aid);
+                self.reset_ball();
+            }
+            if kind.is_car {
+                self.car_demo.remove(&aid);
+            }
+            if kind.is_ball || kind.is_car {
+                self.actor_kind.insert(aid, kind);
+            } else if is_spectator_object_name_lower(&obj_name_lower) {
+                // Classified as neither, which keeps it out of the unclassified-car fallback.
+                self.actor_kind.insert(aid, kind);
+            }
+            if let (true, Some(rot)) = (kind.is_car, initial_trajectory.rotation) {
+                self.set_compressed_rotation(aid, rot);
+            }
+            if let Some(component) = classify_component_name_lower(&obj_name_lower) {
+                self.component_kind.insert(aid, component);
+            }
+            self.pad_registry.track_new_actor(aid, &obj_name);
+            self.actor_object_name.insert(aid, obj_name);
+        }
+
+        // Process updates
+        for upd in &nf.updated_actors {
+            let aid: i32 = upd.actor_id.into();
+            let oid: usize = upd.object_id.into();
+            let attr_name = objects.get(oid).map(|s| s.as_str()).unwrap_or("");
+            match &upd.attribute {
+                // Demolished cars drop their PRI link; keep the last driver for attribution.
+                Attribute::ActiveActor(active) if attr_name == ATTR_PAWN_PRI && active.active => {
+                    self.car_pri.insert(aid, active.actor.into());
+                }
+                Attribute::Boolean(contact) if is_wheel_contact_attribute(attr_name) => {
+                    let target = self.component_owner.get(&aid).cloned().unwrap_or(aid);
+                    self.car_wheel_contact.insert(target, *contact);
+                }
+                Attribute::String(name) if attr_name == ATTR_PLAYER_NAME => {
+                    self.pri_name.insert(aid, name.clone());
+                }
+                Attribute::Loadout(loadout) => {
+                    self.pri_bodies.insert(aid, [loadout.body; 2]);
+                }
+                Attribute::TeamLoadout(loadouts) => {
+                    self.pri_bodies
+                        .insert(aid, [loadouts.blue.body, loadouts.orange.body]);
+                }
+                Attribute::ActiveActor(active) => {
+                    if let Some(component) = self.component_kind.get(&aid) {
+                        let owner_id: i32 = active.actor.into();
+                        self.component_owner.insert(aid, owner_id);
+                        if active.active {
+                            if component.is_jump {
+                                frame_jumping_actors.insert(owner_id);
+                            }
+                            if component.is_dodge {
+                                frame_dodging_actors.insert(owner_id);
+                            }
+                            if component.is_double_jump {
+                                frame_double_jumping_actors.insert(owner_id);
+                            }
+                        }
+                    }
+                }
+                // Primary physics carrier observed across builds
+                Attribute::RigidBody(rb) => {
+                    frame_rigid_body_actors.insert(aid);
+                    let set_position = self.physics_priority == PhysicsPriority::RigidBody
+                        || !frame_location_actors.contains(&aid);
+                    let loc = rb.location;
+                    let vel = rb.linear_velocity.unwrap_or(Vector3f {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                    });
+                    let ang = rb.angular_velocity.unwrap_or(Vector3f {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                    });
+                    // Update ball or car state depending on classification and fallback
+                    let is_ball = Some(aid) == self.ball_actor
+                        || self
+                            .actor_kind
+                            .get(&aid)
+                            .map(|kind| kind.is_ball)
+                            .unwrap_or(false);
+                    if is_ball {
+                        self.ball_actor = Some(aid);
+                        if set_position {
+                            self.ball_pos = (loc.x, loc.y, loc.z);
+                        }
+                        self.ball_vel = (vel.x, vel.y, vel.z);
+                        self.ball_angvel = (ang.x, ang.y, ang.z);
+                        self.ball_sleeping = rb.sleeping;
+                    } else {
+                        if set_position {
+                            self.car_pos.insert(aid, (loc.x, loc.y, loc.z));
+                        }
+                        self.car_vel.insert(aid, (vel.x, vel.y, vel.z));
+                        self.car_angvel.insert(aid, (ang.x, ang.y, ang.z));
+                        // Extract quaternion rotation from RigidBody. Before net version 7
+                        // boxcars stores a compressed (pitch, yaw, roll) rotator in x / y / z
+                        // (fractions of π) with w = 0, which no unit quaternion decodes to.
+                        let rot = rb.rotation;
+                        let quat = if rot.w == 0.0 {
+                            euler_to_quat(rot.z * PI, rot.x * PI, rot.y * PI)
+                        } else {
+                            (rot.x, rot.y, rot.z, rot.w)
+                        };
+                        self.car_rot.insert(aid, quat);
+                        self.car_rot_source.insert(aid, RotationSource::RigidBody);
+                        // A demolished car's physics stays at the wreck until it respawns.
+                        let respawned = self.car_demo.get(&aid).is_some_and(|(t, at)| {
+                            nf.time > *t
+                                && distance(*at, (loc.x, loc.y, loc.z)) > DEMO_RESPAWN_MIN_MOVE_UU
+                        });
+                        if respawned && !rb.sleeping {
+                            self.car_demo.remove(&aid);
+                        }
+                    }
+                    if set_position {
+                        let events = self
+                            .pad_registry
+                            .update_position(aid, (loc.x, loc.y, loc.z));
+                        frame_pad_events.extend(events);
+                    }
+                }
+                Attribute::Rotation(rot)
+                    if self.actor_kind.get(&aid).is_some_and(|kind| kind.is_car) =>
+                {
+                    self.set_compressed_rotation(aid, *rot);
+                }
+                // Some builds carry these separately
+                Attribute::Location(loc) => {
+                    if let Some(component) = self.component_kind.get(&aid) {
+                        let target = self.component_owner.get(&aid).cloned().unwrap_or(aid);
+                        if component.is_jump {
+                            frame_jumping_actors.insert(target);
+                        }
+                        if component.is_dodge {
+                            frame_dodging_actors.insert(target);
+                        }
+                        if component.is_double_jump {
+                            frame_double_jumping_actors.insert(target);
+                        }
+                    }
+                    if self.physics_priority == PhysicsPriority::RigidBody
+                        && frame_rigid_body_actors.contains(&aid)
+                    {
+                        continue;
+                    }
+                    frame_location_actors.insert(aid);
+                    if Some(aid) == self.ball_actor {
+                        self.ball_pos = (loc.x, loc.y, loc.z);
+                    } else {
+                        self.car_pos.insert(aid, (loc.x, loc.y, loc.z));
+                    }
+                    let events = self
+                        .pad_registry
+                        .update_position(aid, (loc.x, loc.y, loc.z));
+                    frame_pad_events.extend(events);
+                }
+
+                Attribute::PickupNew(pickup) => {
+                    let mut raw_actor_opt: Option<i32> = None;
+                    let mut resolved_actor: Option<i32> = None;
+                    if let Some(instigator) = pickup.instigator {
+                        let raw_actor: i32 = instigator.into();
+                        raw_actor_opt = Some(raw_actor);
+                        resolved_actor = Some(self.resolve_owner(raw_actor));
+                    }
+
+                    let events = self.pad_registry.handle_pickup(
+                        aid,
+                        pickup.picked_up,
+                        nf.time,
+                        raw_actor_opt,
+                        resolved_actor,
+                        resolved_actor.and_then(|actor| self.car_pos.get(&actor).copied()),
+                    );
+                    frame_pad_events.extend(events);
+                }
+                // Team + visual paint data (use team assignment if present)
+                Attribute::TeamPaint(tp) => {
+                    let t = (tp.team as i64).clamp(0, 1);
+                    let target = self.component_owner.get(&aid).cloned().unwrap_or(aid);
+                    self.car_team.insert(target, t);
+                    // Skip only if we positively know this actor is NOT a car.
+                    // Unclassified actors that receive TeamPaint are treated as cars
+                    // (TeamPaint is a car-exclusive attribute in Rocket League).
+                    if self
+                        .actor_kind
+                        .get(&target)
+                        .map(|kind| !kind.is_car)
+                        .unwrap_or(false)
+                    {
+                        continue;
+                    }
+                    // Register as car if not yet classified (TeamPaint implies car)
+                    self.actor_kind.entry(target).or_insert(ActorKind {
+                        is_ball: false,
+                        is_car: true,
+                    });
+                    if !self.actor_to_player_index.contains_key(&target) {
+                        if let Some(v) = self.next_by_team.get_mut(&t) {
+                            if !v.is_empty() {
+                                let idx = v.remove(0);
+                                self.actor_to_player_index.insert(target, idx);
+                            }
+                        }
+                    }
+                }
+                // Boost value replication (0..=255) → scale to 0..=100
+                Attribute::ReplicatedBoost(rb) => {
+                    let amt = ((rb.boost_amount as f64) * (100.0 / 255.0)).round() as i64;
+                    let target = self.component_owner.get(&aid).cloned().unwrap_or(aid);
+                    let before = self.car_boost.insert(target, amt.clamp(0, 100));
+                    frame_boost_before.entry(target).or_insert(before);
+                }
+                // Demolition signals (varies by build)
+                Attribute::Demolish(_)
+                | Attribute::DemolishExtended(_)
+                | Attribute::DemolishFx(_) => {
+                    // The payload is re-replicated on the victim car; only the first copy
+                    // per car actor is a new demolition.
+                    let repeat = self.car_demo.contains_key(&aid)
+                        || self.car_last_demo.get(&aid) == Some(&upd.attribute);
+                    if !repeat {
+                        let at = self.car_pos.get(&aid).copied().unwrap_or_default();
+                        self.car_demo.insert(aid, (nf.time, at));
+                        self.car_last_demo.insert(aid, upd.attribute.clone());
+                        if let Some(demo) = demolition_actors(&upd.attribute) {
+                            frame_demolitions.push(demo);
+                        }
+                    }
+                }
+                Attribute::Int(name_idx) if attr_name == ATTR_GAME_STATE_NAME => {
+                    if let Some(name) = usize::try_from(*name_idx)
+                        .ok()
+                        .and_then(|idx| self.names.get(idx))
+                    {
+                        self.game_state = Some(name.clone());
+                    }
+                }
+                // Note: Jump/Dodge/Throttle/Steer/Handbrake attributes are not directly
+                // exposed by boxcars 0.10.7. These mechanics will be inferred in Python
+                // from physics state changes and position/velocity derivatives.
+                _ => {}
+            }
+        }
+
+        frame_pad_events.extend(self.pad_registry.flush_ready_events());
+
+        // Loadout and name may replicate on different frames; resolve once both are known.
+        for (pri, bodies) in &self.pri_bodies {
+            let Some(name) = self.pri_name.get(pri) else {
+                continue;
+            };
+            if let Some(slot) = self.header_players.iter().position(|(n, _)| n == name) {
+                let team = self.header_players[slot].1.clamp(0, 1) as usize;
+                self.slot_body.insert(slot, bodies[team]);
+            }
+        }
+
+        // Players: union of actors that have position or boost info
+        let mut actors: BTreeSet<i32> = BTreeSet::new();
+        actors.extend(self.car_pos.keys().copied());
+        actors.extend(self.car_boost.keys().copied());
+        actors.extend(self.car_team.keys().copied());
+        if let Some(ball_id) = self.ball_actor {
+            actors.remove(&ball_id);
+        }
+        // Filter using classification when available; keep unclassified for fallback
+        actors.retain(|aid| {
+            self.actor_kind
+                .get(aid)
+                .map(|kind| kind.is_car)
+                .unwrap_or(true)
+        });
+
+        let mut players_map: BTreeMap<usize, PlayerSnapshot> = BTreeMap::new();
+        let owned_actor_ids: HashSet<i32> = self.component_owner.values().copied().collect();
+        let mut frame_classification_source = "object_name";
+        for aid in actors {
+            let mut actor_classification_source = "fallback_unclassified";
+            if self.actor_kind.contains_key(&aid) {
+                actor_classification_source = "object_name";
+            } else if owned_actor_ids.contains(&aid) {
+                actor_classification_source = "component_owner_chain";
+            }
+            if actor_classification_source == "fallback_unclassified" {
+                frame_classification_source = "fallback_unclassified";
+            } else if actor_classification_source == "component_owner_chain"
+                && frame_classification_source == "object_name"
+            {
+                frame_classification_source = "component_owner_chain";
+            }
+
+            let position = self.car_pos.get(&aid).copied();
+            let (x, y, z) = position.unwrap_or((0.0, 0.0, 17.0));
+            // Determine team: prefer decoded team_paint else the car's sticky y-sign guess.
+            // An explicit override for the assigned slot replaces it in the snapshot.
+            let team = self.fallback_teams.team(
+                aid,
+                self.car_team.get(&aid).copied(),
+                position.map(|(_, y, _)| y),
+            );
+            // Without TeamPaint or a replicated position the team is unknown; guessing from
+            // the placeholder would lock the car to blue. It waits unassigned.
+            let Some(team) = team else {
+                continue;
+            };
+            // Assign player index if not assigned
+            if !self.actor_to_player_index.contains_key(&aid) {
+                let next = self.next_by_team.get_mut(&team).map(|v| {
+                    if v.is_empty() {
+                        None
+                    } else {
+                        Some(v.remove(0))
+                    }
+                });
+                match next {
+                    Some(Some(idx)) => {
+                        self.actor_to_player_index.insert(aid, idx);
+                    }
+                    // Without a roster to bound enrollment, only classified cars get slots.
+                    _ if self.header_players.is_empty()
+                        && actor_classification_source != "fallback_unclassified" =>
+                    {
+                        let pri = self.car_pri.get(&aid).copied();
+                        let slot = self.network_slots.slot(aid, pri);
+                        self.actor_to_player_index.insert(aid, slot);
+                    }
+                    _ => {}
+                }
+                if actor_classification_source == "fallback_unclassified"
+                    && self.actor_to_player_index.contains_key(&aid)
+                    && self.inferred_actors.insert(aid)
+                {
+                    self.inferred_player_count += 1;
+                }
+            }
+            if let Some(idx) = self.actor_to_player_index.get(&aid).cloned() {
+                let is_demolished = self.car_demo.contains_key(&aid);
+                players_map.insert(
+                    idx,
+                    PlayerSnapshot {
+                        slot: idx,
+                        team: self.team_overrides.get(&idx).copied().unwrap_or(team),
+                        position: (x, y, z),
+                        velocity: self.car_vel.get(&aid).cloned().unwrap_or((0.0, 0.0, 0.0)),
+                        angular_velocity: self
+                            .car_angvel
+                            .get(&aid)
+                            .cloned()
+                            .unwrap_or((0.0, 0.0, 0.0)),
+                        rotation: self.car_rot.get(&aid).cloned(),
+                        rotation_source: self
+                            .car_rot_source
+                            .get(&aid)
+                            .copied()
+                            .unwrap_or(RotationSource::VelocityFallback),
+                        boost_amount: *self.car_boost.get(&aid).unwrap_or(&33),
+                        is_demolished,
+                        is_jumping: frame_jumping_actors.contains(&aid),
+                        is_dodging: frame_dodging_actors.contains(&aid),
+                        is_double_jumping: frame_double_jumping_actors.contains(&aid),
+                        wheel_contact: self.car_wheel_contact.get(&aid).copied(),
+                        on_pad_id: if is_demolished {
+                            None
+                        } else {
+                            self.pad_registry.overlapping_pad_id((x, y, z))
+                        },
+                        inferred: self.inferred_actors.contains(&aid),
+                    },
+                );
+            }
+        }
+
+        let pad_events = frame_pad_events
+            .into_iter()
+            .map(|event| {
+                let (player_slot, player_team) = match event.resolved_actor_id {
+                    Some(resolved) => {
+                        let slot = self.actor_to_player_index.get(&resolved).copied();
+                        let team = slot
+                            .and_then(|slot| self.team_overrides.get(&slot).copied())
+                            .or_else(|| self.car_team.get(&resolved).copied());
+                        (slot, team)
+                    }
+                    None => (None, None),
+                };
+                let boost_before = match (event.status, event.resolved_actor_id) {
+                    (PadEventStatus::Collected, Some(resolved)) => frame_boost_before
+                        .get(&resolved)
+                        .copied()
+                        .unwrap_or_else(|| self.car_boost.get(&resolved).copied()),
+                    _ => None,
+                };
+                FramePadEvent {
+                    event,
+                    player_slot,
+                    player_team,
+                    boost_before,
+                }
+            })
+            .collect();
+        let demolitions = frame_demolitions
+            .into_iter()
+            .map(|(attacker, victim)| FrameDemolition {
+                attacker_slot: attacker
+                    .filter(|car| *car != victim)
+                    .and_then(|car| self.slot_for_car(car)),
+                victim_slot: self.slot_for_car(victim),
+            })
+            .collect();
+
+        FrameSnapshot {
+            timestamp: nf.time,
+            ball: BallSnapshot {
+                position: self.ball_pos,
+                velocity: self.ball_vel,
+                angular_velocity: self.ball_angvel,
+                sleeping: self.ball_sleeping,
+            },
+            players: players_map.into_values().collect(),
+            pad_events,
+            demolitions,
+            classification_source: frame_classification_source,
+            is_live: self.ball_actor.is_some()
+                && self
+                    .game_state
+                    .as_deref()
+                    .is_none_or(|state| state == GAME_STATE_ACTIVE),
+        }
+    }
+}
+
+/// Snapshot builders for detector tests; override fields with struct update syntax, e.g.
+/// `PlayerSnapshot { position, ..player(0) }`.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    /// A car of team 0 at rest on the floor at the origin, without a rotation, at 33 boost.
+    pub fn player(slot: usize) -> PlayerSnapshot {
+        PlayerSnapshot {
+            slot,
+            team: 0,
+            position: (0.0, 0.0, 17.0),
+            velocity: (0.0, 0.0, 0.0),
+            angular_velocity: (0.0, 0.0, 0.0),
+            rotation: None,
+            rotation_source: RotationSource::VelocityFallback,
+            boost_amount: 33,
+            is_demolished: false,
+            is_jumping: false,
+            is_dodging: false,
+            is_double_jumping: false,
+            wheel_contact: None,
+            on_pad_id: None,
+            inferred: false,
+        }
+    }
+
+    /// The ball at rest on the centre spot.
+    pub fn ball() -> BallSnapshot {
+        BallSnapshot {
+            position: BALL_REST_POSITION,
+            velocity: (0.0, 0.0, 0.0),
+            angular_velocity: (0.0, 0.0, 0.0),
+            sleeping: false,
+        }
+    }
+
+    /// A live frame with the ball at rest and no pad events or demolitions.
+    pub fn frame(timestamp: f32, players: Vec<PlayerSnapshot>) -> FrameSnapshot {
+        FrameSnapshot {
+            timestamp,
+            ball: ball(),
+            players,
+            pad_events: Vec::new(),
+            demolitions: Vec::new(),
+            classification_source: "test",
+            is_live: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boxcars::{
+        ActiveActor, ActorId, Demolish, HeaderProp, ObjectId, Quaternion, ReplicatedBoost,
+        RigidBody, StreamId, Trajectory, UpdatedAttribute,
+    };
+
+    const CAR: i32 = 5;
+    const BALL: i32 = 9;
+    const COMPONENT: i32 = 20;
+
+    // Object ids of `replay()`'s object table.
+    const CAR_OBJECT: i32 = 0;
+    const RIGID_BODY: i32 = 1;
+    const DEMOLISH: i32 = 2;
+    const BOOST: i32 = 3;
+    const BALL_OBJECT: i32 = 4;
+    const LOCATION: i32 = 5;
+    const UNCLASSIFIED_OBJECT: i32 = 6;
+    const SPECTATOR_OBJECT: i32 = 7;
+    const JUMP_COMPONENT_OBJECT: i32 = 8;
+    const COMPONENT_VEHICLE: i32 = 9;
+    const WHEEL_CONTACT: i32 = 10;
+
+    fn replay() -> Replay {
+        Replay {
+            header_size: 0,
+            header_crc: 0,
+            major_version: 868,
+            minor_version: 32,
+            net_version: Some(10),
+            game_type: String::new(),
+            properties: Vec::new(),
+            content_size: 0,
+            content_crc: 0,
+            network_frames: None,
+            levels: Vec::new(),
+            keyframes: Vec::new(),
+            debug_info: Vec::new(),
+            tick_marks: Vec::new(),
+            packages: Vec::new(),
+            objects: [
+                "Archetypes.Car.Car_Default",
+                "TAGame.RBActor_TA:ReplicatedRBState",
+                "TAGame.Car_TA:ReplicatedDemolish",
+                "TAGame.CarComponent_Boost_TA:ReplicatedBoost",
+                "Archetypes.Ball.Ball_Default",
+                "Engine.Actor:Location",
+                "TAGame.Mystery_TA",
+                "Archetypes.Car.Car_Spectator",
+                "Archetypes.CarComponents.CarComponent_Jump",
+                "TAGame.CarComponent_TA:Vehicle",
+                "TAGame.CarComponent_TA:bWheelContact",
+            ]
+            .map(String::from)
+            .to_vec(),
+            names: Vec::new(),
+            class_indices: Vec::new(),
+            net_cache: Vec::new(),
+        }
+    }
+
+    fn spawn_actor(actor: i32, object: i32) -> NewActor {
+        NewActor {
+            actor_id: ActorId(actor),
+            name_id: None,
+            object_id: ObjectId(object),
+            initial_trajectory: Trajectory {
+                location: None,
+                rotation: None,
+            },
+        }
+    }
+
+    fn spawn() -> NewActor {
+        spawn_actor(CAR, CAR_OBJECT)
+    }
+
+    fn update(actor: i32, object: i32, attribute: Attribute) -> UpdatedAttribute {
+        UpdatedAttribute {
+            actor_id: ActorId(actor),
+            stream_id: StreamId(0),
+            object_id: ObjectId(object),
+            attribute,
+        }
+    }
+
+    fn vector(v: (f32, f32, f32)) -> Vector3f {
+        Vector3f {
+            x: v.0,
+            y: v.1,
+            z: v.2,
+        }
+    }
+
+    fn rigid_body_at(actor: i32, location: (f32, f32, f32)) -> UpdatedAttribute {
+        let body = RigidBody {
+            sleeping: false,
+            location: vector(location),
+            rotation: Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 1.0,
+            },
+            linear_velocity: Some(vector((100.0, 0.0, 0.0))),
+            angular_velocity: None,
+        };
+        update(actor, RIGID_BODY, Attribute::RigidBody(body))
+    }
+
+    fn rigid_body(y: f32) -> UpdatedAttribute {
+        rigid_body_at(CAR, (0.0, y, 17.0))
+    }
+
+    fn demolish(speed: f32) -> UpdatedAttribute {
+        let velocity = vector((speed, 0.0, 0.0));
+        let demo = Demolish {
+            attacker_flag: false,
+            attacker: ActorId(-1),
+            victim_flag: true,
+            victim: ActorId(CAR),
+            attack_velocity: velocity,
+            victim_velocity: velocity,
+        };
+        update(CAR, DEMOLISH, Attribute::Demolish(Box::new(demo)))
+    }
+
+    fn boost(amount: u8) -> UpdatedAttribute {
+        let boost = ReplicatedBoost {
+            grant_count: 0,
+            boost_amount: amount,
+            unused1: 0,
+            unused2: 0,
+        };
+        update(CAR, BOOST, Attribute::ReplicatedBoost(boost))
+    }
+
+    fn frame(time: f32, new_actors: Vec<NewActor>, updated: Vec<UpdatedAttribute>) -> Frame {
+        Frame {
+            time,
+            delta: 0.1,
+            new_actors,
+            deleted_actors: Vec::new(),
+            updated_actors: updated,
+        }
+    }
+
+    #[test]
+    fn test_process_frame_ball_and_car_state() {
+        let replay = replay();
+        let mut state = NetworkState::new(&replay);
+        let snapshot = state.process_frame(&frame(
+            1.0,
+            vec![spawn_actor(BALL, BALL_OBJECT), spawn()],
+            vec![
+                rigid_body_at(BALL, (100.0, 200.0, 300.0)),
+                rigid_body_at(CAR, (-500.0, -2000.0, 17.0)),
+                boost(255),
+            ],
+        ));
+        assert_eq!(snapshot.ball.position, (100.0, 200.0, 300.0));
+        assert_eq!(snapshot.players.len(), 1);
+        let car = &snapshot.players[0];
+        assert_eq!((car.slot, car.team), (0, 0));
+        assert_eq!(car.position, (-500.0, -2000.0, 17.0));
+        assert_eq!(car.velocity, (100.0, 0.0, 0.0));
+        assert_eq!(car.rotation_source, RotationSource::RigidBody);
+        assert_eq!(car.boost_amount, 100);
+
+        // Deleting the ball puts it back at rest; the car keeps its state.
+        let mut deleted = frame(1.1, Vec::new(), Vec::new());
+        deleted.deleted_actors.push(ActorId(BALL));
+        let snapshot = state.process_frame(&deleted);
+        assert_eq!(snapshot.ball.position, BALL_REST_POSITION);
+        assert_eq!(snapshot.players[0].position, (-500.0, -2000.0, 17.0));
+    }
+
+    /// Car position after one frame carrying a RigidBody at y = 1000 and a Location at
+    /// y = 2000, in the given order.
+    fn position_with_both(priority: PhysicsPriority, location_first: bool) -> (f32, f32, f32) {
+        let replay = replay();
+        let mut state = NetworkState::new(&replay);
+        state.set_physics_priority(priority);
+        let body = rigid_body(1000.0);
+        let location = update(
+            CAR,
+            LOCATION,
+            Attribute::Location(vector((0.0, 2000.0, 17.0))),
+        );
+        let updated = if location_first {
+            vec![location, body]
+        } else {
+            vec![body, location]
+        };
+        let snapshot = state.process_frame(&frame(1.0, vec![spawn()], updated));
+        assert_eq!(snapshot.players.len(), 1);
+        // Velocity always comes from the RigidBody.
+        assert_eq!(snapshot.players[0].velocity, (100.0, 0.0, 0.0));
+        snapshot.players[0].position
+    }
+
+    #[test]
+    fn test_rigid_body_priority_ignores_location() {
+        for location_first in [false, true] {
+            assert_eq!(
+                position_with_both(PhysicsPriority::RigidBody, location_first),
+                (0.0, 1000.0, 17.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_location_priority_overrides_rigid_body() {
+        for location_first in [false, true] {
+            assert_eq!(
+                position_with_both(PhysicsPriority::Location, location_first),
+                (0.0, 2000.0, 17.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_spectator_object_names() {
+        for name in ["archetypes.car.car_spectator", "tagame.freecam_ta"] {
+            assert!(is_spectator_object_name_lower(name));
+            let kind = classify_object_name_lower(name);
+            assert!(!kind.is_car && !kind.is_ball);
+        }
+        assert!(!is_spectator_object_name_lower(
+            "archetypes.car.car_default"
+        ));
+    }
+
+    #[test]
+    fn test_spectator_skipped_and_unclassified_inferred() {
+        let mut replay = replay();
+        let player = vec![
+            ("Name".to_string(), HeaderProp::Str("A".to_string())),
+            ("Team".to_string(), HeaderProp::Int(0)),
+        ];
+        replay.properties = vec![("PlayerStats".to_string(), HeaderProp::Array(vec![player]))];
+        let mut state = NetworkState::new(&replay);
+        // A moving spectator is never enrolled, even with a slot free.
+        let spectator = state.process_frame(&frame(
+            1.0,
+            vec![spawn_actor(CAR, SPECTATOR_OBJECT)],
+            vec![rigid_body(-1000.0)],
+        ));
+        assert!(spectator.players.is_empty());
+        assert_eq!(state.inferred_player_count(), 0);
+
+        let mut deleted = frame(1.1, Vec::new(), Vec::new());
+        deleted.deleted_actors.push(ActorId(CAR));
+        state.process_frame(&deleted);
+        // An unclassified actor takes the free slot by fallback and is tagged inferred.
+        let inferred = state.process_frame(&frame(
+            1.2,
+            vec![spawn_actor(CAR, UNCLASSIFIED_OBJECT)],
+            vec![rigid_body(-1000.0)],
+        ));
+        assert_eq!(inferred.players.len(), 1);
+        assert_eq!(inferred.players[0].slot, 0);
+        assert!(inferred.players[0].inferred);
+        assert_eq!(state.inferred_player_count(), 1);
+    }
+
+    #[test]
+    fn test_component_wheel_contact_overrides_height() {
+        assert!(is_wheel_contact_attribute("TAGame.Vehicle_TA:bOnGround"));
+        assert!(!is_wheel_contact_attribute("TAGame.Vehicle_TA:bDriving"));
+        let replay = replay();
+        let mut state = NetworkState::new(&replay);
+        let vehicle = ActiveActor {
+            active: false,
+            actor: ActorId(CAR),
+        };
+        let spawned = state.process_frame(&frame(
+            1.0,
+            vec![spawn(), spawn_actor(COMPONENT, JUMP_COMPONENT_OBJECT)],
+            vec![
+                rigid_body(-1000.0),
+                update(
+                    COMPONENT,
+                    COMPONENT_VEHICLE,
+                    Attribute::ActiveActor(vehicle),
+                ),
+            ],
+        ));
+        // Resting height, no replicated contact: the height heuristic.
+        assert_eq!(
+            spawned.players[0].on_ground(),
+            (true, GroundSource::ZHeuristic)
+        );
+
+        // The component reports the wheels off the floor for its car.
+        let contact = |on: bool| update(COMPONENT, WHEEL_CONTACT, Attribute::Boolean(on));
+        let lifted = state.process_frame(&frame(1.1, Vec::new(), vec![contact(false)]));
+        let (on_ground, source) = lifted.players[0].on_ground();
+        assert!(!on_ground);
+        assert_eq!(source.as_str(), "wheel_contact");
+        let landed = state.process_frame(&frame(1.2, Vec::new(), vec![contact(true)]));
+        assert_eq!(
+            landed.players[0].on_ground(),
+            (true, GroundSource::WheelContact)
+        );
+    }
+
+    #[test]
+    fn test_team_waits_for_first_position() {
+        let replay = replay();
+        let mut state = NetworkState::new(&replay);
+        // Spawned without a trajectory location: not guessed onto a team yet.
+        let spawned = state.process_frame(&frame(1.0, vec![spawn()], vec![boost(85)]));
+        assert!(spawned.players.is_empty());
+        // The first RigidBody puts it in the orange half.
+        let moved = state.process_frame(&frame(1.1, Vec::new(), vec![rigid_body(3000.0)]));
+        assert_eq!(moved.players.len(), 1);
+        assert_eq!(moved.players[0].team, 1);
+    }
+
+    #[test]
+    fn test_demolition_clears_on_respawn() {
+        let replay = replay();
+        let mut state = NetworkState::new(&replay);
+        let mut observe = |nf: Frame| {
+            let snapshot = state.process_frame(&nf);
+            (
+                snapshot.players[0].is_demolished,
+                snapshot.demolitions.len(),
+            )
+        };
+        assert_eq!(
+            observe(frame(1.0, vec![spawn()], vec![rigid_body(-1000.0)])),
+            (false, 0)
+        );
+        assert_eq!(
+            observe(frame(1.1, Vec::new(), vec![demolish(2000.0)])),
+            (true, 1)
+        );
+        // The wreck's own physics and the re-replicated payload keep it demolished.
+        assert_eq!(
+            observe(frame(
+                1.2,
+                Vec::new(),
+                vec![rigid_body(-1000.0), demolish(2000.0)]
+            )),
+            (true, 0)
+        );
+        // Respawned at a kickoff spot; a later copy of the old payload is not a new demo.
+        assert_eq!(
+            observe(frame(4.1, Vec::new(), vec![rigid_body(-4608.0)])),
+            (false, 0)
+        );
+        assert_eq!(
+            observe(frame(4.2, Vec::new(), vec![demolish(2000.0)])),
+            (false, 0)
+        );
+        // A second demolition, then the respawn re-creates the car actor.
+        assert_eq!(
+            observe(frame(5.0, Vec::new(), vec![demolish(1500.0)])),
+            (true, 1)
+        );
+        assert_eq!(observe(frame(8.0, vec![spawn()], Vec::new())), (false, 0));
+    }
+}
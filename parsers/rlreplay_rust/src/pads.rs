@@ -1,8 +1,11 @@
-use crate::arena_tables::{lookup_arena_slug, pad_table_for_slug, snap_to_pad, ArenaPadDef};
+use crate::arena_tables::{
+    lookup_arena_slug, overlapping_pad, pad_respawn_for_slug, pad_table_for_slug, snap_to_pad,
+    ArenaPadDef, PadRespawnTimes, SOCCAR_PAD_RESPAWN,
+};
 use std::collections::{HashMap, VecDeque};
 use std::env;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PadEventStatus {
     Collected,
     Respawned,
@@ -36,10 +39,83 @@ pub struct PadEvent {
     pub instigator_actor_id: Option<i32>,
     pub resolved_actor_id: Option<i32>,
     pub status: PadEventStatus,
+    /// Seconds until this pad is available again after a pickup, from the arena table.
+    pub respawn_s: f32,
     /// Snap distance from observed position to canonical pad centre (uu).
     pub snap_distance: Option<f32>,
     /// Alias for snap_distance, exposed as snap_error_uu in Python payload.
     pub snap_error_uu: Option<f32>,
+    /// Seconds the pickup waited in the pending queue for its pad definition and position
+    /// to resolve: the frame it was emitted on minus `timestamp`.
+    pub emission_latency_s: f32,
+}
+
+/// Summary of `PadEvent::emission_latency_s` over a replay.
+#[derive(Clone, Debug, Default)]
+pub struct PadLatencyStats {
+    pub events: usize,
+    /// Events emitted on a later frame than their own.
+    pub delayed_events: usize,
+    pub max_s: f32,
+    total_s: f32,
+}
+
+impl PadLatencyStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, event: &PadEvent) {
+        self.events += 1;
+        if event.emission_latency_s > 0.0 {
+            self.delayed_events += 1;
+        }
+        self.max_s = self.max_s.max(event.emission_latency_s);
+        self.total_s += event.emission_latency_s;
+    }
+
+    /// Mean latency in seconds; 0 without events.
+    pub fn mean_s(&self) -> f32 {
+        if self.events == 0 {
+            return 0.0;
+        }
+        self.total_s / self.events as f32
+    }
+}
+
+/// Window (seconds) within which a repeat of a pad's previous event status is treated as a
+/// re-trigger of that event. A genuine repeat needs a full respawn cycle in between (4 s for
+/// small pads), so this only removes flush duplicates.
+pub const PAD_EVENT_COALESCE_WINDOW_S: f32 = 0.5;
+
+/// Drops pad events that repeat the same pad's previous event status within
+/// `PAD_EVENT_COALESCE_WINDOW_S` of it, keeping the earliest.
+#[derive(Default)]
+pub struct PadEventCoalescer {
+    /// pad_id → status and timestamp of the last kept event.
+    last: HashMap<usize, (PadEventStatus, f32)>,
+}
+
+impl PadEventCoalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed events in stream order; false for a duplicate to drop.
+    pub fn keep(&mut self, event: &PadEvent) -> bool {
+        let window = PAD_EVENT_COALESCE_WINDOW_S;
+        let duplicate = self
+            .last
+            .get(&event.pad_id)
+            .is_some_and(|(status, timestamp)| {
+                *status == event.status && event.timestamp - timestamp <= window
+            });
+        if !duplicate {
+            self.last
+                .insert(event.pad_id, (event.status, event.timestamp));
+        }
+        !duplicate
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -75,6 +151,7 @@ impl PadInstance {
     }
 }
 
+#[derive(Clone)]
 pub struct PadRegistry {
     instances: HashMap<i32, PadInstance>,
     name_to_def: HashMap<String, ArenaPadDef>,
@@ -82,6 +159,10 @@ pub struct PadRegistry {
     arena_slug: &'static str,
     /// Pad table for the active arena (None for unsupported arenas).
     pad_table: Option<&'static [ArenaPadDef]>,
+    /// Pad respawn durations for the active arena (None for unsupported arenas).
+    pad_respawn: Option<PadRespawnTimes>,
+    /// Time of the network frame being processed, for `emission_latency_s`.
+    frame_time: f32,
     debug_enabled: bool,
 }
 
@@ -103,16 +184,24 @@ impl PadRegistry {
 
         let arena_slug = lookup_arena_slug(map_name).unwrap_or("unknown");
         let pad_table = pad_table_for_slug(arena_slug);
+        let pad_respawn = pad_respawn_for_slug(arena_slug);
 
         PadRegistry {
             instances: HashMap::new(),
             name_to_def: HashMap::new(),
             arena_slug,
             pad_table,
+            pad_respawn,
+            frame_time: 0.0,
             debug_enabled,
         }
     }
 
+    /// Call at the start of each network frame.
+    pub fn set_frame_time(&mut self, time: f32) {
+        self.frame_time = time;
+    }
+
     pub fn track_new_actor(&mut self, actor_id: i32, object_name: &str) {
         if !object_name.contains("VehiclePickup_Boost_TA") {
             return;
@@ -126,6 +215,12 @@ impl PadRegistry {
         self.instances.remove(&actor_id);
     }
 
+    /// Canonical id of the pad a car at `position` is overlapping, if any.
+    pub fn overlapping_pad_id(&self, position: (f32, f32, f32)) -> Option<usize> {
+        let table = self.pad_table?;
+        overlapping_pad(table, position.0, position.1, position.2).map(|pad| pad.id)
+    }
+
     pub fn update_position(&mut self, actor_id: i32, position: (f32, f32, f32)) -> Vec<PadEvent> {
         if let Some(instance) = self.instances.get_mut(&actor_id) {
             instance.position = Some(position);
@@ -206,6 +301,7 @@ impl PadRegistry {
         let mut ready: Vec<PadEvent> = Vec::new();
 
         let mut should_log = false;
+        let frame_time = self.frame_time;
         if let Some(instance) = self.instances.get_mut(&actor_id) {
             let can_emit = instance.pad_def.is_some() && instance.position.is_some();
             if !can_emit {
@@ -241,8 +337,13 @@ impl PadRegistry {
                     instigator_actor_id: pending.instigator_actor_id,
                     resolved_actor_id: pending.resolved_actor_id,
                     status,
+                    respawn_s: self
+                        .pad_respawn
+                        .unwrap_or(SOCCAR_PAD_RESPAWN)
+                        .for_pad(pad_def.is_big),
                     snap_distance: instance.snap_distance,
                     snap_error_uu: instance.snap_distance,
+                    emission_latency_s: (frame_time - pending.timestamp).max(0.0),
                 });
                 instance.last_state = Some(pending.raw_state);
                 instance.last_time = pending.timestamp;
@@ -286,3 +387,71 @@ impl PadRegistry {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(pad_id: usize, status: PadEventStatus, timestamp: f32) -> PadEvent {
+        PadEvent {
+            pad_id,
+            is_big: false,
+            pad_side: "mid",
+            arena: "soccar",
+            arena_supported: true,
+            object_name: String::new(),
+            position: (0.0, 0.0, 70.0),
+            timestamp,
+            raw_state: 1,
+            instigator_actor_id: None,
+            resolved_actor_id: None,
+            status,
+            respawn_s: 4.0,
+            snap_distance: None,
+            snap_error_uu: None,
+            emission_latency_s: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_coalesce_repeated_status() {
+        use PadEventStatus::{Collected, Respawned};
+        let mut coalescer = PadEventCoalescer::new();
+        let kept: Vec<bool> = [
+            event(3, Collected, 10.0),
+            event(3, Collected, 10.2),
+            event(5, Collected, 10.3),
+            event(3, Collected, 10.45),
+            event(3, Collected, 10.6),
+            event(3, Respawned, 14.0),
+            event(3, Collected, 14.1),
+        ]
+        .iter()
+        .map(|e| coalescer.keep(e))
+        .collect();
+        // Duplicates are measured from the kept event, so a slow re-trigger chain ends.
+        assert_eq!(kept, vec![true, false, true, false, true, true, true]);
+    }
+
+    #[test]
+    fn test_emission_latency_waits_for_position() {
+        let mut registry = PadRegistry::new_with_arena("Stadium_P");
+        registry.track_new_actor(7, "VehiclePickup_Boost_TA_1");
+        registry.set_frame_time(10.0);
+        // No position or fallback yet: the pickup is held back.
+        assert!(registry
+            .handle_pickup(7, 1, 10.0, Some(3), Some(3), None)
+            .is_empty());
+        registry.set_frame_time(10.25);
+        let events = registry.update_position(7, (0.0, -4240.0, 70.0));
+        assert_eq!(events.len(), 1);
+        assert!((events[0].emission_latency_s - 0.25).abs() < 1e-6);
+
+        let mut stats = PadLatencyStats::new();
+        stats.record(&events[0]);
+        stats.record(&event(3, PadEventStatus::Respawned, 14.0));
+        assert_eq!(stats.events, 2);
+        assert_eq!(stats.delayed_events, 1);
+        assert!((stats.max_s - 0.25).abs() < 1e-6);
+        assert!((stats.mean_s() - 0.125).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,122 @@
+/// Raw attribute passthrough for `iter_frames(raw_attribute_objects=...)`: the decoded
+/// attribute updates of actors whose object name contains one of a set of substrings,
+/// for data the frame model does not cover yet.
+use boxcars::{Attribute, Frame};
+use std::collections::HashMap;
+
+/// One attribute update on a matching actor.
+pub struct RawUpdate<'a> {
+    pub actor_id: i32,
+    pub object_name: &'a str,
+    /// Attribute property name, e.g. "TAGame.Vehicle_TA:ReplicatedThrottle".
+    pub attribute_name: &'a str,
+    pub attribute: &'a Attribute,
+}
+
+pub struct RawAttributeFilter<'a> {
+    /// Object name substrings, matched case-sensitively.
+    substrings: Vec<String>,
+    /// Live matching actor → its object name.
+    actors: HashMap<i32, &'a str>,
+}
+
+impl<'a> RawAttributeFilter<'a> {
+    pub fn new(substrings: Vec<String>) -> Self {
+        RawAttributeFilter {
+            substrings,
+            actors: HashMap::new(),
+        }
+    }
+
+    /// Feed the next frame; returns its updates on matching actors, in stream order.
+    pub fn observe(&mut self, nf: &'a Frame, objects: &'a [String]) -> Vec<RawUpdate<'a>> {
+        for deleted in &nf.deleted_actors {
+            self.actors.remove(&(*deleted).into());
+        }
+        for na in &nf.new_actors {
+            let oid: usize = na.object_id.into();
+            if let Some(name) = objects.get(oid) {
+                if self.substrings.iter().any(|s| name.contains(s.as_str())) {
+                    self.actors.insert(na.actor_id.into(), name.as_str());
+                }
+            }
+        }
+        nf.updated_actors
+            .iter()
+            .filter_map(|upd| {
+                let actor_id: i32 = upd.actor_id.into();
+                let object_name = self.actors.get(&actor_id)?;
+                let oid: usize = upd.object_id.into();
+                Some(RawUpdate {
+                    actor_id,
+                    object_name,
+                    attribute_name: objects.get(oid).map(|s| s.as_str()).unwrap_or(""),
+                    attribute: &upd.attribute,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boxcars::{ActorId, NewActor, ObjectId, StreamId, Trajectory, UpdatedAttribute};
+
+    fn new_actor(actor: i32, object: i32) -> NewActor {
+        NewActor {
+            actor_id: ActorId(actor),
+            name_id: None,
+            object_id: ObjectId(object),
+            initial_trajectory: Trajectory {
+                location: None,
+                rotation: None,
+            },
+        }
+    }
+
+    fn update(actor: i32, object: i32, value: bool) -> UpdatedAttribute {
+        UpdatedAttribute {
+            actor_id: ActorId(actor),
+            stream_id: StreamId(0),
+            object_id: ObjectId(object),
+            attribute: Attribute::Boolean(value),
+        }
+    }
+
+    #[test]
+    fn test_raw_attribute_filter() {
+        let objects: Vec<String> = [
+            "Archetypes.Car.Car_Default",
+            "Archetypes.Ball.Ball_Default",
+            "TAGame.Vehicle_TA:bDriving",
+        ]
+        .map(String::from)
+        .to_vec();
+        let frames = [
+            Frame {
+                time: 1.0,
+                delta: 0.03,
+                new_actors: vec![new_actor(5, 0), new_actor(6, 1)],
+                deleted_actors: Vec::new(),
+                updated_actors: vec![update(5, 2, true), update(6, 2, false)],
+            },
+            Frame {
+                time: 1.03,
+                delta: 0.03,
+                new_actors: Vec::new(),
+                deleted_actors: vec![ActorId(5)],
+                updated_actors: vec![update(5, 2, false)],
+            },
+        ];
+        let mut filter = RawAttributeFilter::new(vec!["Car_".to_string()]);
+        let updates = filter.observe(&frames[0], &objects);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].actor_id, 5);
+        assert_eq!(updates[0].object_name, "Archetypes.Car.Car_Default");
+        assert_eq!(updates[0].attribute_name, "TAGame.Vehicle_TA:bDriving");
+        assert!(matches!(updates[0].attribute, Attribute::Boolean(true)));
+        // Deleted actors stop matching.
+        assert!(filter.observe(&frames[1], &objects).is_empty());
+    }
+}
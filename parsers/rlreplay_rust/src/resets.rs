@@ -0,0 +1,205 @@
+/// Flip reset detection: an airborne car getting its wheels onto the ball, which restores
+/// its flip.
+///
+/// A car is in reset contact on a live frame when it is airborne (`Surface::Air`), its
+/// rotation is known, its centre is within `FLIP_RESET_RANGE_UU` of the ball centre, its
+/// down (wheels) axis points at the ball to within `FLIP_RESET_MIN_ALIGNMENT` and the ball
+/// is at least `FLIP_RESET_MIN_BALL_Z` high. A reset is the first frame of such a contact.
+/// It is `used` when the car dodges on a later frame within `FLIP_RESET_USE_WINDOW_S`
+/// before touching a surface; landing, a demolition or play stopping resolves it as unused.
+use crate::geometry::{classify_surface, distance, quat_up, Surface, BALL_RADIUS};
+use crate::network::{FrameSnapshot, PlayerSnapshot};
+use std::collections::{BTreeMap, HashSet};
+
+/// Car-centre to ball-centre distance (uu) for wheels-on-ball contact: ball radius plus the
+/// car's half-height, with slack for the 30 Hz sampling.
+pub const FLIP_RESET_RANGE_UU: f32 = 160.0;
+/// Min ball centre height (uu) for a reset: ball radius, an Octane's height (about 36 uu)
+/// and a margin. Lower, the car is riding a ball near the floor (a dribble), not resetting.
+pub const FLIP_RESET_MIN_BALL_Z: f32 = BALL_RADIUS + 36.0 + 50.0;
+/// Min cosine between the car's down axis and the car-to-ball direction.
+pub const FLIP_RESET_MIN_ALIGNMENT: f32 = 0.8;
+/// Max seconds after the reset for a dodge to count as using it.
+pub const FLIP_RESET_USE_WINDOW_S: f32 = 3.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlipReset {
+    pub slot: usize,
+    pub timestamp: f32,
+    /// Ball centre height (uu) at the reset.
+    pub ball_height: f32,
+    pub used: bool,
+}
+
+#[derive(Default)]
+pub struct FlipResetDetector {
+    /// Slot → reset awaiting a dodge.
+    pending: BTreeMap<usize, FlipReset>,
+    /// Slots in reset contact on the previous frame.
+    in_contact: HashSet<usize>,
+}
+
+impl FlipResetDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame; returns the resets resolved on it.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> Vec<FlipReset> {
+        if !frame.is_live {
+            self.in_contact.clear();
+            return self.finish();
+        }
+        let t = frame.timestamp;
+        let mut out = Vec::new();
+        let mut in_contact = HashSet::new();
+        for player in &frame.players {
+            let slot = player.slot;
+            if let Some(reset) = self.pending.get(&slot).copied() {
+                let landed =
+                    player.is_demolished || classify_surface(player.position) != Surface::Air;
+                if player.is_dodging && t - reset.timestamp <= FLIP_RESET_USE_WINDOW_S {
+                    out.push(FlipReset {
+                        used: true,
+                        ..reset
+                    });
+                    self.pending.remove(&slot);
+                } else if landed || t - reset.timestamp > FLIP_RESET_USE_WINDOW_S {
+                    out.push(reset);
+                    self.pending.remove(&slot);
+                }
+            }
+            if !is_reset_contact(frame, player) {
+                continue;
+            }
+            in_contact.insert(slot);
+            if !self.in_contact.contains(&slot) && !self.pending.contains_key(&slot) {
+                self.pending.insert(
+                    slot,
+                    FlipReset {
+                        slot,
+                        timestamp: t,
+                        ball_height: frame.ball.position.2,
+                        used: false,
+                    },
+                );
+            }
+        }
+        self.in_contact = in_contact;
+        out
+    }
+
+    /// Close out pending resets as unused.
+    pub fn finish(&mut self) -> Vec<FlipReset> {
+        std::mem::take(&mut self.pending).into_values().collect()
+    }
+}
+
+fn is_reset_contact(frame: &FrameSnapshot, car: &PlayerSnapshot) -> bool {
+    let Some(rotation) = car.rotation else {
+        return false;
+    };
+    if car.is_demolished || classify_surface(car.position) != Surface::Air {
+        return false;
+    }
+    let ball = frame.ball.position;
+    if ball.2 < FLIP_RESET_MIN_BALL_Z {
+        return false;
+    }
+    let gap = distance(car.position, ball);
+    if gap > FLIP_RESET_RANGE_UU || gap == 0.0 {
+        return false;
+    }
+    let up = quat_up(rotation);
+    let to_ball = (
+        (ball.0 - car.position.0) / gap,
+        (ball.1 - car.position.1) / gap,
+        (ball.2 - car.position.2) / gap,
+    );
+    // The wheels face along -up.
+    -(up.0 * to_ball.0 + up.1 * to_ball.1 + up.2 * to_ball.2) >= FLIP_RESET_MIN_ALIGNMENT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, ball, player};
+    use crate::network::{BallSnapshot, RotationSource};
+
+    /// Upside down: half a turn about the forward (x) axis.
+    const UPSIDE_DOWN: (f32, f32, f32, f32) = (1.0, 0.0, 0.0, 0.0);
+
+    fn car(z: f32, rotation: (f32, f32, f32, f32), is_dodging: bool) -> PlayerSnapshot {
+        PlayerSnapshot {
+            position: (0.0, 0.0, z),
+            rotation: Some(rotation),
+            rotation_source: RotationSource::RigidBody,
+            is_dodging,
+            ..player(0)
+        }
+    }
+
+    fn frame_with_ball(timestamp: f32, ball_z: f32, player: PlayerSnapshot) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position: (0.0, 0.0, ball_z),
+                ..ball()
+            },
+            ..test_support::frame(timestamp, vec![player])
+        }
+    }
+
+    fn frame(timestamp: f32, player: PlayerSnapshot) -> FrameSnapshot {
+        frame_with_ball(timestamp, 1000.0, player)
+    }
+
+    #[test]
+    fn test_used_flip_reset() {
+        let mut detector = FlipResetDetector::new();
+        let mut found = Vec::new();
+        // Upright under the ball, roof into it: not a reset.
+        found.extend(detector.observe(&frame(1.0, car(880.0, (0.0, 0.0, 0.0, 1.0), false))));
+        // Wheels up into the ball for two frames, then a dodge.
+        found.extend(detector.observe(&frame(1.1, car(880.0, UPSIDE_DOWN, false))));
+        found.extend(detector.observe(&frame(1.2, car(870.0, UPSIDE_DOWN, false))));
+        assert!(found.is_empty());
+        found.extend(detector.observe(&frame(1.8, car(600.0, UPSIDE_DOWN, true))));
+        assert_eq!(
+            found,
+            vec![FlipReset {
+                slot: 0,
+                timestamp: 1.1,
+                ball_height: 1000.0,
+                used: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unused_flip_reset() {
+        let mut detector = FlipResetDetector::new();
+        let mut found = Vec::new();
+        found.extend(detector.observe(&frame(1.0, car(880.0, UPSIDE_DOWN, false))));
+        // Lands on the floor without dodging.
+        found.extend(detector.observe(&frame(2.0, car(17.0, UPSIDE_DOWN, false))));
+        assert_eq!(found.len(), 1);
+        assert!(!found[0].used);
+        assert!(detector.finish().is_empty());
+    }
+
+    #[test]
+    fn test_no_reset_on_a_low_ball() {
+        let mut detector = FlipResetDetector::new();
+        let upright = (0.0, 0.0, 0.0, 1.0);
+        // Wheels down on top of a ball rolling along the floor: a dribble.
+        let rolling = ball().position.2;
+        let on_top = car(rolling + 140.0, upright, false);
+        assert!(detector
+            .observe(&frame_with_ball(1.0, rolling, on_top))
+            .is_empty());
+        assert!(detector.finish().is_empty());
+        // The same contact with the ball up in the air is a reset.
+        detector.observe(&frame_with_ball(2.0, 1000.0, car(1140.0, upright, false)));
+        assert_eq!(detector.finish().len(), 1);
+    }
+}
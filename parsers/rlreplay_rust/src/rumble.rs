@@ -0,0 +1,268 @@
+/// Rumble power-up (item) tracking.
+///
+/// Rumble items are replicated as `Archetypes.SpecialPickups.SpecialPickup_*` actors.
+/// A car points at its `RumblePickups_TA` actor via `Car_TA:RumblePickups`; that actor
+/// in turn points at the currently held item via `RumblePickups_TA:AttachedPickup`.
+/// The item itself is a car component, so it also replicates `CarComponent_TA:Vehicle`
+/// (owning car) and `CarComponent_TA:ReplicatedActive` (odd values = activated).
+///
+/// Events:
+///   "granted" — first time an item actor is attached to a car
+///   "used"    — first activation of that item actor
+use boxcars::{Attribute, Frame};
+use std::collections::{HashMap, HashSet};
+
+const ATTR_CAR_RUMBLE_PICKUPS: &str = "TAGame.Car_TA:RumblePickups";
+const ATTR_ATTACHED_PICKUP: &str = "TAGame.RumblePickups_TA:AttachedPickup";
+const ATTR_PICKUP_INFO: &str = "TAGame.RumblePickups_TA:PickupInfo";
+const ATTR_COMPONENT_VEHICLE: &str = "TAGame.CarComponent_TA:Vehicle";
+const ATTR_COMPONENT_ACTIVE: &str = "TAGame.CarComponent_TA:ReplicatedActive";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RumbleEventKind {
+    Granted,
+    Used,
+}
+
+impl RumbleEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RumbleEventKind::Granted => "granted",
+            RumbleEventKind::Used => "used",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RumbleItemEvent {
+    pub timestamp: f32,
+    /// Car actor holding the item, when known.
+    pub car_actor_id: Option<i32>,
+    pub item: String,
+    pub event: RumbleEventKind,
+}
+
+/// Short item name for a Rumble pickup archetype, e.g.
+/// `Archetypes.SpecialPickups.SpecialPickup_BallFreeze` → `BallFreeze`.
+/// Returns `None` for anything that is not a Rumble item.
+pub fn rumble_item_name(object_name: &str) -> Option<&str> {
+    let idx = object_name.find("SpecialPickup_")?;
+    let tail = &object_name[idx + "SpecialPickup_".len()..];
+    let tail = tail.strip_suffix("_TA").unwrap_or(tail);
+    if tail.is_empty() || tail == "TA" {
+        None
+    } else {
+        Some(tail)
+    }
+}
+
+#[derive(Default)]
+pub struct RumbleTracker {
+    /// Item actor → short item name.
+    items: HashMap<i32, String>,
+    /// RumblePickups_TA actor → owning car actor.
+    pickups_owner: HashMap<i32, i32>,
+    /// Item actor → car actor.
+    item_car: HashMap<i32, i32>,
+    granted: HashSet<i32>,
+    used: HashSet<i32>,
+}
+
+impl RumbleTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn grant(
+        &mut self,
+        item_actor: i32,
+        car: Option<i32>,
+        timestamp: f32,
+    ) -> Option<RumbleItemEvent> {
+        if let Some(car) = car {
+            self.item_car.insert(item_actor, car);
+        }
+        let item = self.items.get(&item_actor)?.clone();
+        if !self.granted.insert(item_actor) {
+            return None;
+        }
+        Some(RumbleItemEvent {
+            timestamp,
+            car_actor_id: self.item_car.get(&item_actor).copied(),
+            item,
+            event: RumbleEventKind::Granted,
+        })
+    }
+
+    pub fn process_frame(&mut self, nf: &Frame, objects: &[String]) -> Vec<RumbleItemEvent> {
+        let mut out: Vec<RumbleItemEvent> = Vec::new();
+
+        for deleted in &nf.deleted_actors {
+            let aid: i32 = (*deleted).into();
+            self.items.remove(&aid);
+            self.item_car.remove(&aid);
+            self.pickups_owner.remove(&aid);
+            self.granted.remove(&aid);
+            self.used.remove(&aid);
+        }
+
+        for na in &nf.new_actors {
+            let oid: usize = na.object_id.into();
+            if let Some(item) = objects.get(oid).and_then(|name| rumble_item_name(name)) {
+                self.items.insert(na.actor_id.into(), item.to_string());
+            }
+        }
+
+        for upd in &nf.updated_actors {
+            let aid: i32 = upd.actor_id.into();
+            let oid: usize = upd.object_id.into();
+            let attr_name = objects.get(oid).map(|s| s.as_str()).unwrap_or("");
+            match (&upd.attribute, attr_name) {
+                (Attribute::ActiveActor(active), ATTR_CAR_RUMBLE_PICKUPS) if active.active => {
+                    self.pickups_owner.insert(active.actor.into(), aid);
+                }
+                (Attribute::ActiveActor(active), ATTR_ATTACHED_PICKUP) if active.active => {
+                    let car = self.pickups_owner.get(&aid).copied();
+                    out.extend(self.grant(active.actor.into(), car, nf.time));
+                }
+                (Attribute::PickupInfo(info), ATTR_PICKUP_INFO)
+                    if info.active && !info.items_are_preview =>
+                {
+                    let car = self.pickups_owner.get(&aid).copied();
+                    out.extend(self.grant(info.actor.into(), car, nf.time));
+                }
+                (Attribute::ActiveActor(active), ATTR_COMPONENT_VEHICLE)
+                    if active.active && self.items.contains_key(&aid) =>
+                {
+                    out.extend(self.grant(aid, Some(active.actor.into()), nf.time));
+                }
+                (Attribute::Byte(state), ATTR_COMPONENT_ACTIVE)
+                    if state % 2 == 1 && self.items.contains_key(&aid) && self.used.insert(aid) =>
+                {
+                    // An item can be activated in the same frame it is handed out.
+                    out.extend(self.grant(aid, None, nf.time));
+                    out.push(RumbleItemEvent {
+                        timestamp: nf.time,
+                        car_actor_id: self.item_car.get(&aid).copied(),
+                        item: self.items[&aid].clone(),
+                        event: RumbleEventKind::Used,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boxcars::{
+        ActiveActor, ActorId, NewActor, ObjectId, StreamId, Trajectory, UpdatedAttribute,
+    };
+
+    const CAR: i32 = 10;
+    const PICKUPS: i32 = 20;
+    const ITEM: i32 = 30;
+
+    fn objects() -> Vec<String> {
+        [
+            "Archetypes.SpecialPickups.SpecialPickup_BallFreeze",
+            ATTR_CAR_RUMBLE_PICKUPS,
+            ATTR_ATTACHED_PICKUP,
+            ATTR_COMPONENT_ACTIVE,
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
+    fn update(actor: i32, object: i32, attribute: Attribute) -> UpdatedAttribute {
+        UpdatedAttribute {
+            actor_id: ActorId(actor),
+            stream_id: StreamId(0),
+            object_id: ObjectId(object),
+            attribute,
+        }
+    }
+
+    fn link(actor: i32) -> Attribute {
+        Attribute::ActiveActor(ActiveActor {
+            active: true,
+            actor: ActorId(actor),
+        })
+    }
+
+    fn frame(time: f32, new_actors: Vec<NewActor>, updated: Vec<UpdatedAttribute>) -> Frame {
+        Frame {
+            time,
+            delta: 0.1,
+            new_actors,
+            deleted_actors: Vec::new(),
+            updated_actors: updated,
+        }
+    }
+
+    #[test]
+    fn test_item_granted_then_used() {
+        let objects = objects();
+        let mut tracker = RumbleTracker::new();
+        let item = NewActor {
+            actor_id: ActorId(ITEM),
+            name_id: None,
+            object_id: ObjectId(0),
+            initial_trajectory: Trajectory {
+                location: None,
+                rotation: None,
+            },
+        };
+        let granted = tracker.process_frame(
+            &frame(
+                1.0,
+                vec![item],
+                vec![
+                    update(CAR, 1, link(PICKUPS)),
+                    update(PICKUPS, 2, link(ITEM)),
+                ],
+            ),
+            &objects,
+        );
+        assert_eq!(granted.len(), 1);
+        assert_eq!(granted[0].event, RumbleEventKind::Granted);
+        assert_eq!(granted[0].item, "BallFreeze");
+        assert_eq!(granted[0].car_actor_id, Some(CAR));
+        // Re-replicated attachment is not a second grant.
+        let repeat = frame(1.5, Vec::new(), vec![update(PICKUPS, 2, link(ITEM))]);
+        assert!(tracker.process_frame(&repeat, &objects).is_empty());
+
+        // Odd ReplicatedActive values are activations; only the first counts.
+        let used = tracker.process_frame(
+            &frame(2.0, Vec::new(), vec![update(ITEM, 3, Attribute::Byte(1))]),
+            &objects,
+        );
+        assert_eq!(used.len(), 1);
+        assert_eq!(used[0].event, RumbleEventKind::Used);
+        assert_eq!(used[0].car_actor_id, Some(CAR));
+        let again = frame(2.1, Vec::new(), vec![update(ITEM, 3, Attribute::Byte(3))]);
+        assert!(tracker.process_frame(&again, &objects).is_empty());
+    }
+
+    #[test]
+    fn test_rumble_item_name_archetype() {
+        assert_eq!(
+            rumble_item_name("Archetypes.SpecialPickups.SpecialPickup_BallFreeze"),
+            Some("BallFreeze")
+        );
+        assert_eq!(
+            rumble_item_name("TAGame.SpecialPickup_Tornado_TA"),
+            Some("Tornado")
+        );
+    }
+
+    #[test]
+    fn test_rumble_item_name_non_item() {
+        assert_eq!(rumble_item_name("Archetypes.Car.Car_Default"), None);
+        assert_eq!(rumble_item_name("TAGame.VehiclePickup_Boost_TA"), None);
+    }
+}
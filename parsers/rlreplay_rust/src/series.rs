@@ -0,0 +1,96 @@
+/// Roster comparison for grouping replays into a series (the games of one best-of).
+///
+/// Players are matched by online id, or by name for entries without one (bots, offline
+/// and LAN games). Teams are ignored since sides can swap between games. Similarity is the
+/// Jaccard index of the two player sets: shared players over all distinct players.
+use crate::header::RosterEntry;
+
+#[derive(Clone, Debug)]
+pub struct RosterComparison<'a> {
+    /// Players in both rosters, as listed in the first.
+    pub shared: Vec<&'a RosterEntry>,
+    pub only_a: Vec<&'a RosterEntry>,
+    pub only_b: Vec<&'a RosterEntry>,
+}
+
+impl RosterComparison<'_> {
+    /// Shared players over distinct players, 0..=1; 0 when both rosters are empty.
+    pub fn similarity(&self) -> f32 {
+        let union = self.shared.len() + self.only_a.len() + self.only_b.len();
+        if union == 0 {
+            return 0.0;
+        }
+        self.shared.len() as f32 / union as f32
+    }
+
+    /// Whether the similarity reaches `threshold`; never for two empty rosters.
+    pub fn same_series(&self, threshold: f32) -> bool {
+        !self.shared.is_empty() && self.similarity() >= threshold
+    }
+}
+
+fn player_key(entry: &RosterEntry) -> (bool, &str) {
+    match &entry.online_id {
+        Some(id) => (true, id.as_str()),
+        None => (false, entry.name.as_str()),
+    }
+}
+
+pub fn compare_rosters<'a>(a: &'a [RosterEntry], b: &'a [RosterEntry]) -> RosterComparison<'a> {
+    let in_roster = |roster: &[RosterEntry], entry: &RosterEntry| {
+        roster.iter().any(|e| player_key(e) == player_key(entry))
+    };
+    let (shared, only_a) = a.iter().partition(|entry| in_roster(b, entry));
+    let only_b = b.iter().filter(|entry| !in_roster(a, entry)).collect();
+    RosterComparison {
+        shared,
+        only_a,
+        only_b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, team: i64, online_id: Option<&str>) -> RosterEntry {
+        RosterEntry {
+            name: name.to_string(),
+            team,
+            online_id: online_id.map(str::to_string),
+            platform: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_rosters() {
+        let game1 = [
+            entry("alpha", 0, Some("1")),
+            entry("bravo", 0, Some("2")),
+            entry("charlie", 1, Some("3")),
+            entry("Bot", 1, None),
+        ];
+        // Sides swapped, one renamed, one substitute.
+        let game2 = [
+            entry("alpha", 1, Some("1")),
+            entry("bravo_new", 1, Some("2")),
+            entry("delta", 0, Some("4")),
+            entry("Bot", 0, None),
+        ];
+        let comparison = compare_rosters(&game1, &game2);
+        let names = |entries: &[&RosterEntry]| -> Vec<String> {
+            entries.iter().map(|e| e.name.clone()).collect()
+        };
+        assert_eq!(names(&comparison.shared), ["alpha", "bravo", "Bot"]);
+        assert_eq!(names(&comparison.only_a), ["charlie"]);
+        assert_eq!(names(&comparison.only_b), ["delta"]);
+        assert_eq!(comparison.similarity(), 0.6);
+        assert!(!comparison.same_series(1.0));
+        assert!(comparison.same_series(0.5));
+
+        let same = compare_rosters(&game1, &game1);
+        assert_eq!(same.similarity(), 1.0);
+        assert!(same.same_series(1.0));
+        assert!(!compare_rosters(&[], &[]).same_series(0.0));
+    }
+}
@@ -0,0 +1,332 @@
+/// Shot detection and "xG-lite" shot quality.
+///
+/// A shot is a touch that leaves the ball moving at `SHOT_MIN_SPEED_UU_S` or faster on a
+/// straight (x, y) line that crosses the opponent goal line inside the goal mouth, widened
+/// by the ball radius (`BALL_RADIUS` unless built `with_physics`). Height and gravity are
+/// ignored, so lobs over the crossbar still count. A run of consecutive touches by one
+/// player (a dribble, a double touch) makes at most one shot, at its first goalward touch;
+/// the run ends when another player touches the ball or play stops.
+///
+/// Shot quality is a closed-form logistic heuristic, not a trained model:
+///
+///   quality = 1 / (1 + exp(-(intercept + speed * v / 1000 + distance * d / 1000 + angle * a)))
+///
+/// where v is the ball speed (uu/s), d the (x, y) distance from the ball to the centre of
+/// the target goal line (uu), and a the angle (radians, 0..π) between the ball's (x, y)
+/// heading and the direction to that point. Blue (team 0) shoots at the +y goal.
+///
+/// A save is a touch by the defending team on a ball whose straight (x, y) path before the
+/// touch (the previous frame's ball) was on target for its own goal and would have reached
+/// the goal line within `SAVE_HORIZON_S`, and whose path after the touch no longer is.
+use crate::geometry::{magnitude, Physics, BACK_WALL_Y, GOAL_HALF_WIDTH};
+use crate::network::{BallSnapshot, FrameSnapshot};
+use crate::touches::Touch;
+
+/// Minimum post-touch ball speed (uu/s) for a goalward touch to count as a shot.
+pub const SHOT_MIN_SPEED_UU_S: f32 = 1000.0;
+/// Max time (s) before the untouched ball would reach the goal line for a clearing touch to
+/// count as a save.
+pub const SAVE_HORIZON_S: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShotQualityCoefficients {
+    pub intercept: f32,
+    /// Per 1000 uu/s of ball speed.
+    pub speed: f32,
+    /// Per 1000 uu of distance to goal.
+    pub distance: f32,
+    /// Per radian off the direct line to goal.
+    pub angle: f32,
+}
+
+impl ShotQualityCoefficients {
+    /// 2000 uu/s, straight at goal from 2000 uu scores 0.5.
+    pub const DEFAULT: ShotQualityCoefficients = ShotQualityCoefficients {
+        intercept: 0.0,
+        speed: 1.0,
+        distance: -1.0,
+        angle: -3.0,
+    };
+
+    pub fn score(&self, features: &ShotFeatures) -> f32 {
+        let logit = self.intercept
+            + self.speed * features.speed / 1000.0
+            + self.distance * features.distance / 1000.0
+            + self.angle * features.angle;
+        1.0 / (1.0 + (-logit).exp())
+    }
+}
+
+impl Default for ShotQualityCoefficients {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Inputs to the shot quality score.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShotFeatures {
+    /// Ball speed (uu/s).
+    pub speed: f32,
+    /// (x, y) distance from the ball to the target goal line centre (uu).
+    pub distance: f32,
+    /// Angle (radians) between the ball heading and the direction to goal.
+    pub angle: f32,
+}
+
+impl ShotFeatures {
+    /// Features of a ball shot by `team`, i.e. towards the opponent goal.
+    pub fn new(position: (f32, f32, f32), velocity: (f32, f32, f32), team: i64) -> Self {
+        let goal_y = target_goal_y(team);
+        let to_goal = (-position.0, goal_y - position.1);
+        let distance = to_goal.0.hypot(to_goal.1);
+        let heading = velocity.0.hypot(velocity.1);
+        let angle = if distance > 0.0 && heading > 0.0 {
+            let cos = (to_goal.0 * velocity.0 + to_goal.1 * velocity.1) / (distance * heading);
+            cos.clamp(-1.0, 1.0).acos()
+        } else {
+            0.0
+        };
+        ShotFeatures {
+            speed: magnitude(velocity),
+            distance,
+            angle,
+        }
+    }
+}
+
+fn target_goal_y(team: i64) -> f32 {
+    if team == 0 {
+        BACK_WALL_Y
+    } else {
+        -BACK_WALL_Y
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shot {
+    pub timestamp: f32,
+    pub slot: usize,
+    pub team: i64,
+    pub features: ShotFeatures,
+}
+
+pub struct ShotDetector {
+    min_speed: f32,
+    ball_radius: f32,
+    /// Slot of the current run of touches, and whether the run has made a shot.
+    run: Option<(usize, bool)>,
+}
+
+impl Default for ShotDetector {
+    fn default() -> Self {
+        Self::with_physics(&Physics::SOCCAR)
+    }
+}
+
+impl ShotDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A detector for a ball of `physics.ball_radius`, which widens the goal mouth.
+    pub fn with_physics(physics: &Physics) -> Self {
+        ShotDetector {
+            min_speed: SHOT_MIN_SPEED_UU_S,
+            ball_radius: physics.ball_radius,
+            run: None,
+        }
+    }
+
+    /// Feed the next frame and the touch detected on it; returns the shot it started, if any.
+    pub fn observe(&mut self, frame: &FrameSnapshot, touch: Option<&Touch>) -> Option<Shot> {
+        if !frame.is_live {
+            self.run = None;
+            return None;
+        }
+        let touch = touch?;
+        let (position, velocity) = (frame.ball.position, frame.ball.velocity);
+        let goalward = magnitude(velocity) >= self.min_speed
+            && on_target(position, velocity, touch.team, self.ball_radius);
+        let run_shot = matches!(self.run, Some((slot, true)) if slot == touch.slot);
+        self.run = Some((touch.slot, run_shot || goalward));
+        if !goalward || run_shot {
+            return None;
+        }
+        Some(Shot {
+            timestamp: frame.timestamp,
+            slot: touch.slot,
+            team: touch.team,
+            features: ShotFeatures::new(position, velocity, touch.team),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Save {
+    pub timestamp: f32,
+    pub slot: usize,
+    pub team: i64,
+}
+
+pub struct SaveDetector {
+    /// Ball on the previous frame.
+    prev_ball: Option<BallSnapshot>,
+    ball_radius: f32,
+}
+
+impl Default for SaveDetector {
+    fn default() -> Self {
+        Self::with_physics(&Physics::SOCCAR)
+    }
+}
+
+impl SaveDetector {
+    /// A detector for a ball of `physics.ball_radius`, which widens the goal mouth.
+    pub fn with_physics(physics: &Physics) -> Self {
+        SaveDetector {
+            prev_ball: None,
+            ball_radius: physics.ball_radius,
+        }
+    }
+
+    /// Feed the next frame and the touch detected on it; returns the save it made, if any.
+    pub fn observe(&mut self, frame: &FrameSnapshot, touch: Option<&Touch>) -> Option<Save> {
+        let prev = self
+            .prev_ball
+            .replace(frame.ball)
+            .filter(|_| frame.is_live)?;
+        let touch = touch?;
+        // The goal the touching team defends is the one its opponent shoots at.
+        let shooter = 1 - touch.team;
+        let on_target = |ball: &BallSnapshot| {
+            on_target(ball.position, ball.velocity, shooter, self.ball_radius)
+        };
+        let threatened = on_target(&prev)
+            && (target_goal_y(shooter) - prev.position.1) / prev.velocity.1 <= SAVE_HORIZON_S;
+        if !threatened || on_target(&frame.ball) {
+            return None;
+        }
+        Some(Save {
+            timestamp: frame.timestamp,
+            slot: touch.slot,
+            team: touch.team,
+        })
+    }
+}
+
+/// Whether the ball's straight (x, y) path crosses the target goal line inside the goal mouth.
+fn on_target(
+    position: (f32, f32, f32),
+    velocity: (f32, f32, f32),
+    team: i64,
+    ball_radius: f32,
+) -> bool {
+    let goal_y = target_goal_y(team);
+    let dy = goal_y - position.1;
+    // Moving towards the goal line and still in front of it.
+    if velocity.1 == 0.0 || dy.signum() != velocity.1.signum() {
+        return false;
+    }
+    let x_at_line = position.0 + velocity.0 * dy / velocity.1;
+    x_at_line.abs() <= GOAL_HALF_WIDTH + ball_radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, ball};
+
+    fn frame(position: (f32, f32, f32), velocity: (f32, f32, f32)) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position,
+                velocity,
+                ..ball()
+            },
+            ..test_support::frame(1.0, Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_default_score_midpoint() {
+        let features = ShotFeatures::new((0.0, 3120.0, 93.0), (0.0, 2000.0, 0.0), 0);
+        assert!((features.distance - 2000.0).abs() < 1e-3);
+        assert!(features.angle.abs() < 1e-3);
+        let quality = ShotQualityCoefficients::DEFAULT.score(&features);
+        assert!((quality - 0.5).abs() < 1e-4);
+
+        let wide = ShotFeatures::new((0.0, 3120.0, 93.0), (400.0, 2000.0, 0.0), 0);
+        assert!(ShotQualityCoefficients::DEFAULT.score(&wide) < quality);
+    }
+
+    #[test]
+    fn test_shot_detection() {
+        let orange = Touch { slot: 3, team: 1 };
+        // Orange shoots at the -y goal.
+        let shot = ShotDetector::new()
+            .observe(
+                &frame((200.0, -3000.0, 93.0), (-100.0, -2500.0, 0.0)),
+                Some(&orange),
+            )
+            .unwrap();
+        assert_eq!(shot.slot, 3);
+        // Away from goal, too slow, wide, or without a touch.
+        let mut detector = ShotDetector::new();
+        let f = frame((200.0, -3000.0, 93.0), (0.0, 2500.0, 0.0));
+        assert!(detector.observe(&f, Some(&orange)).is_none());
+        let f = frame((200.0, -3000.0, 93.0), (0.0, -500.0, 0.0));
+        assert!(detector.observe(&f, Some(&orange)).is_none());
+        let f = frame((200.0, -3000.0, 93.0), (1500.0, -1500.0, 0.0));
+        assert!(detector.observe(&f, Some(&orange)).is_none());
+        let f = frame((200.0, -3000.0, 93.0), (0.0, -2500.0, 0.0));
+        assert!(detector.observe(&f, None).is_none());
+    }
+
+    #[test]
+    fn test_one_shot_per_touch_run() {
+        let mut detector = ShotDetector::new();
+        let orange = Touch { slot: 3, team: 1 };
+        let teammate = Touch { slot: 2, team: 1 };
+        let goalward = frame((200.0, -3000.0, 93.0), (0.0, -2500.0, 0.0));
+        assert!(detector.observe(&goalward, Some(&orange)).is_some());
+        // Dribbling on: the same run.
+        assert!(detector.observe(&goalward, Some(&orange)).is_none());
+        assert!(detector.observe(&goalward, Some(&orange)).is_none());
+        // A teammate's touch starts a new run, and so does a stoppage.
+        assert!(detector.observe(&goalward, Some(&teammate)).is_some());
+        let dead = FrameSnapshot {
+            is_live: false,
+            ..goalward.clone()
+        };
+        detector.observe(&dead, None);
+        assert!(detector.observe(&goalward, Some(&teammate)).is_some());
+    }
+
+    #[test]
+    fn test_save_detection() {
+        let blue = Touch { slot: 0, team: 0 };
+        // Heading into the blue (-y) goal 0.8 s out, then cleared upfield.
+        let mut detector = SaveDetector::default();
+        detector.observe(&frame((0.0, -3120.0, 93.0), (0.0, -2500.0, 0.0)), None);
+        let cleared = frame((0.0, -3100.0, 93.0), (800.0, 1500.0, 0.0));
+        let save = detector.observe(&cleared, Some(&blue)).unwrap();
+        assert_eq!((save.slot, save.team), (0, 0));
+
+        // Too far out to be imminent.
+        let mut detector = SaveDetector::default();
+        detector.observe(&frame((0.0, 0.0, 93.0), (0.0, -2500.0, 0.0)), None);
+        let f = frame((0.0, 20.0, 93.0), (0.0, 1500.0, 0.0));
+        assert!(detector.observe(&f, Some(&blue)).is_none());
+
+        // Touched but still on target (an own-goal deflection), or touched by the attacker.
+        let mut detector = SaveDetector::default();
+        detector.observe(&frame((0.0, -3120.0, 93.0), (0.0, -2500.0, 0.0)), None);
+        let f = frame((0.0, -3200.0, 93.0), (0.0, -3000.0, 0.0));
+        assert!(detector.observe(&f, Some(&blue)).is_none());
+        let f = frame((0.0, -3200.0, 93.0), (0.0, 1500.0, 0.0));
+        assert!(detector
+            .observe(&f, Some(&Touch { slot: 3, team: 1 }))
+            .is_none());
+    }
+}
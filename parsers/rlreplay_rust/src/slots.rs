@@ -0,0 +1,80 @@
+/// Player slots synthesised from the network stream, for replays without a header
+/// `PlayerStats` roster (early disconnects, some private matches).
+///
+/// Slots are handed out in order of first appearance. A car's slot is keyed by the
+/// PlayerReplicationInfo (PRI) actor it links to, so the car a player respawns in after a
+/// demolition or goal keeps their slot; a car never linked to a PRI keeps a slot of its own.
+use std::collections::HashMap;
+
+#[derive(Clone, Default)]
+pub struct NetworkSlots {
+    /// Car actor → slot.
+    by_car: HashMap<i32, usize>,
+    /// PRI actor → slot.
+    by_pri: HashMap<i32, usize>,
+    next: usize,
+}
+
+impl NetworkSlots {
+    /// Slot for `car`, driven by the player behind `pri` when known; a new slot for a car
+    /// and PRI not seen before.
+    pub fn slot(&mut self, car: i32, pri: Option<i32>) -> usize {
+        let known = self
+            .by_car
+            .get(&car)
+            .or_else(|| pri.and_then(|pri| self.by_pri.get(&pri)))
+            .copied();
+        let slot = known.unwrap_or_else(|| {
+            self.next += 1;
+            self.next - 1
+        });
+        self.by_car.insert(car, slot);
+        if let Some(pri) = pri {
+            self.by_pri.entry(pri).or_insert(slot);
+        }
+        slot
+    }
+
+    /// Record the PRI of a car that got its slot before its PRI link arrived.
+    pub fn link(&mut self, car: i32, pri: i32) {
+        if let Some(&slot) = self.by_car.get(&car) {
+            self.by_pri.entry(pri).or_insert(slot);
+        }
+    }
+
+    /// Forget a deleted car or PRI actor; its id may be reused by an unrelated actor.
+    pub fn remove(&mut self, actor: i32) {
+        self.by_car.remove(&actor);
+        self.by_pri.remove(&actor);
+    }
+
+    /// Number of slots handed out so far.
+    pub fn count(&self) -> usize {
+        self.next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_respawned_car_keeps_slot() {
+        let mut slots = NetworkSlots::default();
+        assert_eq!(slots.slot(10, Some(100)), 0);
+        assert_eq!(slots.slot(11, Some(101)), 1);
+        assert_eq!(slots.slot(10, Some(100)), 0);
+        // Car 10 is demolished; the player respawns in car 12.
+        slots.remove(10);
+        assert_eq!(slots.slot(12, Some(100)), 0);
+        // A car whose PRI link arrives only later.
+        assert_eq!(slots.slot(13, None), 2);
+        slots.link(13, 102);
+        slots.remove(13);
+        assert_eq!(slots.slot(14, Some(102)), 2);
+        // The PRI left; a reused id is a new player.
+        slots.remove(102);
+        assert_eq!(slots.slot(15, Some(102)), 3);
+        assert_eq!(slots.count(), 4);
+    }
+}
@@ -0,0 +1,136 @@
+/// Velocity smoothing against decode jitter.
+///
+/// Replicated velocities are quantized, so frame-to-frame differences (accelerations) are
+/// noisy. The smoother is a trailing moving average over the last
+/// `VELOCITY_SMOOTHING_WINDOW` frames (the current one included) of the ball and of each
+/// car slot. It only looks back, so a frame's value never depends on later frames, at the
+/// cost of lagging real changes by about half the window (~2 frames, 0.07 s at 30 fps).
+/// Histories restart wherever a jump is real rather than noise: the ball's on every
+/// non-live frame (kickoff resets, goal explosions), a car's whenever it is demolished or
+/// missing from the frame.
+use crate::network::FrameSnapshot;
+use std::collections::{HashMap, VecDeque};
+
+/// Frames averaged, current frame included.
+pub const VELOCITY_SMOOTHING_WINDOW: usize = 5;
+
+type Vec3 = (f32, f32, f32);
+
+#[derive(Default)]
+struct Window {
+    samples: VecDeque<Vec3>,
+}
+
+impl Window {
+    fn push(&mut self, v: Vec3) -> Vec3 {
+        if self.samples.len() == VELOCITY_SMOOTHING_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(v);
+        let n = self.samples.len() as f32;
+        let sum = self.samples.iter().fold((0.0, 0.0, 0.0), |acc, s| {
+            (acc.0 + s.0, acc.1 + s.1, acc.2 + s.2)
+        });
+        (sum.0 / n, sum.1 / n, sum.2 / n)
+    }
+}
+
+/// Smoothed velocities of one frame.
+pub struct SmoothedVelocities {
+    pub ball: Vec3,
+    /// (slot, velocity) of every non-demolished car, in frame order.
+    pub cars: Vec<(usize, Vec3)>,
+}
+
+impl SmoothedVelocities {
+    pub fn car(&self, slot: usize) -> Option<Vec3> {
+        self.cars.iter().find(|(s, _)| *s == slot).map(|(_, v)| *v)
+    }
+}
+
+#[derive(Default)]
+pub struct VelocitySmoother {
+    ball: Window,
+    /// Slot → recent car velocities.
+    cars: HashMap<usize, Window>,
+}
+
+impl VelocitySmoother {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame; returns its smoothed velocities.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> SmoothedVelocities {
+        if !frame.is_live {
+            self.ball = Window::default();
+        }
+        let ball = self.ball.push(frame.ball.velocity);
+        let on_field = |slot: usize| {
+            frame
+                .players
+                .iter()
+                .any(|p| p.slot == slot && !p.is_demolished)
+        };
+        self.cars.retain(|slot, _| on_field(*slot));
+        let cars = frame
+            .players
+            .iter()
+            .filter(|p| !p.is_demolished)
+            .map(|p| {
+                (
+                    p.slot,
+                    self.cars.entry(p.slot).or_default().push(p.velocity),
+                )
+            })
+            .collect();
+        SmoothedVelocities { ball, cars }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, ball, player};
+    use crate::network::{BallSnapshot, PlayerSnapshot};
+
+    fn frame(ball_vx: f32, car_vx: Option<f32>) -> FrameSnapshot {
+        let players = car_vx
+            .map(|vx| PlayerSnapshot {
+                velocity: (vx, 0.0, 0.0),
+                ..player(0)
+            })
+            .into_iter()
+            .collect();
+        FrameSnapshot {
+            ball: BallSnapshot {
+                velocity: (ball_vx, 0.0, 0.0),
+                ..ball()
+            },
+            ..test_support::frame(0.0, players)
+        }
+    }
+
+    #[test]
+    fn test_trailing_moving_average() {
+        let mut smoother = VelocitySmoother::new();
+        // Jitter around 1000 uu/s averages out once the window fills.
+        let mut last = None;
+        for vx in [1000.0, 1010.0, 990.0, 1020.0, 980.0, 1000.0] {
+            last = Some(smoother.observe(&frame(vx, Some(vx))));
+        }
+        let last = last.unwrap();
+        assert_eq!(last.ball.0, 1000.0);
+        assert_eq!(last.car(0).map(|v| v.0), Some(1000.0));
+
+        // A car missing for a frame restarts its history.
+        smoother.observe(&frame(1000.0, None));
+        let back = smoother.observe(&frame(1000.0, Some(200.0)));
+        assert_eq!(back.car(0), Some((200.0, 0.0, 0.0)));
+
+        // So does the ball on a dead frame.
+        let mut dead = frame(0.0, None);
+        dead.is_live = false;
+        assert_eq!(smoother.observe(&dead).ball, (0.0, 0.0, 0.0));
+    }
+}
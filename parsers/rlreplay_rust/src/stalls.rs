@@ -0,0 +1,175 @@
+/// Ball stall detection: the ball sitting nearly still against a wall or in a corner.
+///
+/// The ball is against a wall when its centre is within `BALL_RADIUS` +
+/// `STALL_WALL_SLACK_UU` of a side wall, a back wall outside the goal mouth or a corner
+/// plane, at any height. A stall is a run of live frames with the ball against a wall and
+/// slower than `STALL_MAX_SPEED_UU_S`, lasting at least the detector's minimum duration. It
+/// ends on the first frame the ball speeds up, leaves the wall, or play stops. Kickoff
+/// rests never count: the ball is not live and sits at centre field.
+use crate::geometry::{
+    magnitude, BACK_WALL_Y, BALL_RADIUS, CORNER_SUM, GOAL_HALF_WIDTH, GOAL_HEIGHT, SIDE_WALL_X,
+};
+use crate::network::FrameSnapshot;
+
+/// Slack (uu) beyond the ball radius between the ball centre and a wall plane.
+pub const STALL_WALL_SLACK_UU: f32 = 40.0;
+/// Ball speed (uu/s) below which it counts as stalled; allows for cars nudging it.
+pub const STALL_MAX_SPEED_UU_S: f32 = 250.0;
+/// Default minimum stall duration (seconds).
+pub const STALL_MIN_DURATION_S: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StallLocation {
+    SideWall,
+    BackWall,
+    Corner,
+}
+
+impl StallLocation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StallLocation::SideWall => "side_wall",
+            StallLocation::BackWall => "back_wall",
+            StallLocation::Corner => "corner",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BallStall {
+    pub start: f32,
+    /// Timestamp of the frame the stall ended on.
+    pub end: f32,
+    /// Where the ball sat when the stall began.
+    pub location: StallLocation,
+    pub position: (f32, f32, f32),
+}
+
+/// Wall the ball centred at `position` is resting against, if any.
+pub fn ball_wall_location(position: (f32, f32, f32)) -> Option<StallLocation> {
+    let (x, y, z) = (position.0.abs(), position.1.abs(), position.2);
+    let reach = BALL_RADIUS + STALL_WALL_SLACK_UU;
+    let in_goal_mouth = x < GOAL_HALF_WIDTH && z < GOAL_HEIGHT;
+    let near_side = x >= SIDE_WALL_X - reach;
+    let near_back = y >= BACK_WALL_Y - reach && !in_goal_mouth;
+    // Distance to a 45° plane is (|x| + |y| - CORNER_SUM) / sqrt(2).
+    let near_corner = x + y >= CORNER_SUM - reach * std::f32::consts::SQRT_2;
+    if near_corner || (near_side && near_back) {
+        Some(StallLocation::Corner)
+    } else if near_side {
+        Some(StallLocation::SideWall)
+    } else if near_back {
+        Some(StallLocation::BackWall)
+    } else {
+        None
+    }
+}
+
+pub struct BallStallDetector {
+    min_duration_s: f32,
+    current: Option<BallStall>,
+}
+
+impl BallStallDetector {
+    pub fn new(min_duration_s: f32) -> Self {
+        BallStallDetector {
+            min_duration_s,
+            current: None,
+        }
+    }
+
+    /// Feed the next frame; returns the stall that ended on it, if it lasted long enough.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> Option<BallStall> {
+        let ball = &frame.ball;
+        let location = (frame.is_live && magnitude(ball.velocity) < STALL_MAX_SPEED_UU_S)
+            .then(|| ball_wall_location(ball.position))
+            .flatten();
+        if location.is_some() && self.current.is_some() {
+            return None;
+        }
+        let ended = self.current.take().map(|stall| BallStall {
+            end: frame.timestamp,
+            ..stall
+        });
+        self.current = location.map(|location| BallStall {
+            start: frame.timestamp,
+            end: frame.timestamp,
+            location,
+            position: ball.position,
+        });
+        ended.filter(|stall| stall.end - stall.start >= self.min_duration_s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, ball};
+    use crate::network::BallSnapshot;
+
+    fn frame(
+        timestamp: f32,
+        position: (f32, f32, f32),
+        speed: f32,
+        is_live: bool,
+    ) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position,
+                velocity: (0.0, speed, 0.0),
+                ..ball()
+            },
+            is_live,
+            ..test_support::frame(timestamp, Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_ball_wall_location() {
+        assert_eq!(ball_wall_location((0.0, 0.0, 93.0)), None);
+        assert_eq!(
+            ball_wall_location((-4000.0, 1000.0, 93.0)),
+            Some(StallLocation::SideWall)
+        );
+        assert_eq!(
+            ball_wall_location((2000.0, 5020.0, 300.0)),
+            Some(StallLocation::BackWall)
+        );
+        // Resting in the goal mouth is not against the back wall.
+        assert_eq!(ball_wall_location((0.0, 5020.0, 93.0)), None);
+        assert_eq!(
+            ball_wall_location((3600.0, -4350.0, 93.0)),
+            Some(StallLocation::Corner)
+        );
+    }
+
+    #[test]
+    fn test_stall_detection() {
+        let corner = (3600.0, -4350.0, 93.0);
+        let mut detector = BallStallDetector::new(STALL_MIN_DURATION_S);
+        let mut stalls = Vec::new();
+        // Kickoff rest at centre field, then a 3 s corner stall ended by a clear.
+        let frames = [
+            frame(0.0, (0.0, 0.0, 93.0), 0.0, false),
+            frame(1.0, (0.0, 0.0, 93.0), 0.0, true),
+            frame(5.0, corner, 100.0, true),
+            frame(6.5, corner, 200.0, true),
+            frame(8.0, corner, 1500.0, true),
+            // Too short, then cut off by a goal.
+            frame(9.0, (4000.0, 0.0, 400.0), 50.0, true),
+            frame(10.0, (4000.0, 0.0, 400.0), 50.0, false),
+        ];
+        for f in &frames {
+            stalls.extend(detector.observe(f));
+        }
+        assert_eq!(
+            stalls,
+            vec![BallStall {
+                start: 5.0,
+                end: 8.0,
+                location: StallLocation::Corner,
+                position: corner,
+            }]
+        );
+    }
+}
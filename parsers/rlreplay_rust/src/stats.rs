@@ -0,0 +1,852 @@
+/// Match-level accumulators computed during the single network pass of `parse_all`.
+///
+/// Everything here consumes `FrameSnapshot`s (plus the touch detected on each) in order and
+/// is exposed through the `header` section of the `parse_all` payload.
+use crate::geometry::{
+    classify_surface, distance, magnitude, Surface, ATTACKING_THIRD_Y, BACK_WALL_Y,
+};
+use crate::network::{FrameSnapshot, PlayerSnapshot};
+use crate::pads::PadEventStatus;
+use crate::touches::Touch;
+
+/// Supersonic hysteresis (uu/s): a car becomes supersonic at the enter speed and, as in
+/// game, stays supersonic until it slows below the exit speed.
+pub const SUPERSONIC_ENTER_UU_S: f32 = 2200.0;
+pub const SUPERSONIC_EXIT_UU_S: f32 = 2100.0;
+/// Boost held (0-100) at or below which a supersonic car counts as running on empty.
+pub const EMPTY_SUPERSONIC_MAX_BOOST: i64 = 10;
+
+/// Boost is replicated in steps a fraction of a second apart, so a drop is credited with
+/// the time since the car's previous boost change as boosting time, capped at this many
+/// seconds for drops that follow a long unchanged stretch. At 0.5 s full-throttle boosting
+/// comes out near the game's 33.3 boost per second.
+pub const BOOST_USE_MAX_GAP_S: f32 = 0.5;
+
+/// Largest position change (uu) between consecutive frames always counted as driving.
+pub const ROUTE_MAX_STEP_UU: f32 = 500.0;
+/// Fastest travel (uu/s) counted as driving across a longer frame gap: top car speed
+/// (2300 uu/s) with some slack. A bigger step for the time elapsed is a respawn or a
+/// position correction, not distance travelled.
+pub const ROUTE_MAX_SPEED_UU_S: f32 = 2500.0;
+/// Minimum distance travelled (uu) for `pads_per_100uu` to be meaningful.
+pub const ROUTE_MIN_DISTANCE_UU: f64 = 1000.0;
+
+/// Weight of the last touch in `possession_probability`; proximity gets the rest.
+pub const POSSESSION_TOUCH_WEIGHT: f32 = 0.6;
+/// Car-to-ball distance (uu) at which a car's proximity weight has halved.
+pub const POSSESSION_PROXIMITY_SCALE_UU: f32 = 1000.0;
+
+/// Weights of the three `goal_pressure` terms; they sum to 1, keeping pressure in 0..1.
+pub const PRESSURE_DISTANCE_WEIGHT: f32 = 0.5;
+pub const PRESSURE_SPEED_WEIGHT: f32 = 0.3;
+pub const PRESSURE_ATTACKER_WEIGHT: f32 = 0.2;
+/// Ball-to-goal distance (uu) at which the distance term reaches 0: the field length.
+pub const PRESSURE_DISTANCE_SCALE_UU: f32 = 2.0 * BACK_WALL_Y;
+/// Ball speed toward the goal (uu/s) at which the speed term saturates at 1.
+pub const PRESSURE_SPEED_SCALE_UU_S: f32 = 3000.0;
+/// The `goal_pressure` coefficients by name, for callers that chart or reproduce it.
+pub const PRESSURE_COEFFICIENTS: [(&str, f32); 5] = [
+    ("distance_weight", PRESSURE_DISTANCE_WEIGHT),
+    ("speed_weight", PRESSURE_SPEED_WEIGHT),
+    ("attacker_weight", PRESSURE_ATTACKER_WEIGHT),
+    ("distance_scale_uu", PRESSURE_DISTANCE_SCALE_UU),
+    ("speed_scale_uu_s", PRESSURE_SPEED_SCALE_UU_S),
+];
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TeamStats {
+    /// Live seconds during which this team made the most recent touch.
+    pub possession_seconds: f64,
+    /// Possession seconds with the ball in this team's attacking third.
+    pub attacking_third_possession_seconds: f64,
+    /// Live seconds with at least one of this team's cars on the field.
+    pub field_seconds: f64,
+    /// Integral over `field_seconds` of the mean boost (0-100) of the team's cars on the field.
+    pub boost_integral: f64,
+}
+
+impl TeamStats {
+    /// Time-weighted mean boost held by the team's on-field cars; None if never on the field.
+    pub fn avg_boost(&self) -> Option<f64> {
+        (self.field_seconds > 0.0).then(|| self.boost_integral / self.field_seconds)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlayerStats {
+    pub demos_inflicted: u32,
+    pub demos_taken: u32,
+    /// Live seconds spent goal-side of the ball (between it and their own goal) while the
+    /// other team had possession.
+    pub goalside_seconds: f64,
+    /// Live seconds supersonic while on a surface (ground, walls or ceiling).
+    pub supersonic_ground_seconds: f64,
+    /// Live seconds supersonic while airborne.
+    pub supersonic_air_seconds: f64,
+    /// Live seconds with a car on the field (spawned, not demolished).
+    pub field_seconds: f64,
+    /// Integral of boost held (0-100) over `field_seconds`.
+    pub boost_integral: f64,
+    /// Live seconds as their team's last man back (see `last_man_back`).
+    pub last_man_seconds: f64,
+    /// Touches credited by the touch detector.
+    pub touches: u32,
+    /// Sum of the ball speeds (uu/s) just after each touch.
+    pub touch_speed_sum: f64,
+    /// Fastest ball speed (uu/s) just after one of the player's touches; 0 without touches.
+    pub hardest_touch_speed: f64,
+    /// Live distance travelled (uu) by the player's car, excluding respawn jumps.
+    pub distance_uu: f64,
+    /// Collected pad events credited to the player. Pad events can be repeated or credited
+    /// to the wrong car, so route efficiency uses `boost_pickups` instead.
+    pub big_pads: u32,
+    pub small_pads: u32,
+    /// Live rises in the player's replicated boost amount, one per pickup; respawn and
+    /// kickoff refills are not counted.
+    pub boost_pickups: u32,
+    /// Live boost consumed (0-100 units), from drops in the replicated boost amount.
+    pub boost_used: f64,
+    /// Estimated live seconds spent boosting (see `BOOST_USE_MAX_GAP_S`).
+    pub boosting_seconds: f64,
+    /// Times the car was supersonic with `EMPTY_SUPERSONIC_MAX_BOOST` boost or less, counted
+    /// once per run of such frames.
+    pub empty_supersonic_events: u32,
+}
+
+impl PlayerStats {
+    /// Time-weighted mean boost held while on the field; None if never on the field.
+    pub fn avg_boost(&self) -> Option<f64> {
+        (self.field_seconds > 0.0).then(|| self.boost_integral / self.field_seconds)
+    }
+
+    /// Mean ball speed (uu/s) just after the player's touches; None without touches.
+    pub fn avg_touch_speed(&self) -> Option<f64> {
+        (self.touches > 0).then(|| self.touch_speed_sum / self.touches as f64)
+    }
+
+    /// Boost pickups per 100 uu travelled; None below `ROUTE_MIN_DISTANCE_UU`.
+    pub fn pads_per_100uu(&self) -> Option<f64> {
+        (self.distance_uu >= ROUTE_MIN_DISTANCE_UU)
+            .then(|| self.boost_pickups as f64 / (self.distance_uu / 100.0))
+    }
+
+    /// Boost consumed per second of boosting; None if the player never boosted.
+    pub fn boost_use_rate(&self) -> Option<f64> {
+        (self.boosting_seconds > 0.0).then(|| self.boost_used / self.boosting_seconds)
+    }
+}
+
+pub struct MatchStats {
+    pub teams: [TeamStats; 2],
+    /// Indexed by player slot; grows as slots appear.
+    pub players: Vec<PlayerStats>,
+    /// Team of the most recent touch; flips only when the other team touches the ball.
+    possession_team: Option<usize>,
+    /// |y| boundary of the attacking thirds; None on arenas without known dimensions.
+    attacking_third_y: Option<f32>,
+    prev_timestamp: Option<f32>,
+    prev_live: bool,
+    prev_ball_y: f32,
+    /// Slots that were goal-side defenders on the previous frame.
+    prev_goalside: Vec<usize>,
+    /// Latched supersonic state, indexed by slot.
+    supersonic: Vec<bool>,
+    /// Slots supersonic on the previous frame, and whether they were airborne.
+    prev_supersonic: Vec<(usize, bool)>,
+    /// (slot, team, boost) of the cars on the field on the previous frame.
+    prev_boost: Vec<(usize, i64, f64)>,
+    /// Each team's last man back on the previous frame.
+    prev_last_man: [Option<usize>; 2],
+    /// (slot, position) of the cars on the field on the previous frame.
+    prev_positions: Vec<(usize, (f32, f32, f32))>,
+    /// (timestamp, boost) of each on-field car's last boost change, indexed by slot.
+    boost_changes: Vec<Option<(f32, i64)>>,
+    /// Slots supersonic on empty on the previous frame, indexed by slot.
+    empty_supersonic: Vec<bool>,
+}
+
+impl MatchStats {
+    pub fn new(attacking_third_y: Option<f32>) -> Self {
+        MatchStats {
+            teams: [TeamStats::default(); 2],
+            players: Vec::new(),
+            possession_team: None,
+            attacking_third_y,
+            prev_timestamp: None,
+            prev_live: false,
+            prev_ball_y: 0.0,
+            prev_goalside: Vec::new(),
+            supersonic: Vec::new(),
+            prev_supersonic: Vec::new(),
+            prev_boost: Vec::new(),
+            prev_last_man: [None; 2],
+            prev_positions: Vec::new(),
+            boost_changes: Vec::new(),
+            empty_supersonic: Vec::new(),
+        }
+    }
+
+    pub fn has_field_thirds(&self) -> bool {
+        self.attacking_third_y.is_some()
+    }
+
+    pub fn observe(&mut self, frame: &FrameSnapshot, touch: Option<&Touch>) {
+        // The interval since the previous frame is credited to the state held during it.
+        let dt = self
+            .prev_timestamp
+            .map(|prev| (frame.timestamp - prev).max(0.0) as f64)
+            .unwrap_or(0.0);
+        if self.prev_live {
+            if let Some(team) = self.possession_team {
+                self.teams[team].possession_seconds += dt;
+                // Blue (team 0) attacks +y.
+                let attacking_y = if team == 0 {
+                    self.prev_ball_y
+                } else {
+                    -self.prev_ball_y
+                };
+                if self
+                    .attacking_third_y
+                    .is_some_and(|third| attacking_y > third)
+                {
+                    self.teams[team].attacking_third_possession_seconds += dt;
+                }
+            }
+            for slot in std::mem::take(&mut self.prev_goalside) {
+                self.player_mut(slot).goalside_seconds += dt;
+            }
+            for (slot, airborne) in std::mem::take(&mut self.prev_supersonic) {
+                let player = self.player_mut(slot);
+                if airborne {
+                    player.supersonic_air_seconds += dt;
+                } else {
+                    player.supersonic_ground_seconds += dt;
+                }
+            }
+            let prev_boost = std::mem::take(&mut self.prev_boost);
+            for (team, totals) in self.teams.iter_mut().enumerate() {
+                let boosts: Vec<f64> = prev_boost
+                    .iter()
+                    .filter(|(_, t, _)| *t == team as i64)
+                    .map(|(_, _, boost)| *boost)
+                    .collect();
+                if !boosts.is_empty() {
+                    totals.field_seconds += dt;
+                    totals.boost_integral += boosts.iter().sum::<f64>() / boosts.len() as f64 * dt;
+                }
+            }
+            for (slot, _, boost) in prev_boost {
+                let player = self.player_mut(slot);
+                player.field_seconds += dt;
+                player.boost_integral += boost * dt;
+            }
+            for slot in self.prev_last_man.into_iter().flatten() {
+                self.player_mut(slot).last_man_seconds += dt;
+            }
+            if frame.is_live {
+                let max_step = ROUTE_MAX_STEP_UU.max(ROUTE_MAX_SPEED_UU_S * dt as f32);
+                for (slot, prev) in std::mem::take(&mut self.prev_positions) {
+                    let Some(car) = frame.players.iter().find(|p| p.slot == slot) else {
+                        continue;
+                    };
+                    let step = distance(prev, car.position);
+                    if !car.is_demolished && step <= max_step {
+                        self.player_mut(slot).distance_uu += step as f64;
+                    }
+                }
+            }
+        }
+        self.prev_timestamp = Some(frame.timestamp);
+        self.prev_live = frame.is_live;
+        self.prev_ball_y = frame.ball.position.1;
+
+        if !frame.is_live {
+            // Kickoff resets and post-goal time clear possession.
+            self.possession_team = None;
+        }
+        for demo in &frame.demolitions {
+            // Only player-inflicted demolitions count towards the box score.
+            if let (Some(attacker), Some(victim)) = (demo.attacker_slot, demo.victim_slot) {
+                self.player_mut(attacker).demos_inflicted += 1;
+                self.player_mut(victim).demos_taken += 1;
+            }
+        }
+        for pad in &frame.pad_events {
+            if let (PadEventStatus::Collected, Some(slot)) = (pad.event.status, pad.player_slot) {
+                let player = self.player_mut(slot);
+                if pad.event.is_big {
+                    player.big_pads += 1;
+                } else {
+                    player.small_pads += 1;
+                }
+            }
+        }
+        if let Some(touch) = touch {
+            if (0..=1).contains(&touch.team) {
+                self.possession_team = Some(touch.team as usize);
+            }
+            let speed = magnitude(frame.ball.velocity) as f64;
+            let player = self.player_mut(touch.slot);
+            player.touches += 1;
+            player.touch_speed_sum += speed;
+            player.hardest_touch_speed = player.hardest_touch_speed.max(speed);
+        }
+
+        self.prev_last_man = last_man_back(&frame.players);
+        self.prev_positions = frame
+            .players
+            .iter()
+            .filter(|p| !p.is_demolished)
+            .map(|p| (p.slot, p.position))
+            .collect();
+        self.prev_boost = frame
+            .players
+            .iter()
+            .filter(|p| !p.is_demolished)
+            .map(|p| (p.slot, p.team, p.boost_amount as f64))
+            .collect();
+        self.prev_supersonic.clear();
+        for player in &frame.players {
+            if self.supersonic.len() <= player.slot {
+                self.supersonic.resize(player.slot + 1, false);
+            }
+            let speed = player.speed();
+            let latched = &mut self.supersonic[player.slot];
+            *latched = !player.is_demolished
+                && (speed >= SUPERSONIC_ENTER_UU_S || (*latched && speed >= SUPERSONIC_EXIT_UU_S));
+            if *latched {
+                let airborne = classify_surface(player.position) == Surface::Air;
+                self.prev_supersonic.push((player.slot, airborne));
+            }
+            let empty =
+                frame.is_live && *latched && player.boost_amount <= EMPTY_SUPERSONIC_MAX_BOOST;
+            if self.empty_supersonic.len() <= player.slot {
+                self.empty_supersonic.resize(player.slot + 1, false);
+            }
+            let was_empty = std::mem::replace(&mut self.empty_supersonic[player.slot], empty);
+            if empty && !was_empty {
+                self.player_mut(player.slot).empty_supersonic_events += 1;
+            }
+            self.observe_boost(frame, player);
+        }
+
+        self.prev_goalside.clear();
+        if let Some(attacking) = self.possession_team {
+            let ball_y = frame.ball.position.1;
+            let defenders = frame
+                .players
+                .iter()
+                .filter(|p| p.team != attacking as i64 && (0..=1).contains(&p.team));
+            for player in defenders {
+                // Blue (team 0) defends the -y goal. Cars can only be past their goal line
+                // inside the net, which still counts as goal-side.
+                let y = player.position.1;
+                let goalside = if player.team == 0 {
+                    y < ball_y
+                } else {
+                    y > ball_y
+                };
+                if goalside && !player.is_demolished {
+                    self.prev_goalside.push(player.slot);
+                }
+            }
+        }
+    }
+
+    /// Credit a drop in `car`'s boost since its last change as boost used.
+    fn observe_boost(&mut self, frame: &FrameSnapshot, car: &PlayerSnapshot) {
+        if self.boost_changes.len() <= car.slot {
+            self.boost_changes.resize(car.slot + 1, None);
+        }
+        if car.is_demolished {
+            // Respawns refill boost; start over.
+            self.boost_changes[car.slot] = None;
+            return;
+        }
+        let t = frame.timestamp;
+        let boost = car.boost_amount;
+        let last = self.boost_changes[car.slot];
+        if last.is_some_and(|(_, prev)| prev == boost) {
+            return;
+        }
+        self.boost_changes[car.slot] = Some((t, boost));
+        if last.is_some_and(|(_, prev)| boost > prev) && frame.is_live {
+            self.player_mut(car.slot).boost_pickups += 1;
+        }
+        if let Some((since, prev)) = last.filter(|(_, prev)| boost < *prev) {
+            if frame.is_live {
+                let player = self.player_mut(car.slot);
+                player.boost_used += (prev - boost) as f64;
+                player.boosting_seconds += (t - since).clamp(0.0, BOOST_USE_MAX_GAP_S) as f64;
+            }
+        }
+    }
+
+    pub fn player(&self, slot: usize) -> PlayerStats {
+        self.players.get(slot).copied().unwrap_or_default()
+    }
+
+    fn player_mut(&mut self, slot: usize) -> &mut PlayerStats {
+        if self.players.len() <= slot {
+            self.players.resize(slot + 1, PlayerStats::default());
+        }
+        &mut self.players[slot]
+    }
+
+    /// Share of possessed time per team, 0-100. Both 0 when nobody had possession.
+    pub fn possession_pct(&self) -> [f64; 2] {
+        let total = self.teams[0].possession_seconds + self.teams[1].possession_seconds;
+        if total <= 0.0 {
+            return [0.0, 0.0];
+        }
+        [
+            self.teams[0].possession_seconds / total * 100.0,
+            self.teams[1].possession_seconds / total * 100.0,
+        ]
+    }
+}
+
+/// Each team's last man back: the slot of its on-field (spawned, not demolished) car
+/// deepest toward its own goal, i.e. lowest y for blue and highest y for orange, with the
+/// lower slot on a tie. Indexed by team; None when the team has no car on the field.
+pub fn last_man_back(players: &[PlayerSnapshot]) -> [Option<usize>; 2] {
+    [0, 1].map(|team| {
+        // Distance up the field from the team's own goal.
+        let depth = |p: &PlayerSnapshot| {
+            if team == 0 {
+                p.position.1
+            } else {
+                -p.position.1
+            }
+        };
+        players
+            .iter()
+            .filter(|p| p.team == team && !p.is_demolished)
+            .min_by(|a, b| depth(a).total_cmp(&depth(b)).then(a.slot.cmp(&b.slot)))
+            .map(|p| p.slot)
+    })
+}
+
+/// Soft possession estimate: 0 when team 0 surely has the ball, 1 for team 1.
+///
+/// `POSSESSION_TOUCH_WEIGHT` × last touch (0 or 1, 0.5 with no touch since the last
+/// restart) + the rest × team 1's share of proximity, where each on-field car weighs
+/// 1 / (1 + d / `POSSESSION_PROXIMITY_SCALE_UU`) at distance d from the ball, so more and
+/// closer cars pull toward their team. The share is 0.5 with no cars on the field.
+pub fn possession_probability(last_touch_team: Option<i64>, frame: &FrameSnapshot) -> f32 {
+    let touch = match last_touch_team {
+        Some(0) => 0.0,
+        Some(1) => 1.0,
+        _ => 0.5,
+    };
+    let mut weights = [0.0f32; 2];
+    for player in frame.players.iter().filter(|p| !p.is_demolished) {
+        if let Some(weight) = weights.get_mut(player.team as usize) {
+            let d = distance(player.position, frame.ball.position);
+            *weight += 1.0 / (1.0 + d / POSSESSION_PROXIMITY_SCALE_UU);
+        }
+    }
+    let total = weights[0] + weights[1];
+    let proximity = if total > 0.0 { weights[1] / total } else { 0.5 };
+    POSSESSION_TOUCH_WEIGHT * touch + (1.0 - POSSESSION_TOUCH_WEIGHT) * proximity
+}
+
+/// How threatening the frame is to each goal (index = defending team, `goal_centers` order),
+/// from 0 to 1:
+///
+///   `PRESSURE_DISTANCE_WEIGHT` × (1 - min(d / `PRESSURE_DISTANCE_SCALE_UU`, 1))
+///   + `PRESSURE_SPEED_WEIGHT` × clamp(v / `PRESSURE_SPEED_SCALE_UU_S`, 0, 1)
+///   + `PRESSURE_ATTACKER_WEIGHT` × attackers in the third / attackers on the field
+///
+/// where d is the (x, y) distance from the ball to the goal line centre and v the ball's
+/// (x, y) velocity toward it. Attackers are the other team's on-field cars; the attacking
+/// third is the `ATTACKING_THIRD_Y` band in front of the goal. The attacker term is 0 with
+/// no attackers on the field.
+pub fn goal_pressure(frame: &FrameSnapshot, goal_centers: [(f32, f32); 2]) -> [f32; 2] {
+    let (x, y, _) = frame.ball.position;
+    let (vx, vy, _) = frame.ball.velocity;
+    [0usize, 1].map(|goal| {
+        let (goal_x, goal_y) = goal_centers[goal];
+        let (dx, dy) = (goal_x - x, goal_y - y);
+        let d = dx.hypot(dy);
+        let toward = if d > 0.0 {
+            (vx * dx + vy * dy) / d
+        } else {
+            0.0
+        };
+        let attackers: Vec<&PlayerSnapshot> = frame
+            .players
+            .iter()
+            .filter(|p| p.team != goal as i64 && !p.is_demolished)
+            .collect();
+        let in_third = attackers
+            .iter()
+            .filter(|p| p.position.1 * goal_y.signum() >= ATTACKING_THIRD_Y)
+            .count();
+        let attacker_share = if attackers.is_empty() {
+            0.0
+        } else {
+            in_third as f32 / attackers.len() as f32
+        };
+        PRESSURE_DISTANCE_WEIGHT * (1.0 - (d / PRESSURE_DISTANCE_SCALE_UU).min(1.0))
+            + PRESSURE_SPEED_WEIGHT * (toward / PRESSURE_SPEED_SCALE_UU_S).clamp(0.0, 1.0)
+            + PRESSURE_ATTACKER_WEIGHT * attacker_share
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena_tables::SOCCAR_GOAL_CENTERS;
+    use crate::network::test_support::{self, ball};
+    use crate::network::{BallSnapshot, FrameDemolition, FramePadEvent, PlayerSnapshot};
+    use crate::pads::PadEvent;
+
+    fn frame(timestamp: f32, is_live: bool) -> FrameSnapshot {
+        frame_at(timestamp, is_live, 0.0)
+    }
+
+    fn frame_at(timestamp: f32, is_live: bool, ball_y: f32) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position: (0.0, ball_y, 93.0),
+                ..ball()
+            },
+            is_live,
+            ..test_support::frame(timestamp, Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_possession_excludes_dead_frames() {
+        let mut stats = MatchStats::new(None);
+        let blue = Touch { slot: 0, team: 0 };
+        let orange = Touch { slot: 1, team: 1 };
+        stats.observe(&frame(0.0, true), None);
+        stats.observe(&frame(1.0, true), Some(&blue));
+        stats.observe(&frame(3.0, true), None);
+        stats.observe(&frame(4.0, false), None); // goal: possession cleared
+        stats.observe(&frame(9.0, false), None); // kickoff reset
+        stats.observe(&frame(10.0, true), None);
+        stats.observe(&frame(11.0, true), Some(&orange));
+        stats.observe(&frame(12.0, true), None);
+
+        assert!((stats.teams[0].possession_seconds - 3.0).abs() < 1e-6);
+        assert!((stats.teams[1].possession_seconds - 1.0).abs() < 1e-6);
+        let pct = stats.possession_pct();
+        assert!((pct[0] - 75.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_attacking_third_possession() {
+        let mut stats = MatchStats::new(Some(ATTACKING_THIRD_Y));
+        let blue = Touch { slot: 0, team: 0 };
+        let orange = Touch { slot: 1, team: 1 };
+        stats.observe(&frame_at(0.0, true, 0.0), Some(&blue));
+        stats.observe(&frame_at(1.0, true, 3000.0), None);
+        stats.observe(&frame_at(3.0, true, -3000.0), None); // blue's defensive third
+        stats.observe(&frame_at(4.0, true, -3000.0), Some(&orange));
+        stats.observe(&frame_at(6.0, false, -3000.0), None);
+        stats.observe(&frame_at(8.0, true, -3000.0), None); // dead time excluded
+
+        assert!((stats.teams[0].possession_seconds - 4.0).abs() < 1e-6);
+        assert!((stats.teams[0].attacking_third_possession_seconds - 2.0).abs() < 1e-6);
+        assert!((stats.teams[1].attacking_third_possession_seconds - 2.0).abs() < 1e-6);
+
+        let mut unsupported = MatchStats::new(None);
+        unsupported.observe(&frame_at(0.0, true, 3000.0), Some(&blue));
+        unsupported.observe(&frame_at(1.0, true, 3000.0), None);
+        assert!(!unsupported.has_field_thirds());
+        assert_eq!(unsupported.teams[0].attacking_third_possession_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_touch_speeds() {
+        let mut stats = MatchStats::new(None);
+        let blue = Touch { slot: 0, team: 0 };
+        let mut hit = |speed: f32| {
+            let mut f = frame(1.0, true);
+            f.ball.velocity = (0.0, speed, 0.0);
+            stats.observe(&f, Some(&blue));
+        };
+        hit(1000.0);
+        hit(3000.0);
+
+        let player = stats.player(0);
+        assert_eq!(player.touches, 2);
+        assert_eq!(player.avg_touch_speed(), Some(2000.0));
+        assert_eq!(player.hardest_touch_speed, 3000.0);
+        assert_eq!(stats.player(1).avg_touch_speed(), None);
+    }
+
+    #[test]
+    fn test_demos_skip_unattributed() {
+        let mut stats = MatchStats::new(None);
+        let mut f = frame(0.0, true);
+        f.demolitions = vec![
+            FrameDemolition {
+                attacker_slot: Some(0),
+                victim_slot: Some(2),
+            },
+            // goal explosion / self-demo
+            FrameDemolition {
+                attacker_slot: None,
+                victim_slot: Some(1),
+            },
+        ];
+        stats.observe(&f, None);
+
+        assert_eq!(stats.player(0).demos_inflicted, 1);
+        assert_eq!(stats.player(2).demos_taken, 1);
+        assert_eq!(stats.player(1).demos_taken, 0);
+        assert_eq!(stats.player(5).demos_inflicted, 0);
+    }
+
+    fn player(slot: usize, team: i64, y: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            team,
+            position: (0.0, y, 17.0),
+            ..test_support::player(slot)
+        }
+    }
+
+    #[test]
+    fn test_avg_boost_time_weighted() {
+        let mut stats = MatchStats::new(None);
+        let mut f = frame(0.0, true);
+        let with_boost = |slot, team, boost| PlayerSnapshot {
+            boost_amount: boost,
+            ..player(slot, team, 0.0)
+        };
+        f.players = vec![
+            with_boost(0, 0, 100),
+            with_boost(1, 0, 0),
+            with_boost(2, 1, 40),
+        ];
+        stats.observe(&f, None);
+        f.timestamp = 1.0;
+        f.players = vec![with_boost(0, 0, 20), with_boost(2, 1, 40)];
+        stats.observe(&f, None);
+        f.timestamp = 4.0;
+        f.is_live = false;
+        stats.observe(&f, None);
+        f.timestamp = 10.0; // dead time excluded
+        stats.observe(&f, None);
+
+        // Blue: mean 50 for 1 s, then 20 for 3 s.
+        assert!((stats.teams[0].avg_boost().unwrap() - 27.5).abs() < 1e-6);
+        assert!((stats.teams[1].avg_boost().unwrap() - 40.0).abs() < 1e-6);
+        assert!((stats.player(0).avg_boost().unwrap() - 40.0).abs() < 1e-6);
+        assert!((stats.player(1).avg_boost().unwrap() - 0.0).abs() < 1e-6);
+        assert_eq!(stats.player(3).avg_boost(), None);
+    }
+
+    fn collected(slot: usize, is_big: bool) -> FramePadEvent {
+        FramePadEvent {
+            event: PadEvent {
+                pad_id: 0,
+                is_big,
+                pad_side: "mid",
+                arena: "soccar",
+                arena_supported: true,
+                object_name: String::new(),
+                position: (0.0, 0.0, 70.0),
+                timestamp: 0.0,
+                raw_state: 1,
+                instigator_actor_id: Some(1),
+                resolved_actor_id: Some(1),
+                status: PadEventStatus::Collected,
+                respawn_s: 4.0,
+                snap_distance: None,
+                snap_error_uu: None,
+                emission_latency_s: 0.0,
+            },
+            player_slot: Some(slot),
+            player_team: Some(0),
+            boost_before: None,
+        }
+    }
+
+    #[test]
+    fn test_route_efficiency() {
+        let mut stats = MatchStats::new(None);
+        let mut f = frame(0.0, true);
+        for (t, y, boost) in [
+            (0.0, 0.0, 20),
+            (1.0, 400.0, 32),
+            (2.0, 800.0, 100),
+            (3.0, 4000.0, 100),
+            (4.0, 4400.0, 100),
+            (4.5, 5400.0, 100),
+        ] {
+            f.timestamp = t;
+            // Slot 0 drives up the field picking up two pads, respawning far ahead at t = 3
+            // and covering 1000 uu in the last half second; slot 1 stays put.
+            let driver = PlayerSnapshot {
+                boost_amount: boost,
+                ..player(0, 0, y)
+            };
+            f.players = vec![driver, player(1, 0, 0.0)];
+            f.pad_events = if t == 2.0 {
+                vec![collected(0, true), collected(0, false), collected(1, false)]
+            } else {
+                Vec::new()
+            };
+            stats.observe(&f, None);
+        }
+        f.timestamp = 5.0;
+        f.is_live = false;
+        f.players = vec![player(0, 0, 0.0)];
+        stats.observe(&f, None); // kickoff reset is not travel
+
+        let driver = stats.player(0);
+        assert!((driver.distance_uu - 2200.0).abs() < 1e-3);
+        assert_eq!((driver.big_pads, driver.small_pads), (1, 1));
+        assert_eq!(driver.boost_pickups, 2);
+        assert!((driver.pads_per_100uu().unwrap() - 2.0 / 22.0).abs() < 1e-6);
+        let parked = stats.player(1);
+        // A pad event without a boost rise is not a pickup.
+        assert_eq!((parked.small_pads, parked.boost_pickups), (1, 0));
+        assert_eq!(parked.pads_per_100uu(), None);
+    }
+
+    #[test]
+    fn test_boost_use_rate_and_empty_supersonic() {
+        let mut stats = MatchStats::new(None);
+        let mut f = frame(0.0, true);
+        let mut car = player(0, 0, 0.0);
+        let mut step = |stats: &mut MatchStats, t: f32, boost: i64, speed: f32| {
+            car.boost_amount = boost;
+            car.velocity = (speed, 0.0, 0.0);
+            f.timestamp = t;
+            f.players = vec![car.clone()];
+            stats.observe(&f, None);
+        };
+        step(&mut stats, 0.0, 40, 1400.0);
+        step(&mut stats, 0.2, 40, 1600.0);
+        step(&mut stats, 0.5, 30, 1900.0); // 10 used over 0.5 s
+        step(&mut stats, 0.8, 8, 2250.0); // 22 over 0.3 s; supersonic on empty
+        step(&mut stats, 1.0, 0, 2250.0);
+        step(&mut stats, 5.0, 100, 2250.0); // pad pickup
+        step(&mut stats, 9.0, 80, 2250.0); // 20 after a long gap: capped at 0.5 s
+        step(&mut stats, 9.5, 5, 2250.0); // empty again
+        step(&mut stats, 10.0, 5, 1000.0);
+
+        let totals = stats.player(0);
+        assert!((totals.boost_used - 135.0).abs() < 1e-6);
+        assert!((totals.boosting_seconds - 2.0).abs() < 1e-5);
+        assert!((totals.boost_use_rate().unwrap() - 67.5).abs() < 1e-3);
+        assert_eq!(totals.empty_supersonic_events, 2);
+        assert_eq!(stats.player(1).boost_use_rate(), None);
+    }
+
+    #[test]
+    fn test_last_man_back() {
+        let mut demolished = player(4, 0, -5000.0);
+        demolished.is_demolished = true;
+        let players = vec![
+            player(0, 0, -1000.0),
+            player(1, 0, -3000.0),
+            player(2, 1, 2000.0),
+            player(3, 1, 2000.0),
+            demolished,
+        ];
+        assert_eq!(last_man_back(&players), [Some(1), Some(2)]);
+        assert_eq!(last_man_back(&players[2..4]), [None, Some(2)]);
+
+        let mut stats = MatchStats::new(None);
+        let mut f = frame(0.0, true);
+        f.players = players;
+        stats.observe(&f, None);
+        f.timestamp = 2.5;
+        stats.observe(&f, None);
+        assert!((stats.player(1).last_man_seconds - 2.5).abs() < 1e-6);
+        assert!((stats.player(2).last_man_seconds - 2.5).abs() < 1e-6);
+        assert_eq!(stats.player(0).last_man_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_possession_probability() {
+        let mut f = frame_at(0.0, true, 0.0);
+        assert_eq!(possession_probability(None, &f), 0.5);
+        // Equidistant cars: only the touch tips it.
+        f.players = vec![player(0, 0, -1000.0), player(1, 1, 1000.0)];
+        assert!((possession_probability(Some(0), &f) - 0.2).abs() < 1e-6);
+        assert!((possession_probability(Some(1), &f) - 0.8).abs() < 1e-6);
+        // Orange pulls back toward even by crowding the ball.
+        f.players.push(player(2, 1, 0.0));
+        let crowded = possession_probability(Some(0), &f);
+        assert!(crowded > 0.2 && crowded < 0.5);
+    }
+
+    #[test]
+    fn test_goal_pressure() {
+        let mut f = frame_at(0.0, true, 0.0);
+        let [blue, orange] = goal_pressure(&f, SOCCAR_GOAL_CENTERS);
+        assert!((blue - 0.25).abs() < 1e-3 && (orange - 0.25).abs() < 1e-3);
+        // Ball racing at the orange goal with one of two blue cars in the attacking third.
+        f.ball.position = (0.0, 4000.0, 93.0);
+        f.ball.velocity = (0.0, 3000.0, 0.0);
+        f.players = vec![
+            player(0, 0, 3000.0),
+            player(1, 0, 0.0),
+            player(2, 1, 4500.0),
+        ];
+        let [blue, orange] = goal_pressure(&f, SOCCAR_GOAL_CENTERS);
+        let d = SOCCAR_GOAL_CENTERS[1].1 - 4000.0;
+        let expected = 0.5 * (1.0 - d / PRESSURE_DISTANCE_SCALE_UU) + 0.3 + 0.2 * 0.5;
+        assert!((orange - expected).abs() < 1e-3);
+        // Heading away from the blue goal: distance term only, and small.
+        assert!(blue < 0.1);
+    }
+
+    #[test]
+    fn test_goalside_seconds() {
+        let mut stats = MatchStats::new(None);
+        let orange = Touch { slot: 2, team: 1 };
+        let blue = Touch { slot: 1, team: 0 };
+        let mut f = frame_at(0.0, true, 0.0);
+        // Blue slot 0 behind the ball, blue slot 1 ahead of it; orange slot 2 attacking.
+        f.players = vec![
+            player(0, 0, -2000.0),
+            player(1, 0, 1000.0),
+            player(2, 1, 500.0),
+        ];
+        stats.observe(&f, Some(&orange));
+        f.timestamp = 2.0;
+        stats.observe(&f, None);
+        // Blue takes possession; orange slot 2 (y = 500) is goal-side of the ball at y = 0.
+        f.timestamp = 3.0;
+        stats.observe(&f, Some(&blue));
+        f.timestamp = 4.0;
+        stats.observe(&f, None);
+
+        assert!((stats.player(0).goalside_seconds - 3.0).abs() < 1e-6);
+        assert_eq!(stats.player(1).goalside_seconds, 0.0);
+        assert!((stats.player(2).goalside_seconds - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_supersonic_surface_split() {
+        let mut stats = MatchStats::new(None);
+        let mut f = frame(0.0, true);
+        let mut car = player(0, 0, 0.0);
+        let mut step = |stats: &mut MatchStats, t: f32, speed: f32, z: f32| {
+            car.velocity = (speed, 0.0, 0.0);
+            car.position.2 = z;
+            f.timestamp = t;
+            f.players = vec![car.clone()];
+            stats.observe(&f, None);
+        };
+        step(&mut stats, 0.0, 2250.0, 17.0); // enters supersonic on the ground
+        step(&mut stats, 1.0, 2150.0, 500.0); // still latched, now airborne
+        step(&mut stats, 3.0, 2150.0, 17.0);
+        step(&mut stats, 4.0, 2000.0, 17.0); // dropped out
+        step(&mut stats, 5.0, 2150.0, 17.0); // below the enter speed: not supersonic
+        step(&mut stats, 6.0, 2150.0, 17.0);
+
+        let totals = stats.player(0);
+        assert!((totals.supersonic_ground_seconds - 2.0).abs() < 1e-6);
+        assert!((totals.supersonic_air_seconds - 2.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,205 @@
+/// Team identity (custom name, club colours) replicated on the `Archetypes.Teams.Team0/1`
+/// actors. Only club / tournament matches set these; ranked replays leave them unset.
+use boxcars::{Attribute, Frame};
+use std::collections::HashMap;
+
+const ATTR_CUSTOM_TEAM_NAME: &str = "TAGame.Team_TA:CustomTeamName";
+const ATTR_CLUB_COLORS: &str = "TAGame.Team_TA:ClubColors";
+
+pub const DEFAULT_TEAM_NAMES: [&str; 2] = ["Blue", "Orange"];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TeamIdentity {
+    pub custom_name: Option<String>,
+    /// Palette index of the team's club colour: boxcars' `blue_color` for team 0 and
+    /// `orange_color` for team 1.
+    pub color: Option<u8>,
+}
+
+/// Team index for a team archetype object name, e.g. `Archetypes.Teams.Team1` → 1.
+pub fn team_index(object_name: &str) -> Option<usize> {
+    match object_name.strip_prefix("Archetypes.Teams.Team")? {
+        "0" => Some(0),
+        "1" => Some(1),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+pub struct TeamTracker {
+    /// Team actor → team index.
+    actors: HashMap<i32, usize>,
+    pub teams: [TeamIdentity; 2],
+}
+
+impl TeamTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn process_frame(&mut self, nf: &Frame, objects: &[String]) {
+        for deleted in &nf.deleted_actors {
+            self.actors.remove(&(*deleted).into());
+        }
+        for na in &nf.new_actors {
+            let oid: usize = na.object_id.into();
+            if let Some(team) = objects.get(oid).and_then(|name| team_index(name)) {
+                self.actors.insert(na.actor_id.into(), team);
+            }
+        }
+        for upd in &nf.updated_actors {
+            let Some(&team) = self.actors.get(&upd.actor_id.into()) else {
+                continue;
+            };
+            let oid: usize = upd.object_id.into();
+            let attr_name = objects.get(oid).map(|s| s.as_str()).unwrap_or("");
+            match (&upd.attribute, attr_name) {
+                (Attribute::String(name), ATTR_CUSTOM_TEAM_NAME) if !name.is_empty() => {
+                    self.teams[team].custom_name = Some(name.clone());
+                }
+                (Attribute::ClubColors(colors), ATTR_CLUB_COLORS) => {
+                    let (set, color) = if team == 0 {
+                        (colors.blue_flag, colors.blue_color)
+                    } else {
+                        (colors.orange_flag, colors.orange_color)
+                    };
+                    if set {
+                        self.teams[team].color = Some(color);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Position-based team guesses for cars without a replicated team (no TeamPaint). The
+/// first guess, orange at y > 0 and blue otherwise, holds for the rest of the actor's life,
+/// so a car crossing midfield keeps its team; a replicated team always wins. No guess is
+/// made before the car's first replicated position.
+#[derive(Clone, Default)]
+pub struct FallbackTeams {
+    /// Car actor → guessed team.
+    guesses: HashMap<i32, i64>,
+}
+
+impl FallbackTeams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Team of car `actor` at field position `y` (None until one has replicated):
+    /// `replicated` when known (>= 0), else the actor's sticky guess, else None.
+    pub fn team(&mut self, actor: i32, replicated: Option<i64>, y: Option<f32>) -> Option<i64> {
+        if let Some(team) = replicated.filter(|team| *team >= 0) {
+            return Some(team);
+        }
+        if let Some(guess) = self.guesses.get(&actor) {
+            return Some(*guess);
+        }
+        let guess = if y? > 0.0 { 1 } else { 0 };
+        self.guesses.insert(actor, guess);
+        Some(guess)
+    }
+
+    /// Forget a deleted actor's guess.
+    pub fn remove(&mut self, actor: i32) {
+        self.guesses.remove(&actor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boxcars::{
+        ActorId, ClubColors, NewActor, ObjectId, StreamId, Trajectory, UpdatedAttribute,
+    };
+
+    #[test]
+    fn test_team_identity_per_team() {
+        let objects: Vec<String> = [
+            "Archetypes.Teams.Team0",
+            "Archetypes.Teams.Team1",
+            ATTR_CUSTOM_TEAM_NAME,
+            ATTR_CLUB_COLORS,
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+        let spawn = |actor: i32, object: i32| NewActor {
+            actor_id: ActorId(actor),
+            name_id: None,
+            object_id: ObjectId(object),
+            initial_trajectory: Trajectory {
+                location: None,
+                rotation: None,
+            },
+        };
+        let update = |actor: i32, object: i32, attribute: Attribute| UpdatedAttribute {
+            actor_id: ActorId(actor),
+            stream_id: StreamId(0),
+            object_id: ObjectId(object),
+            attribute,
+        };
+        let colors = ClubColors {
+            blue_flag: true,
+            blue_color: 12,
+            orange_flag: true,
+            orange_color: 40,
+        };
+        let frame = Frame {
+            time: 0.0,
+            delta: 0.0,
+            new_actors: vec![spawn(1, 0), spawn(2, 1)],
+            deleted_actors: Vec::new(),
+            updated_actors: vec![
+                update(1, 2, Attribute::String("Dignitas".to_string())),
+                update(2, 2, Attribute::String("Geekay".to_string())),
+                update(1, 3, Attribute::ClubColors(colors)),
+                update(2, 3, Attribute::ClubColors(colors)),
+            ],
+        };
+        let mut tracker = TeamTracker::new();
+        tracker.process_frame(&frame, &objects);
+        assert_eq!(tracker.teams[0].custom_name.as_deref(), Some("Dignitas"));
+        assert_eq!(tracker.teams[1].custom_name.as_deref(), Some("Geekay"));
+        assert_eq!(
+            (tracker.teams[0].color, tracker.teams[1].color),
+            (Some(12), Some(40))
+        );
+    }
+
+    #[test]
+    fn test_team_index() {
+        assert_eq!(team_index("Archetypes.Teams.Team0"), Some(0));
+        assert_eq!(team_index("Archetypes.Teams.Team1"), Some(1));
+        assert_eq!(team_index("Archetypes.Teams.Team2"), None);
+        assert_eq!(team_index("TAGame.Team_Soccar_TA"), None);
+    }
+
+    #[test]
+    fn test_fallback_team_is_sticky() {
+        let mut teams = FallbackTeams::new();
+        // A paint-less car crossing midfield back and forth keeps its first guess.
+        for y in [-1500.0, 300.0, -20.0, 2500.0, -4000.0, 10.0] {
+            assert_eq!(teams.team(7, None, Some(y)), Some(0));
+        }
+        // TeamPaint arriving later is authoritative.
+        assert_eq!(teams.team(7, Some(1), Some(-4000.0)), Some(1));
+        // A new actor reusing the id guesses afresh.
+        teams.remove(7);
+        assert_eq!(teams.team(7, None, Some(800.0)), Some(1));
+        assert_eq!(teams.team(7, None, Some(-800.0)), Some(1));
+    }
+
+    #[test]
+    fn test_fallback_team_waits_for_position() {
+        let mut teams = FallbackTeams::new();
+        // No position yet: no guess, and nothing locked in.
+        assert_eq!(teams.team(3, None, None), None);
+        assert_eq!(teams.team(3, None, Some(2500.0)), Some(1));
+        assert_eq!(teams.team(3, None, None), Some(1));
+        // A replicated team needs no position.
+        assert_eq!(teams.team(4, Some(0), None), Some(0));
+    }
+}
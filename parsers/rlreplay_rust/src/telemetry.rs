@@ -0,0 +1,237 @@
+/// Per-second telemetry for compact charts: frames bucketed by the whole second of their
+/// timestamp.
+///
+/// Means are time-weighted as in `stats::MatchStats`: the interval since the previous frame
+/// is credited to the state held during it (the previous frame), split across the seconds
+/// it spans in proportion to the time spent in each, so the final frame holds for no time.
+/// Max ball speed and event counts are taken per frame, unweighted. Demolished cars are left
+/// out of the player means.
+use crate::geometry::magnitude;
+use crate::network::FrameSnapshot;
+use std::collections::BTreeMap;
+
+type Vec3 = (f32, f32, f32);
+
+#[derive(Clone, Copy, Debug, Default)]
+struct WeightedSums {
+    seconds: f64,
+    position: (f64, f64, f64),
+    /// Ball speed, or boost amount for a player.
+    value: f64,
+}
+
+impl WeightedSums {
+    fn add(&mut self, dt: f64, position: Vec3, value: f32) {
+        self.seconds += dt;
+        self.position.0 += position.0 as f64 * dt;
+        self.position.1 += position.1 as f64 * dt;
+        self.position.2 += position.2 as f64 * dt;
+        self.value += value as f64 * dt;
+    }
+
+    /// (mean position, mean value); None when no time was credited.
+    fn means(&self) -> Option<(Vec3, f32)> {
+        if self.seconds <= 0.0 {
+            return None;
+        }
+        let s = self.seconds;
+        let position = (
+            self.position.0 / s,
+            self.position.1 / s,
+            self.position.2 / s,
+        );
+        Some((
+            (position.0 as f32, position.1 as f32, position.2 as f32),
+            (self.value / s) as f32,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SecondBucket {
+    /// Whole second of the frame timestamps in the bucket.
+    pub second: i64,
+    ball: WeightedSums,
+    /// Slot → weighted position / boost sums.
+    players: BTreeMap<usize, WeightedSums>,
+    pub max_ball_speed: f32,
+    pub touches: u32,
+    pub goals: u32,
+    pub demos: u32,
+}
+
+impl SecondBucket {
+    /// Frame time (s) credited to the bucket.
+    pub fn seconds(&self) -> f32 {
+        self.ball.seconds as f32
+    }
+
+    /// Mean ball position and speed (uu/s); None when no time was credited.
+    pub fn ball_means(&self) -> Option<(Vec3, f32)> {
+        self.ball.means()
+    }
+
+    /// (slot, mean position, mean boost amount) of each player on the field during the
+    /// bucket, in slot order.
+    pub fn player_means(&self) -> Vec<(usize, Vec3, f32)> {
+        self.players
+            .iter()
+            .filter_map(|(slot, sums)| sums.means().map(|(p, boost)| (*slot, p, boost)))
+            .collect()
+    }
+}
+
+/// The previous frame's state, credited once the next frame's timestamp is known.
+struct Held {
+    timestamp: f32,
+    ball_position: Vec3,
+    ball_speed: f32,
+    /// (slot, position, boost amount) of the non-demolished cars.
+    players: Vec<(usize, Vec3, f32)>,
+}
+
+#[derive(Default)]
+pub struct TelemetryAggregator {
+    buckets: BTreeMap<i64, SecondBucket>,
+    held: Option<Held>,
+}
+
+impl TelemetryAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame, whether a touch / goal was detected on it.
+    pub fn observe(&mut self, frame: &FrameSnapshot, touch: bool, goal: bool) {
+        if let Some(held) = self.held.take() {
+            let end = frame.timestamp as f64;
+            let mut start = held.timestamp as f64;
+            while start < end {
+                let second = start.floor();
+                let until = end.min(second + 1.0);
+                self.credit(second as i64, until - start, &held);
+                start = until;
+            }
+        }
+
+        let second = frame.timestamp.floor() as i64;
+        let ball_speed = magnitude(frame.ball.velocity);
+        let bucket = self.bucket(second);
+        bucket.max_ball_speed = bucket.max_ball_speed.max(ball_speed);
+        bucket.touches += touch as u32;
+        bucket.goals += goal as u32;
+        bucket.demos += frame.demolitions.len() as u32;
+        self.held = Some(Held {
+            timestamp: frame.timestamp,
+            ball_position: frame.ball.position,
+            ball_speed,
+            players: frame
+                .players
+                .iter()
+                .filter(|p| !p.is_demolished)
+                .map(|p| (p.slot, p.position, p.boost_amount as f32))
+                .collect(),
+        });
+    }
+
+    /// The buckets in time order. Seconds before the first frame and after the last are
+    /// absent.
+    pub fn finish(self) -> Vec<SecondBucket> {
+        self.buckets.into_values().collect()
+    }
+
+    /// Credit `dt` seconds of `held` to the bucket for `second`.
+    fn credit(&mut self, second: i64, dt: f64, held: &Held) {
+        let bucket = self.bucket(second);
+        bucket.ball.add(dt, held.ball_position, held.ball_speed);
+        for &(slot, position, boost) in &held.players {
+            bucket
+                .players
+                .entry(slot)
+                .or_default()
+                .add(dt, position, boost);
+        }
+    }
+
+    fn bucket(&mut self, second: i64) -> &mut SecondBucket {
+        self.buckets.entry(second).or_insert_with(|| SecondBucket {
+            second,
+            ..SecondBucket::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, ball, player};
+    use crate::network::{BallSnapshot, FrameDemolition, PlayerSnapshot};
+
+    fn car(x: f32, boost: i64) -> PlayerSnapshot {
+        PlayerSnapshot {
+            position: (x, 0.0, 17.0),
+            boost_amount: boost,
+            ..player(0)
+        }
+    }
+
+    fn frame(timestamp: f32, ball_x: f32, ball_vx: f32, player: PlayerSnapshot) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position: (ball_x, 0.0, 93.0),
+                velocity: (ball_vx, 0.0, 0.0),
+                ..ball()
+            },
+            ..test_support::frame(timestamp, vec![player])
+        }
+    }
+
+    #[test]
+    fn test_dt_weighted_means() {
+        let mut telemetry = TelemetryAggregator::new();
+        // Held for 0.75 s at x = 0, then 0.25 s at x = 400.
+        telemetry.observe(&frame(0.0, 0.0, 1000.0, car(100.0, 100)), true, false);
+        telemetry.observe(&frame(0.75, 400.0, 2000.0, car(500.0, 0)), false, false);
+        let mut demo = frame(1.0, 800.0, 500.0, car(900.0, 50));
+        demo.demolitions.push(FrameDemolition {
+            attacker_slot: Some(0),
+            victim_slot: None,
+        });
+        telemetry.observe(&demo, false, true);
+        let buckets = telemetry.finish();
+        assert_eq!(buckets.len(), 2);
+
+        let first = &buckets[0];
+        assert_eq!((first.second, first.seconds()), (0, 1.0));
+        let ((x, _, z), speed) = first.ball_means().unwrap();
+        assert_eq!((x, z, speed), (100.0, 93.0, 1250.0));
+        assert_eq!(first.max_ball_speed, 2000.0);
+        assert_eq!(first.player_means(), vec![(0, (200.0, 0.0, 17.0), 75.0)]);
+        assert_eq!((first.touches, first.goals, first.demos), (1, 0, 0));
+
+        // The final frame holds for no time but still counts its events.
+        let second = &buckets[1];
+        assert_eq!(second.second, 1);
+        assert!(second.ball_means().is_none());
+        assert!(second.player_means().is_empty());
+        assert_eq!(second.max_ball_speed, 500.0);
+        assert_eq!((second.touches, second.goals, second.demos), (0, 1, 1));
+    }
+
+    #[test]
+    fn test_interval_split_across_seconds() {
+        let mut telemetry = TelemetryAggregator::new();
+        // Held from 0.5 s to 2.25 s: half a second in 0, all of 1 and a quarter in 2.
+        telemetry.observe(&frame(0.5, 0.0, 1000.0, car(100.0, 100)), false, false);
+        telemetry.observe(&frame(2.25, 400.0, 0.0, car(500.0, 0)), false, false);
+        telemetry.observe(&frame(2.5, 800.0, 0.0, car(900.0, 0)), false, false);
+        let buckets = telemetry.finish();
+        let durations: Vec<(i64, f32)> = buckets.iter().map(|b| (b.second, b.seconds())).collect();
+        assert_eq!(durations, vec![(0, 0.5), (1, 1.0), (2, 0.5)]);
+        // The gap second holds the earlier frame's state without a frame of its own.
+        assert_eq!(buckets[1].ball_means(), Some(((0.0, 0.0, 93.0), 1000.0)));
+        assert_eq!(buckets[1].max_ball_speed, 0.0);
+        let ((x, _, _), speed) = buckets[2].ball_means().unwrap();
+        assert_eq!((x, speed), (200.0, 500.0));
+    }
+}
@@ -0,0 +1,117 @@
+/// Opt-in per-call phase timings, for telling decode cost apart from frame / PyObject
+/// construction cost when profiling batch jobs.
+///
+/// Disabled by default; while disabled `CallTimer` never reads the clock. Only the most
+/// recent instrumented call is kept (process-wide, last writer wins across threads).
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LAST: Mutex<Option<ParseTimings>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParseTimings {
+    pub function: &'static str,
+    /// Reading the file into memory.
+    pub read_s: f64,
+    /// boxcars header + network decode.
+    pub decode_s: f64,
+    /// Frame state processing and Python object construction.
+    pub frames_s: f64,
+}
+
+impl ParseTimings {
+    pub fn total_s(&self) -> f64 {
+        self.read_s + self.decode_s + self.frames_s
+    }
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        store(None);
+    }
+}
+
+pub fn last() -> Option<ParseTimings> {
+    LAST.lock().map_or(None, |last| *last)
+}
+
+fn store(timings: Option<ParseTimings>) {
+    match LAST.lock() {
+        Ok(mut last) => *last = timings,
+        Err(poisoned) => *poisoned.into_inner() = timings,
+    }
+}
+
+/// Phase timer for one call: `start`, then `read_done` and `decode_done` as the phases
+/// end, then `finish` once the result is built. Calls that fail before `finish` leave the
+/// previous timings in place.
+pub struct CallTimer {
+    function: &'static str,
+    mark: Option<Instant>,
+    read_s: f64,
+    decode_s: f64,
+}
+
+impl CallTimer {
+    pub fn start(function: &'static str) -> Self {
+        CallTimer {
+            function,
+            mark: ENABLED.load(Ordering::Relaxed).then(Instant::now),
+            read_s: 0.0,
+            decode_s: 0.0,
+        }
+    }
+
+    /// Seconds since the previous mark, resetting the mark; 0 when disabled.
+    fn lap(&mut self) -> f64 {
+        let Some(mark) = self.mark else {
+            return 0.0;
+        };
+        let now = Instant::now();
+        self.mark = Some(now);
+        now.duration_since(mark).as_secs_f64()
+    }
+
+    pub fn read_done(&mut self) {
+        self.read_s = self.lap();
+    }
+
+    pub fn decode_done(&mut self) {
+        self.decode_s = self.lap();
+    }
+
+    pub fn finish(mut self) {
+        if self.mark.is_none() {
+            return;
+        }
+        let frames_s = self.lap();
+        store(Some(ParseTimings {
+            function: self.function,
+            read_s: self.read_s,
+            decode_s: self.decode_s,
+            frames_s,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_timer_records_nothing() {
+        let mut timer = CallTimer {
+            function: "test",
+            mark: None,
+            read_s: 0.0,
+            decode_s: 0.0,
+        };
+        timer.read_done();
+        timer.decode_done();
+        assert_eq!(timer.read_s, 0.0);
+        assert_eq!(timer.decode_s, 0.0);
+    }
+}
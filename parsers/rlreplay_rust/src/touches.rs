@@ -0,0 +1,67 @@
+/// Proximity + impulse based ball touch detection.
+///
+/// A touch is registered on a live frame when the ball's velocity changes by at least
+/// `min_velocity_delta` (default `MIN_VELOCITY_DELTA_UU_S`) since the previous frame and the
+/// nearest non-demolished car is within `touch_distance_uu` (default `TOUCH_DISTANCE_UU`)
+/// of the ball centre. Gravity alone changes the ball's vertical velocity by ~22 uu/s per
+/// 30 Hz frame, well under the impulse threshold.
+use crate::geometry::{distance, magnitude};
+use crate::network::FrameSnapshot;
+
+/// Default maximum car-centre to ball-centre distance (uu) for a velocity change to count
+/// as a touch.
+pub const TOUCH_DISTANCE_UU: f32 = 300.0;
+/// Default minimum frame-to-frame ball velocity change (uu/s) treated as an impulse.
+pub const MIN_VELOCITY_DELTA_UU_S: f32 = 100.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Touch {
+    pub slot: usize,
+    pub team: i64,
+}
+
+pub struct TouchDetector {
+    touch_distance_uu: f32,
+    min_velocity_delta: f32,
+    prev_ball_velocity: Option<(f32, f32, f32)>,
+}
+
+impl TouchDetector {
+    pub fn new(touch_distance_uu: f32, min_velocity_delta: f32) -> Self {
+        TouchDetector {
+            touch_distance_uu,
+            min_velocity_delta,
+            prev_ball_velocity: None,
+        }
+    }
+
+    /// Feed the next frame; returns the touch registered on it, if any.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> Option<Touch> {
+        let velocity = frame.ball.velocity;
+        let prev = self.prev_ball_velocity.replace(velocity);
+        if !frame.is_live {
+            return None;
+        }
+        let prev = prev?;
+        let delta = magnitude((
+            velocity.0 - prev.0,
+            velocity.1 - prev.1,
+            velocity.2 - prev.2,
+        ));
+        if delta < self.min_velocity_delta {
+            return None;
+        }
+        let ball = frame.ball.position;
+        frame
+            .players
+            .iter()
+            .filter(|p| !p.is_demolished)
+            .map(|p| (p, distance(p.position, ball)))
+            .filter(|(_, d)| *d <= self.touch_distance_uu)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(p, _)| Touch {
+                slot: p.slot,
+                team: p.team,
+            })
+    }
+}
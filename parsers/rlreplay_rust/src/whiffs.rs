@@ -0,0 +1,210 @@
+/// Whiff (missed touch) detection.
+///
+/// An approach starts on a live frame where a non-demolished car comes within
+/// `WHIFF_DISTANCE_UU` of the ball (centre to centre) while closing on it at
+/// `WHIFF_MIN_APPROACH_SPEED_UU_S` or faster. It ends when the car moves back out of that
+/// range, and is a whiff unless the ball's velocity changed by `MIN_VELOCITY_DELTA_UU_S`
+/// (the touch detector's impulse check) on any frame of it. Any impulse counts, so a ball
+/// hit by another player or bouncing during the approach is not a whiff. Approaches cut
+/// short by a demolition or dead ball are dropped.
+use crate::geometry::{distance, magnitude};
+use crate::network::{FrameSnapshot, PlayerSnapshot};
+use crate::touches::MIN_VELOCITY_DELTA_UU_S;
+use std::collections::HashMap;
+
+/// Car-centre to ball-centre distance (uu) within which a car is close enough to touch.
+pub const WHIFF_DISTANCE_UU: f32 = 250.0;
+/// Min closing speed (uu/s) on the ball for an approach to count as going for it.
+pub const WHIFF_MIN_APPROACH_SPEED_UU_S: f32 = 500.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Whiff {
+    /// Time of the closest approach.
+    pub timestamp: f32,
+    pub slot: usize,
+    /// Closest car-centre to ball-centre distance (uu).
+    pub closest_distance: f32,
+    /// Closing speed (uu/s) on the ball when the car came within range.
+    pub approach_speed: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Approach {
+    closest_distance: f32,
+    closest_time: f32,
+    approach_speed: f32,
+    ball_moved: bool,
+}
+
+#[derive(Default)]
+pub struct WhiffDetector {
+    prev_ball_velocity: Option<(f32, f32, f32)>,
+    /// Slot → approach in progress.
+    approaches: HashMap<usize, Approach>,
+}
+
+impl WhiffDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next frame; returns the whiffs that ended on it, in slot order.
+    pub fn observe(&mut self, frame: &FrameSnapshot) -> Vec<Whiff> {
+        let velocity = frame.ball.velocity;
+        let prev = self.prev_ball_velocity.replace(velocity);
+        if !frame.is_live {
+            self.approaches.clear();
+            return Vec::new();
+        }
+        let impulse = prev.is_some_and(|prev| {
+            let delta = (
+                velocity.0 - prev.0,
+                velocity.1 - prev.1,
+                velocity.2 - prev.2,
+            );
+            magnitude(delta) >= MIN_VELOCITY_DELTA_UU_S
+        });
+        if impulse {
+            for approach in self.approaches.values_mut() {
+                approach.ball_moved = true;
+            }
+        }
+
+        let ball = frame.ball.position;
+        let mut whiffs = Vec::new();
+        for player in &frame.players {
+            if player.is_demolished {
+                self.approaches.remove(&player.slot);
+                continue;
+            }
+            let dist = distance(player.position, ball);
+            if dist > WHIFF_DISTANCE_UU {
+                if let Some(approach) = self.approaches.remove(&player.slot) {
+                    if !approach.ball_moved {
+                        whiffs.push(Whiff {
+                            timestamp: approach.closest_time,
+                            slot: player.slot,
+                            closest_distance: approach.closest_distance,
+                            approach_speed: approach.approach_speed,
+                        });
+                    }
+                }
+                continue;
+            }
+            if let Some(approach) = self.approaches.get_mut(&player.slot) {
+                if dist < approach.closest_distance {
+                    approach.closest_distance = dist;
+                    approach.closest_time = frame.timestamp;
+                }
+                continue;
+            }
+            let closing = closing_speed(player, frame.ball.position, velocity);
+            if closing >= WHIFF_MIN_APPROACH_SPEED_UU_S {
+                self.approaches.insert(
+                    player.slot,
+                    Approach {
+                        closest_distance: dist,
+                        closest_time: frame.timestamp,
+                        approach_speed: closing,
+                        ball_moved: impulse,
+                    },
+                );
+            }
+        }
+        // Cars that left the field mid-approach.
+        self.approaches
+            .retain(|slot, _| frame.players.iter().any(|p| p.slot == *slot));
+        whiffs
+    }
+}
+
+/// Speed (uu/s) at which `car` closes on the ball: their relative velocity along the line
+/// from the car to the ball.
+pub fn closing_speed(
+    car: &PlayerSnapshot,
+    ball_position: (f32, f32, f32),
+    ball_velocity: (f32, f32, f32),
+) -> f32 {
+    let dist = distance(car.position, ball_position);
+    if dist == 0.0 {
+        return 0.0;
+    }
+    let (p, v) = (car.position, car.velocity);
+    let to_ball = (
+        ball_position.0 - p.0,
+        ball_position.1 - p.1,
+        ball_position.2 - p.2,
+    );
+    let relative = (
+        v.0 - ball_velocity.0,
+        v.1 - ball_velocity.1,
+        v.2 - ball_velocity.2,
+    );
+    (relative.0 * to_ball.0 + relative.1 * to_ball.1 + relative.2 * to_ball.2) / dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::test_support::{self, ball, player};
+    use crate::network::BallSnapshot;
+
+    fn car(x: f32, vx: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            position: (x, 0.0, 17.0),
+            velocity: (vx, 0.0, 0.0),
+            ..player(0)
+        }
+    }
+
+    fn frame(timestamp: f32, player: PlayerSnapshot, ball_vx: f32) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                velocity: (ball_vx, 0.0, 0.0),
+                ..ball()
+            },
+            ..test_support::frame(timestamp, vec![player])
+        }
+    }
+
+    #[test]
+    fn test_whiff_past_resting_ball() {
+        let mut whiffs = WhiffDetector::new();
+        assert!(whiffs
+            .observe(&frame(1.0, car(-400.0, 1500.0), 0.0))
+            .is_empty());
+        assert!(whiffs
+            .observe(&frame(1.1, car(-200.0, 1500.0), 0.0))
+            .is_empty());
+        assert!(whiffs
+            .observe(&frame(1.2, car(-100.0, 1500.0), 0.0))
+            .is_empty());
+        let found = whiffs.observe(&frame(1.3, car(300.0, 1500.0), 0.0));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].timestamp, 1.2);
+        let to_ball = |x: f32| distance((x, 0.0, 17.0), ball().position);
+        assert!((found[0].closest_distance - to_ball(-100.0)).abs() < 1e-3);
+        // Only the component of the car's velocity along the line to the ball closes.
+        let closing = 1500.0 * 200.0 / to_ball(-200.0);
+        assert!((found[0].approach_speed - closing).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_touch_and_slow_approach_are_not_whiffs() {
+        let mut whiffs = WhiffDetector::new();
+        whiffs.observe(&frame(1.0, car(-400.0, 1500.0), 0.0));
+        whiffs.observe(&frame(1.1, car(-200.0, 1500.0), 0.0));
+        // The ball takes an impulse while the car is in range.
+        whiffs.observe(&frame(1.2, car(-100.0, 1500.0), 1800.0));
+        assert!(whiffs
+            .observe(&frame(1.3, car(300.0, 1500.0), 1800.0))
+            .is_empty());
+
+        let mut whiffs = WhiffDetector::new();
+        whiffs.observe(&frame(1.0, car(-400.0, 200.0), 0.0));
+        whiffs.observe(&frame(1.1, car(-200.0, 200.0), 0.0));
+        assert!(whiffs
+            .observe(&frame(1.2, car(300.0, 200.0), 0.0))
+            .is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+/// Woodwork detection: the ball striking a goal post or the crossbar.
+///
+/// The goal frame is modelled as line segments: two posts rising from the goal line at
+/// x = ±`GOAL_HALF_WIDTH` up to `GOAL_HEIGHT`, and the crossbar joining their tops. A hit
+/// is registered on a live frame without a touch when the ball's velocity changes by at
+/// least `MIN_VELOCITY_DELTA_UU_S` (the touch impulse check) while the ball centre is within
+/// `BALL_RADIUS` + `WOODWORK_TOLERANCE_UU` of a segment; the nearest segment is the part
+/// hit. A repeat on the same part within `WOODWORK_COOLDOWN_S` is the same contact.
+///
+/// Posts are named from the attacker's point of view, facing the goal. The frame is
+/// left-handed: the left post of the orange (+y) goal is at +x, that of the blue (-y) goal
+/// at -x.
+use crate::geometry::{distance, magnitude, BALL_RADIUS, GOAL_HALF_WIDTH, GOAL_HEIGHT};
+use crate::network::FrameSnapshot;
+use crate::touches::{Touch, MIN_VELOCITY_DELTA_UU_S};
+
+/// Slack (uu) beyond the ball radius between the ball centre and a post / crossbar axis.
+pub const WOODWORK_TOLERANCE_UU: f32 = 40.0;
+/// Seconds during which the same part is not reported again.
+pub const WOODWORK_COOLDOWN_S: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WoodworkPart {
+    LeftPost,
+    RightPost,
+    Crossbar,
+}
+
+impl WoodworkPart {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WoodworkPart::LeftPost => "left_post",
+            WoodworkPart::RightPost => "right_post",
+            WoodworkPart::Crossbar => "crossbar",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WoodworkHit {
+    pub timestamp: f32,
+    /// Team whose goal was hit: 0 for the blue (-y) goal, 1 for orange (+y).
+    pub goal_team: i64,
+    pub part: WoodworkPart,
+    /// Ball speed (uu/s) on the frame before the hit.
+    pub ball_speed: f32,
+}
+
+pub struct WoodworkDetector {
+    /// (x, y) goal line centres, blue goal first.
+    goal_centers: [(f32, f32); 2],
+    prev_ball_velocity: Option<(f32, f32, f32)>,
+    last_hit: Option<(i64, WoodworkPart, f32)>,
+}
+
+impl WoodworkDetector {
+    pub fn new(goal_centers: [(f32, f32); 2]) -> Self {
+        WoodworkDetector {
+            goal_centers,
+            prev_ball_velocity: None,
+            last_hit: None,
+        }
+    }
+
+    /// Feed the next frame and the touch detected on it; returns the hit on it, if any.
+    pub fn observe(&mut self, frame: &FrameSnapshot, touch: Option<&Touch>) -> Option<WoodworkHit> {
+        let velocity = frame.ball.velocity;
+        let prev = self.prev_ball_velocity.replace(velocity);
+        if !frame.is_live || touch.is_some() {
+            return None;
+        }
+        let prev = prev?;
+        let delta = (
+            velocity.0 - prev.0,
+            velocity.1 - prev.1,
+            velocity.2 - prev.2,
+        );
+        if magnitude(delta) < MIN_VELOCITY_DELTA_UU_S {
+            return None;
+        }
+        let ball = frame.ball.position;
+        let (goal_team, part, dist) = (0..2)
+            .flat_map(|goal| {
+                self.parts(goal)
+                    .map(|(part, a, b)| (goal as i64, part, segment_distance(ball, a, b)))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))?;
+        if dist > BALL_RADIUS + WOODWORK_TOLERANCE_UU {
+            return None;
+        }
+        if let Some((last_goal, last_part, last_time)) = self.last_hit {
+            if (last_goal, last_part) == (goal_team, part)
+                && frame.timestamp - last_time < WOODWORK_COOLDOWN_S
+            {
+                return None;
+            }
+        }
+        self.last_hit = Some((goal_team, part, frame.timestamp));
+        Some(WoodworkHit {
+            timestamp: frame.timestamp,
+            goal_team,
+            part,
+            ball_speed: magnitude(prev),
+        })
+    }
+
+    /// The post and crossbar segments of goal `goal` (0 blue, 1 orange).
+    #[allow(clippy::type_complexity)]
+    fn parts(&self, goal: usize) -> [(WoodworkPart, (f32, f32, f32), (f32, f32, f32)); 3] {
+        let (cx, cy) = self.goal_centers[goal];
+        // Facing +y the attacker's left is +x; attacking the blue goal faces -y, so -x.
+        let left = if goal == 0 { -1.0 } else { 1.0 } * GOAL_HALF_WIDTH;
+        let right = -left;
+        [
+            (
+                WoodworkPart::LeftPost,
+                (cx + left, cy, 0.0),
+                (cx + left, cy, GOAL_HEIGHT),
+            ),
+            (
+                WoodworkPart::RightPost,
+                (cx + right, cy, 0.0),
+                (cx + right, cy, GOAL_HEIGHT),
+            ),
+            (
+                WoodworkPart::Crossbar,
+                (cx + left, cy, GOAL_HEIGHT),
+                (cx + right, cy, GOAL_HEIGHT),
+            ),
+        ]
+    }
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: (f32, f32, f32), a: (f32, f32, f32), b: (f32, f32, f32)) -> f32 {
+    let ab = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+    let ap = (p.0 - a.0, p.1 - a.1, p.2 - a.2);
+    let len2 = ab.0 * ab.0 + ab.1 * ab.1 + ab.2 * ab.2;
+    let t = if len2 > 0.0 {
+        ((ap.0 * ab.0 + ap.1 * ab.1 + ap.2 * ab.2) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    distance(p, (a.0 + ab.0 * t, a.1 + ab.1 * t, a.2 + ab.2 * t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena_tables::SOCCAR_GOAL_CENTERS;
+    use crate::network::test_support::{self, ball};
+    use crate::network::BallSnapshot;
+
+    fn frame(
+        timestamp: f32,
+        position: (f32, f32, f32),
+        velocity: (f32, f32, f32),
+    ) -> FrameSnapshot {
+        FrameSnapshot {
+            ball: BallSnapshot {
+                position,
+                velocity,
+                ..ball()
+            },
+            ..test_support::frame(timestamp, Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_crossbar_and_post() {
+        let mut woodwork = WoodworkDetector::new(SOCCAR_GOAL_CENTERS);
+        woodwork.observe(&frame(1.0, (0.0, 4900.0, 680.0), (0.0, 2000.0, 0.0)), None);
+        let hit = woodwork
+            .observe(
+                &frame(1.03, (0.0, 5050.0, 700.0), (0.0, -1200.0, 300.0)),
+                None,
+            )
+            .unwrap();
+        assert_eq!((hit.goal_team, hit.part), (1, WoodworkPart::Crossbar));
+        assert_eq!(hit.ball_speed, 2000.0);
+        // Still rebounding next frame: the same contact.
+        assert!(woodwork
+            .observe(
+                &frame(1.06, (0.0, 5010.0, 710.0), (0.0, -1300.0, 300.0)),
+                None
+            )
+            .is_none());
+
+        // Orange attacking the blue goal: +x is their right.
+        let mut woodwork = WoodworkDetector::new(SOCCAR_GOAL_CENTERS);
+        woodwork.observe(
+            &frame(2.0, (700.0, -4900.0, 200.0), (0.0, -1500.0, 0.0)),
+            None,
+        );
+        let hit = woodwork
+            .observe(
+                &frame(2.03, (830.0, -5060.0, 200.0), (900.0, 1000.0, 0.0)),
+                None,
+            )
+            .unwrap();
+        assert_eq!((hit.goal_team, hit.part), (0, WoodworkPart::RightPost));
+    }
+
+    #[test]
+    fn test_touch_or_far_from_goal_is_not_woodwork() {
+        let mut woodwork = WoodworkDetector::new(SOCCAR_GOAL_CENTERS);
+        let touch = Touch { slot: 0, team: 0 };
+        woodwork.observe(&frame(1.0, (0.0, 4900.0, 680.0), (0.0, 2000.0, 0.0)), None);
+        let f = frame(1.03, (0.0, 5050.0, 700.0), (0.0, -1200.0, 300.0));
+        assert!(woodwork.observe(&f, Some(&touch)).is_none());
+
+        let mut woodwork = WoodworkDetector::new(SOCCAR_GOAL_CENTERS);
+        woodwork.observe(&frame(1.0, (0.0, 0.0, 93.0), (0.0, 2000.0, 0.0)), None);
+        let f = frame(1.03, (0.0, 50.0, 93.0), (0.0, -1200.0, 0.0));
+        assert!(woodwork.observe(&f, None).is_none());
+    }
+}
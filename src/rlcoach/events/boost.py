@@ -136,9 +136,11 @@ def _detect_boost_pickups_from_pad_events(
             event_time = getattr(event, "timestamp", None)
             timestamp = float(event_time) if event_time is not None else frame.timestamp
 
-            respawn_window = (
-                BIG_PAD_RESPAWN_S if pad_meta.is_big else SMALL_PAD_RESPAWN_S
-            )
+            respawn_window = getattr(event, "respawn_s", None)
+            if respawn_window is None:
+                respawn_window = (
+                    BIG_PAD_RESPAWN_S if pad_meta.is_big else SMALL_PAD_RESPAWN_S
+                )
             last_collect = pad_last_collect.get(pad_meta.pad_id)
             if last_collect is not None and (timestamp - last_collect) < (
                 respawn_window - PAD_RESPAWN_TOLERANCE
@@ -45,6 +45,7 @@ BOOST_HISTORY_WINDOW_S = 0.45
 BOOST_HISTORY_MAX_SAMPLES = 18
 BIG_PAD_EXTRA_RADIUS = 220.0
 SMALL_PAD_EXTRA_RADIUS = 140.0
+# Soccar respawn defaults; parser pad events carry per-arena `respawn_s` when known.
 BIG_PAD_RESPAWN_S = 10.0
 SMALL_PAD_RESPAWN_S = 4.0
 PAD_RESPAWN_TOLERANCE = 0.15
@@ -689,6 +689,14 @@ def build_timeline(header: Header, frames: list[Any]) -> list[Frame]:
                 except (TypeError, ValueError):
                     event_time = None
 
+                respawn_value = raw_event.get("respawn_s")
+                try:
+                    respawn_s = (
+                        float(respawn_value) if respawn_value is not None else None
+                    )
+                except (TypeError, ValueError):
+                    respawn_s = None
+
                 object_name = raw_event.get("object_name")
                 object_name_str = (
                     str(object_name)
@@ -714,6 +722,7 @@ def build_timeline(header: Header, frames: list[Any]) -> list[Frame]:
                         position=position,
                         timestamp=event_time,
                         object_name=object_name_str,
+                        respawn_s=respawn_s,
                     )
                 )
 
@@ -401,7 +401,7 @@ class RustAdapter(ParserAdapter):
             else:
                 frames_payload = _rust.iter_frames(str(path))
                 parsed = {
-                    "frames": frames_payload,
+                    "frames": frames_payload["frames"],
                     "diagnostics": {
                         "status": "ok",
                         "error_code": None,
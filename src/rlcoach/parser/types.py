@@ -207,6 +207,7 @@ class BoostPadEventFrame:
     position: Vec3 | None = None
     timestamp: float | None = None
     object_name: str | None = None
+    respawn_s: float | None = None  # arena-specific; None -> Soccar defaults
 
 
 @dataclass(frozen=True)
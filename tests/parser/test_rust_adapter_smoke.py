@@ -67,3 +67,40 @@ def test_ltm_replay_reports_parse_reason_not_silent_none():
     assert hasattr(nf, "diagnostics")
     diagnostics = nf.diagnostics
     assert diagnostics is not None
+
+
+def _corrupt_replay(tmp_path):
+    data = bytearray(Path("testing_replay.replay").read_bytes())
+    # Flip a byte in the body so only the CRC notices.
+    data[-200] ^= 0xFF
+    path = tmp_path / "corrupt.replay"
+    path.write_bytes(bytes(data))
+    return str(path)
+
+
+@pytest.mark.skipif(not _has_rust_core(), reason="Rust core not available")
+def test_header_crc_failure_is_a_quality_warning(tmp_path):
+    import rlreplay_rust  # type: ignore
+
+    path = _corrupt_replay(tmp_path)
+
+    header = rlreplay_rust.parse_header(path, check_crc=True)
+    assert "crc_failed" in header["quality_warnings"]
+    # Without check_crc the body CRC is never consulted.
+    header = rlreplay_rust.parse_header(path)
+    assert "crc_failed" not in header["quality_warnings"]
+
+
+@pytest.mark.skipif(not _has_rust_core(), reason="Rust core not available")
+def test_network_crc_failure_raises_replay_format_error(tmp_path):
+    import rlreplay_rust  # type: ignore
+
+    path = _corrupt_replay(tmp_path)
+
+    with pytest.raises(rlreplay_rust.ReplayFormatError, match="crc_failed"):
+        rlreplay_rust.iter_frames(path, check_crc=True)
+    with pytest.raises(ValueError):
+        rlreplay_rust.parse_all(path, check_crc=True)
+
+    result = rlreplay_rust.parse_network_with_diagnostics(path, check_crc=True)
+    assert result["diagnostics"]["error_code"] == "crc_failed"